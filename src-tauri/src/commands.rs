@@ -2,17 +2,21 @@
 //!
 //! These commands implement the "Dumb UI, Smart Backend" architecture.
 
+use crate::constants::{api_base_url, HEALTH_CHECK_TIMEOUT_SECS};
 use crate::error::{CommandError, FileError};
 use crate::models::{
-    AppConfig, AppStatus, CategoryCount, DownloadedFile, Resource, ResourceListResponse,
-    SavingsStats, WeekIdentifier,
+    AppConfig, AppStatus, CategoryCount, DownloadHistoryEntry, DownloadedFile, FolderLayout,
+    Resource, ResourceListResponse, SavingsStats, WeekIdentifier,
 };
-use crate::services::download::{STATUS_CANCELLED, STATUS_PAUSED};
+use crate::services::download::{
+    ActiveProgress, STATUS_CANCELLED, STATUS_CANCELLED_KEEP_PARTIAL, STATUS_PAUSED, STATUS_STOPPED,
+};
+use crate::services::size_cache::{CachedSize, FileSizeCache};
 use crate::services::{DownloadQueue, PollingService, RetentionScheduler};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
 use std::sync::{Arc, RwLock};
 use tauri::{AppHandle, Manager, State};
 
@@ -35,11 +39,16 @@ pub struct AppState {
     /// worker upserts an entry on each successful download and the errata
     /// pass (`services::errata::process_errata`) marks entries superseded.
     pub downloaded_files: RwLock<Vec<crate::models::DownloadedFile>>,
+    /// Completed-download audit trail, oldest first. Loaded from
+    /// `history.json` at setup and appended by the queue worker through
+    /// `services::history::record_download`.
+    pub download_history: RwLock<Vec<DownloadHistoryEntry>>,
     /// Download queue service
     pub download_queue: Arc<DownloadQueue>,
-    /// Cache for file sizes (keyed by download_url)
-    /// Note: u64::MAX is used as a sentinel value for failed requests (negative cache)
-    pub file_size_cache: RwLock<HashMap<String, u64>>,
+    /// Cache for file sizes (keyed by download_url), bounded with LRU
+    /// eviction. Failed lookups are negative-cached for a few minutes (see
+    /// `services::size_cache`)
+    pub file_size_cache: RwLock<FileSizeCache>,
     /// Persistent global counter of bytes saved by optimized downloads (A2).
     /// Loaded from the `stats` key of `settings.json` at setup (`lib.rs`) and
     /// incremented/persisted by `add_saved_bytes` as each optimized download
@@ -47,8 +56,16 @@ pub struct AppState {
     /// (not the full `SavingsStats` struct) so incrementing never needs to
     /// hold the lock across a struct rebuild.
     pub stats: RwLock<u64>,
-    /// Shared HTTP client for all requests (connection pooling)
-    pub shared_http_client: reqwest::Client,
+    /// Shared HTTP client for all requests (connection pooling). Built by
+    /// `services::http::build_http_client` with the app User-Agent,
+    /// connect/read timeouts and the `request_timeout_secs` total timeout.
+    /// Behind a lock because `set_config` swaps in a rebuilt client when
+    /// `proxy_url` changes; read it through [`AppState::http_client`].
+    pub shared_http_client: RwLock<reqwest::Client>,
+    /// Same as `shared_http_client` but without the total timeout, so big
+    /// files aren't cut off; the queue worker hands a clone of it to each
+    /// `DownloadService`. Read it through [`AppState::download_client`].
+    pub download_http_client: RwLock<reqwest::Client>,
     /// Handle to the background polling scheduler (`None` if
     /// `polling_enabled` is off), so it can be stopped cleanly on app exit
     /// (tray menu "Esci"). Set once at setup, taken and stopped on shutdown.
@@ -63,6 +80,28 @@ pub struct AppState {
     /// (process exits) instead of hiding to a tray icon that doesn't exist,
     /// which would otherwise strand the user with no way to reopen the app.
     pub tray_available: AtomicBool,
+    /// Batches download-complete notifications (see `services::notifications`).
+    pub completion_notifier: crate::services::notifications::CompletionNotifier,
+    /// Last failure message per resource id, so a failure survives the
+    /// transient `download-failed` event (window closed, UI reloaded).
+    /// Recorded by the queue worker on failure, cleared when the resource's
+    /// next download attempt starts. In memory only.
+    pub download_errors: RwLock<HashMap<i64, String>>,
+    /// Generation of the running `estimate_download_size`. Bumped by each new
+    /// estimate and by `cancel_download_size_estimate`; an estimate whose
+    /// generation is no longer current stops issuing HEAD requests.
+    pub size_estimate_generation: AtomicU64,
+    /// Bumped by `cancel_poll`; a `force_poll` waiting on the network gives
+    /// up as soon as it sees the value change.
+    pub poll_cancel: tokio::sync::watch::Sender<u64>,
+    /// Last progress of each active download, updated alongside every
+    /// `download-progress` event and dropped by the queue worker when the
+    /// download ends (any outcome). Read by `get_active_progress`.
+    pub active_progress: RwLock<HashMap<i64, ActiveProgress>>,
+    /// Seconds the API last asked polling to hold off (`Retry-After` on a
+    /// throttled poll), 0 when it hasn't. Set by `poll_once` and taken by the
+    /// polling loop before its next attempt.
+    pub poll_retry_after_secs: AtomicU64,
 }
 
 /// Response for download command
@@ -92,13 +131,115 @@ impl Default for AppState {
             all_categories: RwLock::new(Vec::new()),
             download_signals: RwLock::new(HashMap::new()),
             downloaded_files: RwLock::new(Vec::new()),
+            download_history: RwLock::new(Vec::new()),
             download_queue: Arc::new(DownloadQueue::new()),
-            file_size_cache: RwLock::new(HashMap::new()),
+            file_size_cache: RwLock::new(FileSizeCache::new()),
             stats: RwLock::new(0),
-            shared_http_client: reqwest::Client::new(),
+            shared_http_client: RwLock::new(crate::services::http::build_http_client(
+                None,
+                &HashMap::new(),
+                Some(AppConfig::default().request_timeout()),
+            )),
+            download_http_client: RwLock::new(crate::services::http::build_http_client(
+                None,
+                &HashMap::new(),
+                None,
+            )),
             polling_service: RwLock::new(None),
             retention_scheduler: RwLock::new(None),
             tray_available: AtomicBool::new(false),
+            completion_notifier: crate::services::notifications::CompletionNotifier::new(),
+            download_errors: RwLock::new(HashMap::new()),
+            size_estimate_generation: AtomicU64::new(0),
+            poll_cancel: tokio::sync::watch::channel(0).0,
+            active_progress: RwLock::new(HashMap::new()),
+            poll_retry_after_secs: AtomicU64::new(0),
+        }
+    }
+}
+
+impl AppState {
+    /// Clone of the current shared HTTP client. `reqwest::Client` is an `Arc`
+    /// internally, so the clone is cheap and callers never hold the lock
+    /// across an `.await`. Requests already in flight keep the client they
+    /// started with when `set_config` replaces it.
+    pub fn http_client(&self) -> reqwest::Client {
+        match self.shared_http_client.read() {
+            Ok(client) => client.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Clone of the current download client (see `download_http_client`).
+    pub fn download_client(&self) -> reqwest::Client {
+        match self.download_http_client.read() {
+            Ok(client) => client.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Note that the API asked polling to wait `wait`; the longest pending
+    /// wait is kept.
+    pub fn note_poll_retry_after(&self, wait: std::time::Duration) {
+        self.poll_retry_after_secs
+            .fetch_max(wait.as_secs(), Ordering::SeqCst);
+    }
+
+    /// The pending poll wait, if any, clearing it.
+    pub fn take_poll_retry_after(&self) -> Option<std::time::Duration> {
+        match self.poll_retry_after_secs.swap(0, Ordering::SeqCst) {
+            0 => None,
+            secs => Some(std::time::Duration::from_secs(secs)),
+        }
+    }
+
+    /// Remember `error` as the last failure of resource `id`.
+    pub fn record_download_error(&self, id: i64, error: &str) {
+        match self.download_errors.write() {
+            Ok(mut errors) => {
+                errors.insert(id, error.to_string());
+            }
+            Err(e) => tracing::error!("Failed to record download error for {}: {}", id, e),
+        }
+    }
+
+    /// Record `snapshot` as the live progress of download `id`.
+    pub fn set_active_progress(&self, id: i64, snapshot: ActiveProgress) {
+        match self.active_progress.write() {
+            Ok(mut progress) => {
+                progress.insert(id, snapshot);
+            }
+            Err(e) => tracing::error!("Failed to record progress of {}: {}", id, e),
+        }
+    }
+
+    /// Forget the live progress of download `id` (it completed, failed or
+    /// was paused/cancelled).
+    pub fn clear_active_progress(&self, id: i64) {
+        match self.active_progress.write() {
+            Ok(mut progress) => {
+                progress.remove(&id);
+            }
+            Err(e) => tracing::error!("Failed to clear progress of {}: {}", id, e),
+        }
+    }
+
+    /// Whether `offline_mode` is on. A poisoned config lock reads as online:
+    /// the network calls then fail on their own rather than never running.
+    pub fn is_offline(&self) -> bool {
+        self.config
+            .read()
+            .map(|config| config.offline_mode)
+            .unwrap_or(false)
+    }
+
+    /// Forget the last failure of resource `id` (a new attempt is starting).
+    pub fn clear_download_error(&self, id: i64) {
+        match self.download_errors.write() {
+            Ok(mut errors) => {
+                errors.remove(&id);
+            }
+            Err(e) => tracing::error!("Failed to clear download error for {}: {}", id, e),
         }
     }
 }
@@ -134,20 +275,63 @@ fn persist_config(app: &AppHandle, config: &AppConfig) -> Result<(), CommandErro
 pub async fn set_config(
     state: State<'_, AppState>,
     app: AppHandle,
+    config: AppConfig,
+) -> Result<(), CommandError> {
+    apply_config(&state, app, config).await
+}
+
+/// Shared body of `set_config` and `import_config`: normalize, validate,
+/// persist, swap into state, then let the queue react.
+async fn apply_config(
+    state: &AppState,
+    app: AppHandle,
     mut config: AppConfig,
 ) -> Result<(), CommandError> {
-    // Validate before saving
+    // Normalize, then validate, before saving: the normalized config is the
+    // one persisted, stored in state and used by scan_and_queue below.
+    config.normalize();
     config
         .validate()
         .map_err(|e| CommandError::new("config-invalid", format!("Invalid config: {e:?}")))?;
 
     // `tray_close_os_notice_shown` is backend-owned (set once in lib.rs when the
     // window is first hidden to the tray); never let a stale value round-tripped
-    // by the frontend overwrite it.
-    {
+    // by the frontend overwrite it. Same for `setup_completed`.
+    let (client_changed, log_level_changed, back_online, window_changed) = {
         let current = state.config.read()?;
         config.tray_close_os_notice_shown = current.tray_close_os_notice_shown;
-    }
+        config.setup_completed = current.setup_completed;
+        (
+            current.effective_proxy_url() != config.effective_proxy_url()
+                || current.api_headers != config.api_headers
+                || current.request_timeout_secs != config.request_timeout_secs,
+            current.log_level != config.log_level,
+            current.offline_mode && !config.offline_mode,
+            current.download_window != config.download_window,
+        )
+    };
+
+    // Build the replacement client before persisting anything, so a proxy
+    // reqwest refuses leaves both the saved config and the live client as
+    // they were.
+    let rebuilt_clients = if client_changed {
+        let build = |timeout| {
+            crate::services::http::try_build_http_client(
+                config.effective_proxy_url(),
+                &config.api_headers,
+                timeout,
+            )
+            .map_err(|e| {
+                CommandError::new(
+                    "http-client-failed",
+                    format!("Failed to configure HTTP client: {e}"),
+                )
+            })
+        };
+        Some((build(Some(config.request_timeout()))?, build(None)?))
+    } else {
+        None
+    };
 
     persist_config(&app, &config)?;
 
@@ -156,14 +340,49 @@ pub async fn set_config(
         let mut current = state.config.write()?;
         *current = config.clone();
     }
+    if let Some((client, download_client)) = rebuilt_clients {
+        *state.shared_http_client.write()? = client;
+        *state.download_http_client.write()? = download_client;
+        tracing::info!("HTTP clients rebuilt for updated proxy/header/timeout settings");
+    }
+    if log_level_changed {
+        crate::services::logging::apply_log_level(&config.log_level);
+        tracing::info!("Log level set to {:?}", config.log_level);
+    }
+
+    // Back online: release the held queue and catch up with the server.
+    if back_online {
+        tracing::info!("Offline mode turned off, resuming network activity");
+        state.download_queue.wake();
+        let poll_app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = crate::services::poll_once(&poll_app).await {
+                tracing::warn!("Poll after leaving offline mode failed: {}", e);
+            }
+        });
+    }
+
+    // A worker parked until the old download window opens re-checks now.
+    if window_changed {
+        state.download_queue.wake();
+    }
 
-    // Trigger queue updates
+    // Trigger queue updates; the rescan is debounced so a burst of saves
+    // scans once.
     state.download_queue.update_mode(config.download_mode).await;
-    state.download_queue.scan_and_queue(app).await;
+    state.download_queue.request_scan(app);
 
     Ok(())
 }
 
+/// Error returned instead of attempting a network call in offline mode.
+fn offline_error() -> CommandError {
+    CommandError::new(
+        "offline",
+        "Offline mode is on: the server was not contacted",
+    )
+}
+
 /// Get the current application status
 #[tauri::command]
 pub fn get_status(state: State<'_, AppState>) -> Result<AppStatus, CommandError> {
@@ -255,6 +474,17 @@ pub fn get_savings_stats(state: State<'_, AppState>) -> Result<SavingsStats, Com
     })
 }
 
+/// Completed downloads, newest first, at most `limit` entries (all stored
+/// entries when `None`).
+#[tauri::command]
+pub fn get_download_history(
+    state: State<'_, AppState>,
+    limit: Option<usize>,
+) -> Result<Vec<DownloadHistoryEntry>, CommandError> {
+    let history = state.download_history.read()?;
+    Ok(crate::services::history::newest_first(&history, limit))
+}
+
 /// Get the currently loaded resources
 #[tauri::command]
 pub fn get_resources(state: State<'_, AppState>) -> Result<Vec<Resource>, CommandError> {
@@ -262,6 +492,128 @@ pub fn get_resources(state: State<'_, AppState>) -> Result<Vec<Resource>, Comman
     Ok(resources.clone())
 }
 
+/// Resources of one week, as returned by `get_resources_grouped`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeekGroup {
+    pub week: WeekIdentifier,
+    pub resources: Vec<Resource>,
+}
+
+/// Group `resources` by `Resource::week()`, newest week first, keeping the
+/// resources' own order inside each group.
+fn group_by_week(resources: &[Resource]) -> Vec<WeekGroup> {
+    let mut groups: std::collections::BTreeMap<WeekIdentifier, Vec<Resource>> =
+        std::collections::BTreeMap::new();
+    for resource in resources {
+        groups
+            .entry(resource.week())
+            .or_default()
+            .push(resource.clone());
+    }
+    groups
+        .into_iter()
+        .rev()
+        .map(|(week, resources)| WeekGroup { week, resources })
+        .collect()
+}
+
+/// The loaded resources grouped by week, newest first, so the UI uses the
+/// same week computation as the rest of the backend.
+#[tauri::command]
+pub fn get_resources_grouped(state: State<'_, AppState>) -> Result<Vec<WeekGroup>, CommandError> {
+    let resources = state.resources.read()?;
+    Ok(group_by_week(&resources))
+}
+
+/// Distinct weeks of `resources`, newest first.
+fn available_weeks(resources: &[Resource]) -> Vec<WeekIdentifier> {
+    let weeks: std::collections::BTreeSet<WeekIdentifier> =
+        resources.iter().map(Resource::week).collect();
+    weeks.into_iter().rev().collect()
+}
+
+/// Weeks present in the loaded resources, newest first; with
+/// `get_archived_weeks` this backs the UI's week picker.
+#[tauri::command]
+pub fn get_available_weeks(
+    state: State<'_, AppState>,
+) -> Result<Vec<WeekIdentifier>, CommandError> {
+    let resources = state.resources.read()?;
+    Ok(available_weeks(&resources))
+}
+
+/// The loaded resources of `week`, in their own order.
+#[tauri::command]
+pub fn get_resources_for_week(
+    state: State<'_, AppState>,
+    week: WeekIdentifier,
+) -> Result<Vec<Resource>, CommandError> {
+    Ok(state
+        .resources
+        .read()?
+        .iter()
+        .filter(|resource| resource.week() == week)
+        .cloned()
+        .collect())
+}
+
+/// Criteria for `query_resources`; every `None` field matches everything.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceFilter {
+    /// Exact category name.
+    pub category: Option<String>,
+    /// Case-insensitive substring of the title or description.
+    pub text: Option<String>,
+    /// `Some(true)` keeps only YouTube links, `Some(false)` drops them.
+    pub youtube_only: Option<bool>,
+}
+
+/// Pure filtering step behind `query_resources`, preserving the source order.
+/// A blank `text` is ignored rather than matching nothing.
+pub(crate) fn filter_resources(resources: &[Resource], filter: &ResourceFilter) -> Vec<Resource> {
+    let needle = filter
+        .text
+        .as_deref()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_lowercase);
+
+    resources
+        .iter()
+        .filter(|r| {
+            filter
+                .category
+                .as_ref()
+                .is_none_or(|category| &r.category == category)
+        })
+        .filter(|r| {
+            filter
+                .youtube_only
+                .is_none_or(|youtube| r.is_youtube() == youtube)
+        })
+        .filter(|r| {
+            needle.as_ref().is_none_or(|needle| {
+                r.title.to_lowercase().contains(needle)
+                    || r.description
+                        .as_deref()
+                        .is_some_and(|d| d.to_lowercase().contains(needle))
+            })
+        })
+        .cloned()
+        .collect()
+}
+
+/// Resources matching `filter`, in the same order as `get_resources`.
+#[tauri::command]
+pub fn query_resources(
+    state: State<'_, AppState>,
+    filter: ResourceFilter,
+) -> Result<Vec<Resource>, CommandError> {
+    let resources = state.resources.read()?;
+    Ok(filter_resources(&resources, &filter))
+}
+
 /// Get the full category catalog (from the last successful `categories/counts`
 /// fetch). Used by the UI's initial load; live updates arrive via the
 /// `categories-updated` event.
@@ -271,17 +623,276 @@ pub fn get_all_categories(state: State<'_, AppState>) -> Result<Vec<CategoryCoun
     Ok(categories.clone())
 }
 
+/// A distinct category of the currently loaded resources, for filter chips.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResourceCategory {
+    pub category: String,
+    pub count: usize,
+    /// Whether `scan_and_queue` auto-downloads this category, i.e. it is in
+    /// `AppConfig::auto_download_categories` (exact match, like the scan).
+    pub auto_download: bool,
+}
+
+/// Tally `resources` by category, most common first (ties by name so the
+/// chip order is stable between polls).
+pub(crate) fn tally_categories(
+    resources: &[Resource],
+    auto_download_categories: &[String],
+) -> Vec<ResourceCategory> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for resource in resources {
+        *counts.entry(resource.category.as_str()).or_insert(0) += 1;
+    }
+    let mut categories: Vec<ResourceCategory> = counts
+        .into_iter()
+        .map(|(category, count)| ResourceCategory {
+            category: category.to_string(),
+            count,
+            auto_download: auto_download_categories.iter().any(|c| c == category),
+        })
+        .collect();
+    categories.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.category.cmp(&b.category))
+    });
+    categories
+}
+
+/// Distinct categories of the current resources with their counts. Unlike
+/// `get_all_categories` (the server-wide catalog) this only reflects what is
+/// loaded right now.
+#[tauri::command]
+pub fn get_categories(state: State<'_, AppState>) -> Result<Vec<ResourceCategory>, CommandError> {
+    let auto_download_categories = state.config.read()?.auto_download_categories.clone();
+    let resources = state.resources.read()?;
+    Ok(tally_categories(&resources, &auto_download_categories))
+}
+
+/// Display names in `locale` (e.g. `"en"`, `"it-IT"`; unsupported = Italian)
+/// for every known category, keyed by the raw API name, so the UI never
+/// hardcodes translations. Covers the built-in table, the server catalog and
+/// the loaded resources; a category without a translation maps to itself.
+#[tauri::command]
+pub fn get_category_labels(
+    state: State<'_, AppState>,
+    locale: String,
+) -> Result<HashMap<String, String>, CommandError> {
+    let catalog = state.all_categories.read()?;
+    let resources = state.resources.read()?;
+    Ok(crate::services::category_labels::category_labels(
+        &locale,
+        catalog
+            .iter()
+            .map(|c| c.name.as_str())
+            .chain(resources.iter().map(|r| r.category.as_str())),
+    ))
+}
+
 /// Trigger an immediate poll of the API. Thin wrapper over the shared
 /// `services::poll_once` flow (the same one the background polling loop runs),
 /// so the manual "refresh now" action and the periodic poll can never diverge.
 #[tauri::command]
 pub async fn force_poll(app: AppHandle) -> Result<ResourceListResponse, CommandError> {
+    if app.state::<AppState>().is_offline() {
+        return Err(offline_error());
+    }
+    let cancel_rx = app.state::<AppState>().poll_cancel.subscribe();
     // `poll_once` still surfaces its failure as a flat string (it aggregates
     // HTTP/parse/lock failures across a whole cycle); wrap it under one stable
     // code while preserving the detailed message it built.
-    crate::services::poll_once(&app)
-        .await
-        .map_err(|e| CommandError::new("poll-failed", e))
+    match crate::services::polling::poll_once_cancellable(&app, Some(cancel_rx)).await {
+        Ok(Some(response)) => Ok(response),
+        Ok(None) => Err(CommandError::new(
+            "poll-cancelled",
+            "Poll cancelled before the server answered",
+        )),
+        Err(e) => Err(CommandError::new("poll-failed", e)),
+    }
+}
+
+/// Whether the local copy of `resource` predates the server's version, for
+/// the per-item "update available" badge. Manifest/registry only by default
+/// (the same rule as errata detection); with `check_server` a HEAD request
+/// also compares the server's `Last-Modified` with the download time. A
+/// failed or non-2xx HEAD, or offline mode, falls back to the local answer.
+#[tauri::command]
+pub async fn is_resource_stale(
+    state: State<'_, AppState>,
+    resource: Resource,
+    check_server: Option<bool>,
+) -> Result<bool, CommandError> {
+    let (local, prefer_optimized) = {
+        let registry = state.downloaded_files.read()?;
+        let local = crate::services::errata::live_download(&registry, &resource).cloned();
+        (local, state.config.read()?.prefer_optimized)
+    };
+    let Some(local) = local else {
+        return Ok(false);
+    };
+    if local.downloaded_at < resource.created_at {
+        return Ok(true);
+    }
+    if !check_server.unwrap_or(false) || state.is_offline() {
+        return Ok(false);
+    }
+
+    let url = resource.get_effective_download_url(prefer_optimized);
+    match state.http_client().head(url).send().await {
+        Ok(response) if response.status().is_success() => Ok(
+            crate::services::download::last_modified_time(response.headers())
+                .map(chrono::DateTime::<chrono::Utc>::from)
+                .is_some_and(|modified| modified > local.downloaded_at),
+        ),
+        Ok(response) => {
+            tracing::debug!("Staleness HEAD for {} returned {}", url, response.status());
+            Ok(false)
+        }
+        Err(e) => {
+            tracing::debug!("Staleness HEAD failed for {}: {}", url, e);
+            Ok(false)
+        }
+    }
+}
+
+/// Live progress of every active download, keyed by resource id, so the
+/// download panel can render its bars on mount instead of waiting for the
+/// next `download-progress` event. Downloads that haven't received a chunk
+/// yet are absent.
+#[tauri::command]
+pub fn get_active_progress(
+    state: State<'_, AppState>,
+) -> Result<HashMap<i64, ActiveProgress>, CommandError> {
+    Ok(state.active_progress.read()?.clone())
+}
+
+/// State of every download signal, keyed by resource id, for debugging a
+/// download that looks stuck. Read-only: just loads the atomics.
+#[tauri::command]
+pub fn get_download_signals(
+    state: State<'_, AppState>,
+) -> Result<HashMap<i64, String>, CommandError> {
+    let signals = state.download_signals.read()?;
+    Ok(signals
+        .iter()
+        .map(|(id, signal)| {
+            let status = crate::services::download::status_name(signal.load(Ordering::Relaxed));
+            (*id, status.to_string())
+        })
+        .collect())
+}
+
+/// Whether the download queue has nothing queued or downloading, for the
+/// UI's idle indicator (the transition is also announced by
+/// `all-downloads-complete`).
+#[tauri::command]
+pub async fn is_idle(state: State<'_, AppState>) -> Result<bool, CommandError> {
+    Ok(state.download_queue.is_idle().await)
+}
+
+/// Abort any `force_poll` still waiting on the server; it returns
+/// `poll-cancelled` and the resources and status stay as they were. A poll
+/// that already has its response finishes normally.
+#[tauri::command]
+pub fn cancel_poll(state: State<'_, AppState>) {
+    state.poll_cancel.send_modify(|generation| *generation += 1);
+}
+
+/// Why [`check_api_connectivity`] could not get any HTTP response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConnectivityFailure {
+    /// The API host name could not be resolved (no internet, DNS down).
+    Dns,
+    /// TCP/TLS connection refused or reset.
+    Connection,
+    /// No response within [`HEALTH_CHECK_TIMEOUT_SECS`].
+    Timeout,
+    /// Anything else reqwest reported before a response arrived.
+    Other,
+}
+
+/// Result of [`check_api_connectivity`]. `reachable` with a non-2xx
+/// `status` means the network is fine and the server is at fault; no
+/// `status` (and a `failure`) means the request never got an answer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiConnectivity {
+    /// The server answered with a 2xx/3xx status.
+    pub reachable: bool,
+    /// Round-trip time of the request, when a response arrived.
+    pub latency_ms: Option<u64>,
+    /// HTTP status of the response, if any.
+    pub status: Option<u16>,
+    /// Why no response arrived, if none did.
+    pub failure: Option<ConnectivityFailure>,
+}
+
+/// Classify a request error that produced no response. reqwest has no DNS
+/// flag, so resolver failures are recognized by hyper's "dns error" in the
+/// source chain.
+fn classify_connectivity_error(error: &reqwest::Error) -> ConnectivityFailure {
+    if error.is_timeout() {
+        return ConnectivityFailure::Timeout;
+    }
+    let mut source: Option<&dyn std::error::Error> = Some(error);
+    while let Some(e) = source {
+        if e.to_string().to_lowercase().contains("dns error") {
+            return ConnectivityFailure::Dns;
+        }
+        source = e.source();
+    }
+    if error.is_connect() {
+        ConnectivityFailure::Connection
+    } else {
+        ConnectivityFailure::Other
+    }
+}
+
+/// One HEAD request against `url`, timed and classified.
+async fn probe_connectivity(
+    client: &reqwest::Client,
+    url: &str,
+    timeout: std::time::Duration,
+) -> ApiConnectivity {
+    let started = std::time::Instant::now();
+    match client.head(url).timeout(timeout).send().await {
+        Ok(response) => {
+            let status = response.status();
+            ApiConnectivity {
+                reachable: status.is_success() || status.is_redirection(),
+                latency_ms: Some(started.elapsed().as_millis() as u64),
+                status: Some(status.as_u16()),
+                failure: None,
+            }
+        }
+        Err(e) => {
+            tracing::debug!("API connectivity check failed: {}", e);
+            ApiConnectivity {
+                reachable: false,
+                latency_ms: None,
+                status: None,
+                failure: Some(classify_connectivity_error(&e)),
+            }
+        }
+    }
+}
+
+/// Ping the resources API for the connectivity indicator: tells "my
+/// internet" (`failure` set) apart from "the server" (non-2xx `status`).
+/// Read-only; offline mode fails with `offline` without touching the network.
+#[tauri::command]
+pub async fn check_api_connectivity(
+    state: State<'_, AppState>,
+) -> Result<ApiConnectivity, CommandError> {
+    if state.is_offline() {
+        return Err(offline_error());
+    }
+    let url = format!("{}/api/resources/latest-week", api_base_url());
+    Ok(probe_connectivity(
+        &state.http_client(),
+        &url,
+        std::time::Duration::from_secs(HEALTH_CHECK_TIMEOUT_SECS),
+    )
+    .await)
 }
 
 /// Set the work directory
@@ -293,10 +904,71 @@ pub fn set_work_directory(
 ) -> Result<(), CommandError> {
     let path_buf = validate_work_directory(&path)?;
 
-    let mut config = state.config.write()?;
-    config.work_directory = Some(path_buf);
+    {
+        let mut config = state.config.write()?;
+        config.work_directory = Some(path_buf);
+        persist_config(&app, &config)?;
+    }
+    complete_setup_if_ready(&app);
+    Ok(())
+}
 
-    persist_config(&app, &config)
+/// What first-run onboarding still has to do, from `get_setup_state`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SetupState {
+    pub setup_completed: bool,
+    /// No work directory set, or it doesn't exist.
+    pub needs_work_directory: bool,
+    /// No poll has succeeded yet (always false once setup is completed).
+    pub needs_first_poll: bool,
+}
+
+fn setup_state(
+    config: &AppConfig,
+    last_poll_time: Option<chrono::DateTime<chrono::Utc>>,
+) -> SetupState {
+    SetupState {
+        setup_completed: config.setup_completed,
+        needs_work_directory: !config.work_directory.as_deref().is_some_and(Path::is_dir),
+        needs_first_poll: !config.setup_completed && last_poll_time.is_none(),
+    }
+}
+
+/// First-run state for the onboarding screens.
+#[tauri::command]
+pub fn get_setup_state(state: State<'_, AppState>) -> Result<SetupState, CommandError> {
+    let last_poll_time = state.status.read()?.last_poll_time;
+    let config = state.config.read()?;
+    Ok(setup_state(&config, last_poll_time))
+}
+
+/// Mark setup completed (and persist it) once a valid work directory is set
+/// and a poll has succeeded. Called after each of the two; no-op once done.
+pub(crate) fn complete_setup_if_ready(app: &AppHandle) {
+    let state = app.state::<AppState>();
+    let last_poll_time = match state.status.read() {
+        Ok(status) => status.last_poll_time,
+        Err(e) => {
+            tracing::error!("Setup: failed to read status: {}", e);
+            return;
+        }
+    };
+    let mut config = match state.config.write() {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::error!("Setup: failed to write config: {}", e);
+            return;
+        }
+    };
+    let setup = setup_state(&config, last_poll_time);
+    if setup.setup_completed || setup.needs_work_directory || setup.needs_first_poll {
+        return;
+    }
+    config.setup_completed = true;
+    if let Err(e) = persist_config(app, &config) {
+        tracing::error!("Setup: failed to persist completion: {}", e.message);
+    }
+    tracing::info!("First-run setup completed");
 }
 
 /// Validate a user-selected work directory, returning the resolved path or a
@@ -404,6 +1076,50 @@ pub fn set_polling_interval(
     persist_config(&app, &config_snapshot)
 }
 
+/// Entries of `categories` that no currently loaded resource carries. Not an
+/// error for `set_auto_download_categories`: a category can be absent this
+/// week and come back the next.
+pub(crate) fn unknown_categories<'a>(
+    categories: &'a [String],
+    resources: &[Resource],
+) -> Vec<&'a str> {
+    categories
+        .iter()
+        .filter(|category| !resources.iter().any(|r| &r.category == *category))
+        .map(String::as_str)
+        .collect()
+}
+
+/// Replace the auto-download categories (the focused path for the UI's
+/// per-category toggle, instead of a full `set_config` round-trip) and run
+/// `scan_and_queue` right away so newly enabled categories start downloading.
+#[tauri::command]
+pub async fn set_auto_download_categories(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    categories: Vec<String>,
+) -> Result<(), CommandError> {
+    let config_snapshot = {
+        let mut config = state.config.write()?;
+        config.auto_download_categories = crate::models::normalize_categories(categories);
+        config.clone()
+    };
+
+    {
+        let resources = state.resources.read()?;
+        for category in unknown_categories(&config_snapshot.auto_download_categories, &resources) {
+            tracing::warn!(
+                "Auto-download enabled for category \"{}\", which no current resource has",
+                category
+            );
+        }
+    }
+
+    persist_config(&app, &config_snapshot)?;
+    state.download_queue.request_scan(app);
+    Ok(())
+}
+
 /// Set the retention policy
 #[tauri::command]
 pub fn set_retention_days(
@@ -417,6 +1133,35 @@ pub fn set_retention_days(
     persist_config(&app, &config)
 }
 
+/// Exempt `week` from retention: it is kept in the archive forever.
+#[tauri::command]
+pub fn pin_week(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    week: WeekIdentifier,
+) -> Result<(), CommandError> {
+    let mut config = state.config.write()?;
+    if !config.pinned_weeks.contains(&week) {
+        config.pinned_weeks.push(week);
+        config.pinned_weeks.sort();
+    }
+
+    persist_config(&app, &config)
+}
+
+/// Put `week` back under the normal retention policy.
+#[tauri::command]
+pub fn unpin_week(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    week: WeekIdentifier,
+) -> Result<(), CommandError> {
+    let mut config = state.config.write()?;
+    config.pinned_weeks.retain(|pinned| *pinned != week);
+
+    persist_config(&app, &config)
+}
+
 /// Enable or disable launching the app automatically at OS startup.
 ///
 /// Toggles the actual OS-level autostart entry (Windows registry autorun /
@@ -464,37 +1209,387 @@ pub fn get_archived_weeks(state: State<'_, AppState>) -> Result<Vec<WeekIdentifi
         .as_ref()
         .ok_or(FileError::WorkDirectoryNotSet)?;
 
-    let service = crate::services::FileRetentionService::new(work_dir.clone());
+    let archive_root =
+        crate::services::resolve_archive_root(work_dir, config.effective_archive_directory());
+    let service = crate::services::FileRetentionService::new(work_dir.clone(), archive_root);
     Ok(service.get_archived_weeks())
 }
 
-/// Check if a resource is a YouTube link
-#[tauri::command]
-pub fn is_resource_youtube(url: String) -> bool {
-    crate::models::is_youtube_url(&url)
-}
-
-/// Download a specific resource
-/// This adds the resource to the download queue with priority
+/// Apply the retention policy to the archive right away (the settings
+/// screen's "Clean up now") instead of waiting for the daily scheduler.
+/// Returns the number of archived weeks moved to the trash and emits it as
+/// `retention-applied`.
 #[tauri::command]
-pub async fn download_resource(
+pub async fn enforce_retention_now(
     state: State<'_, AppState>,
     app: AppHandle,
-    resource: Resource,
-) -> Result<(), CommandError> {
-    let config = state.config.read()?.clone();
+) -> Result<u32, CommandError> {
+    use tauri::Emitter;
 
-    let work_dir = config
-        .work_directory
-        .ok_or(FileError::WorkDirectoryNotSet)?;
+    let (work_dir, archive_root, retention_days, pinned_weeks) = {
+        let config = state.config.read()?;
+        let work_dir = config
+            .work_directory
+            .clone()
+            .ok_or(FileError::WorkDirectoryNotSet)?;
+        let archive_root =
+            crate::services::resolve_archive_root(&work_dir, config.effective_archive_directory());
+        (
+            work_dir,
+            archive_root,
+            config.retention_days,
+            config.pinned_weeks.clone(),
+        )
+    };
 
-    let dest_dir =
-        crate::services::download::resolve_week_dir(&resource, &work_dir, config.prefer_optimized);
+    // Blocking scan + trash moves: keep them off the async runtime, as the
+    // scheduler does (`retention::run_retention_once`).
+    let deleted = tauri::async_runtime::spawn_blocking(move || {
+        crate::services::FileRetentionService::new(work_dir, archive_root)
+            .enforce_retention(retention_days, &pinned_weeks)
+    })
+    .await
+    .map_err(|e| CommandError::new("retention-failed", e.to_string()))??;
 
-    if !dest_dir.exists() {
-        std::fs::create_dir_all(&dest_dir)
-            .map_err(|e| CommandError::new("create-directory-failed", e.to_string()))?;
+    if let Err(e) = app.emit(
+        "retention-applied",
+        serde_json::json!({ "deleted": deleted }),
+    ) {
+        tracing::error!("Failed to emit retention-applied: {:?}", e);
     }
+    Ok(deleted)
+}
+
+/// Files on disk that belong to `week`, each marked with the resource that
+/// owns it per its folder's manifest so the UI can tell downloads from stray
+/// files. Located the way downloads place them (folder layout, category
+/// destinations): see [`collect_week_files`]. Empty when nothing is on disk.
+#[tauri::command]
+pub fn get_week_files(
+    state: State<'_, AppState>,
+    week: WeekIdentifier,
+) -> Result<Vec<crate::services::manifest::WeekFile>, CommandError> {
+    let config = state.config.read()?;
+    if config.work_directory.is_none() {
+        return Err(FileError::WorkDirectoryNotSet.into());
+    }
+    let files = {
+        let resources = state.resources.read()?;
+        let registry = state.downloaded_files.read()?;
+        week_file_paths(&config, &resources, &registry, &week)
+    };
+    Ok(collect_week_files(&config, &week, &files))
+}
+
+/// Where `week`'s files are (or would be): the destination of each of its
+/// current resources plus its live entries in the download registry.
+fn week_file_paths(
+    config: &AppConfig,
+    resources: &[Resource],
+    registry: &[DownloadedFile],
+    week: &WeekIdentifier,
+) -> Vec<PathBuf> {
+    resources
+        .iter()
+        .filter(|resource| resource.week() == *week)
+        .filter_map(|resource| crate::services::download::resource_dest_path(config, resource))
+        .chain(
+            registry
+                .iter()
+                .filter(|file| file.week == *week && !file.is_superseded)
+                .map(|file| file.local_path.clone()),
+        )
+        .collect()
+}
+
+/// The files listed in the folders `files` live in (plus the work
+/// directory's week folder under the by-week layout). A folder named after
+/// `week` (new-format or legacy) is the week's own, so every file in it
+/// counts, stray ones included; in a shared folder (flat or by-category
+/// layout) only the files in `files` do.
+fn collect_week_files(
+    config: &AppConfig,
+    week: &WeekIdentifier,
+    files: &[PathBuf],
+) -> Vec<crate::services::manifest::WeekFile> {
+    let week_dir_names = [week.as_dir_name(), week.legacy_dir_name()];
+    let mut dirs: Vec<PathBuf> = files
+        .iter()
+        .filter_map(|file| file.parent().map(Path::to_path_buf))
+        .collect();
+    if let (Some(work_dir), FolderLayout::ByWeek) = (&config.work_directory, config.folder_layout) {
+        dirs.extend(week_dir_names.iter().map(|name| work_dir.join(name)));
+    }
+    dirs.sort();
+    dirs.dedup();
+
+    let mut listed: Vec<crate::services::manifest::WeekFile> = dirs
+        .iter()
+        .flat_map(|dir| {
+            let own = dir
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| week_dir_names.iter().any(|week_name| week_name == name));
+            crate::services::manifest::list_week_files(dir)
+                .into_iter()
+                .filter(move |file| own || files.contains(&dir.join(&file.filename)))
+        })
+        .collect();
+    listed.sort_by(|a, b| a.filename.cmp(&b.filename));
+    listed
+}
+
+/// File count, bytes and time spent downloading `week`, with the average
+/// throughput, from the manifests' transfer records. The week's files are
+/// located the way downloads place them (folder layout, category
+/// destinations): its current resources plus its entries in the download
+/// registry. A pure read: no network calls. All zeros when nothing was
+/// recorded.
+#[tauri::command]
+pub fn get_week_stats(
+    state: State<'_, AppState>,
+    week: WeekIdentifier,
+) -> Result<crate::services::manifest::WeekStats, CommandError> {
+    let config = state.config.read()?;
+    if config.work_directory.is_none() {
+        return Err(FileError::WorkDirectoryNotSet.into());
+    }
+    let resources = state.resources.read()?;
+    let registry = state.downloaded_files.read()?;
+    let files = week_file_paths(&config, &resources, &registry, &week);
+    Ok(crate::services::manifest::week_stats_of_files(&files))
+}
+
+/// Result of the `rebuild_manifests` command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestRebuildSummary {
+    /// Week folders whose manifest was rewritten.
+    pub weeks: usize,
+    /// Files attributed to a resource.
+    pub files: usize,
+    /// Of those, files the download registry didn't know about yet.
+    pub registered: usize,
+}
+
+/// Rebuild every week folder's manifest (work directory and archive) from
+/// the files on disk, for installs that predate manifests or files moved by
+/// hand: files are matched to resources by name, hashed, and the ones the
+/// registry doesn't know are recorded with their mtime as download time.
+/// Hashing runs on the blocking pool.
+#[tauri::command]
+pub async fn rebuild_manifests(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<ManifestRebuildSummary, CommandError> {
+    let (roots, expected) = {
+        let config = state.config.read()?;
+        let work_dir = config
+            .work_directory
+            .clone()
+            .ok_or(FileError::WorkDirectoryNotSet)?;
+        let archive_root =
+            crate::services::resolve_archive_root(&work_dir, config.effective_archive_directory());
+        let resources = state.resources.read()?;
+        let registry = state.downloaded_files.read()?;
+        (
+            vec![work_dir, archive_root],
+            crate::services::manifest::expected_files(&resources, &registry),
+        )
+    };
+
+    let (weeks, attributed) = tauri::async_runtime::spawn_blocking(move || {
+        crate::services::manifest::rebuild_manifests(&roots, &expected)
+    })
+    .await
+    .map_err(|e| CommandError::new("task-join-failed", e.to_string()))?;
+
+    let files = attributed.len();
+    let entries = attributed
+        .into_iter()
+        .map(|(week, file)| DownloadedFile {
+            resource_id: file.resource_id,
+            week,
+            local_path: file.path,
+            downloaded_at: file.modified.unwrap_or_else(chrono::Utc::now),
+            source_url: file.source_url,
+            is_superseded: false,
+        })
+        .collect();
+    let registered = crate::services::errata::import_downloaded_files(&app, entries);
+    tracing::info!(
+        "Rebuilt {} week manifest(s): {} file(s) attributed, {} newly registered",
+        weeks,
+        files,
+        registered
+    );
+
+    Ok(ManifestRebuildSummary {
+        weeks,
+        files,
+        registered,
+    })
+}
+
+/// Files stored more than once across the week folders (work directory,
+/// archive and per-category destinations), grouped by content hash from the
+/// manifests, for a cleanup view. No network, no hashing.
+#[tauri::command]
+pub async fn find_duplicate_files(
+    state: State<'_, AppState>,
+) -> Result<Vec<crate::services::manifest::DuplicateGroup>, CommandError> {
+    let roots = {
+        let config = state.config.read()?;
+        manifest_roots(&config)?
+    };
+
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::services::manifest::find_duplicate_files(&roots)
+    })
+    .await
+    .map_err(|e| CommandError::new("task-join-failed", e.to_string()))
+}
+
+/// Every folder week folders (and their manifests) can live in: the work
+/// directory first, then the archive and any category destinations.
+fn manifest_roots(config: &AppConfig) -> Result<Vec<PathBuf>, CommandError> {
+    let work_dir = config
+        .work_directory
+        .clone()
+        .ok_or(FileError::WorkDirectoryNotSet)?;
+    let archive_root =
+        crate::services::resolve_archive_root(&work_dir, config.effective_archive_directory());
+    let mut roots = vec![work_dir, archive_root];
+    for dir in config.category_destinations.values() {
+        if !roots.contains(dir) {
+            roots.push(dir.clone());
+        }
+    }
+    Ok(roots)
+}
+
+/// SHA-256 of the file at `path`, hashed off the async runtime, for
+/// comparing a manually copied file against a download's recorded hash.
+#[tauri::command]
+pub async fn hash_file(path: String) -> Result<String, CommandError> {
+    let path = PathBuf::from(path);
+    tauri::async_runtime::spawn_blocking(move || crate::services::download::hash_file(&path))
+        .await
+        .map_err(|e| CommandError::new("task-join-failed", e.to_string()))?
+        .map_err(CommandError::from)
+}
+
+/// Image bytes of the thumbnail at `url`, cached in the app cache directory
+/// after the first fetch so the resource list renders offline (see
+/// `services::thumbnails`). Sent as a raw response: an `ArrayBuffer` on the
+/// JS side rather than a JSON array of numbers.
+#[tauri::command]
+pub async fn get_thumbnail(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    url: String,
+) -> Result<tauri::ipc::Response, CommandError> {
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| CommandError::new("cache-dir-unavailable", e.to_string()))?;
+    let client = state.http_client();
+    let bytes = crate::services::thumbnails::get_thumbnail(&client, &cache_dir, &url).await?;
+    Ok(tauri::ipc::Response::new(bytes))
+}
+
+/// What `clear_caches` removed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ClearedCaches {
+    /// The cached resource list was in `cache.json` and got removed.
+    pub cleared_resources: bool,
+    /// The file-size cache held entries (in memory or in `cache.json`).
+    pub cleared_file_sizes: bool,
+    /// Cached thumbnail images deleted.
+    pub cleared_thumbnails: usize,
+}
+
+/// Troubleshooting reset of the derived/cached layer, for when the app
+/// shows stale data after a server change: empties the file-size cache,
+/// drops the `resources` and `file_size_cache` entries of `cache.json` and
+/// deletes the cached thumbnails. Downloaded files, the download registry
+/// and the config are untouched. The in-memory resource list stays until a
+/// poll replaces it; `repoll` starts one right away. Emits `caches-cleared`.
+#[tauri::command]
+pub async fn clear_caches(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    repoll: Option<bool>,
+) -> Result<ClearedCaches, CommandError> {
+    use tauri::Emitter;
+    use tauri_plugin_store::StoreExt;
+
+    let had_sizes = {
+        let mut cache = state.file_size_cache.write()?;
+        let had_sizes = !cache.is_empty();
+        *cache = FileSizeCache::new();
+        had_sizes
+    };
+
+    let store = app.store("cache.json")?;
+    let cleared_resources = store.delete("resources");
+    let cleared_file_sizes = store.delete("file_size_cache") || had_sizes;
+    store.save()?;
+
+    let cache_dir = app
+        .path()
+        .app_cache_dir()
+        .map_err(|e| CommandError::new("cache-dir-unavailable", e.to_string()))?;
+    let cleared_thumbnails = tauri::async_runtime::spawn_blocking(move || {
+        crate::services::thumbnails::clear_thumbnail_cache(&cache_dir)
+    })
+    .await
+    .map_err(|e| CommandError::new("task-join-failed", e.to_string()))?
+    .map_err(|e| CommandError::new("clear-caches-failed", e.to_string()))?;
+
+    let cleared = ClearedCaches {
+        cleared_resources,
+        cleared_file_sizes,
+        cleared_thumbnails,
+    };
+    tracing::info!("Caches cleared: {:?}", cleared);
+    if let Err(e) = app.emit("caches-cleared", &cleared) {
+        tracing::error!("Failed to emit caches-cleared: {:?}", e);
+    }
+
+    if repoll.unwrap_or(false) {
+        let poll_app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = crate::services::poll_once(&poll_app).await {
+                tracing::warn!("Poll after clearing caches failed: {}", e);
+            }
+        });
+    }
+
+    Ok(cleared)
+}
+
+/// Check if a resource is a YouTube link
+#[tauri::command]
+pub fn is_resource_youtube(url: String) -> bool {
+    crate::models::is_youtube_url(&url)
+}
+
+/// Download a specific resource
+/// This adds the resource to the download queue with priority
+#[tauri::command]
+pub async fn download_resource(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    resource: Resource,
+) -> Result<(), CommandError> {
+    if !resource.is_active {
+        return Err(CommandError::new(
+            "resource-inactive",
+            format!("Resource {} has been withdrawn by the server", resource.id),
+        ));
+    }
+
+    ensure_work_dir_available(state.inner(), &app)?;
 
     // Add to queue with priority (manual downloads go first)
     state
@@ -505,6 +1600,296 @@ pub async fn download_resource(
     Ok(())
 }
 
+/// Queue every resource of the current (latest) week that isn't downloaded
+/// yet, regardless of the auto-download categories ("download everything for
+/// Sabbath"). Like a manual download they go ahead of automatic ones and
+/// ignore the download window. Inactive resources are skipped, and so are
+/// resources already queued or downloading. Returns how many were queued and
+/// are still there once the batch is in (`max_queue_size` may evict some).
+#[tauri::command]
+pub async fn download_current_week(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<usize, CommandError> {
+    let config = state.config.read()?.clone();
+    let work_dir = config
+        .work_directory
+        .as_deref()
+        .ok_or(FileError::WorkDirectoryNotSet)?;
+    if !crate::services::workdir::refresh_availability(&app, work_dir) {
+        return Err(CommandError::new(
+            "work-dir-unavailable",
+            format!("Work directory is not available: {}", work_dir.display()),
+        ));
+    }
+
+    let candidates = {
+        let resources = state.resources.read()?.clone();
+        let registry = state.downloaded_files.read()?.clone();
+        week_download_candidates(resources, &registry, &config)
+    };
+
+    // Each priority add goes to the front, so add in reverse to keep the
+    // week's order.
+    let mut added = Vec::new();
+    for resource in candidates.into_iter().rev() {
+        let id = resource.id;
+        if state
+            .download_queue
+            .add_task_priority(app.clone(), resource)
+            .await
+        {
+            added.push(id);
+        }
+    }
+    // A full queue makes room by evicting its tail, which for a big week can
+    // be this batch's own items; only count those that made it.
+    let mut queued = 0;
+    for id in added {
+        if state.download_queue.is_queued(id).await || state.download_queue.is_active(id).await {
+            queued += 1;
+        }
+    }
+    tracing::info!("Download current week: {} resources queued", queued);
+    Ok(queued)
+}
+
+/// The resources `download_current_week` should queue: those of the latest
+/// week (`models::latest_week`) that are active and not already downloaded.
+fn week_download_candidates(
+    resources: Vec<Resource>,
+    registry: &[DownloadedFile],
+    config: &AppConfig,
+) -> Vec<Resource> {
+    let Some(current) = crate::models::latest_week(&resources) else {
+        return Vec::new();
+    };
+    resources
+        .into_iter()
+        .filter(|r| r.week() == current)
+        .filter(|r| r.is_active && !is_downloaded(r, registry, config))
+        .collect()
+}
+
+/// Refuse a manual download while the work directory is unset or gone. The
+/// destination folder itself is created lazily by the download
+/// (`DownloadService::download_resource`), never below an empty mount point.
+fn ensure_work_dir_available(state: &AppState, app: &AppHandle) -> Result<(), CommandError> {
+    let work_dir = state
+        .config
+        .read()?
+        .work_directory
+        .clone()
+        .ok_or(FileError::WorkDirectoryNotSet)?;
+    if !crate::services::workdir::refresh_availability(app, &work_dir) {
+        return Err(CommandError::new(
+            "work-dir-unavailable",
+            format!("Work directory is not available: {}", work_dir.display()),
+        ));
+    }
+    Ok(())
+}
+
+/// Re-fetch a resource whose local copy is suspected corrupt: its existing
+/// file goes to the trash, any leftover `.part` (and sidecar) is deleted so
+/// the download can't resume from it, then it is queued with priority like a
+/// manual download and reports through the usual download events.
+#[tauri::command]
+pub async fn redownload_resource(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    resource: Resource,
+) -> Result<(), CommandError> {
+    if !resource.is_active {
+        return Err(CommandError::new(
+            "resource-inactive",
+            format!("Resource {} has been withdrawn by the server", resource.id),
+        ));
+    }
+    // Trashing the file under a running download would race its rename.
+    if state.download_queue.is_active(resource.id).await {
+        return Err(CommandError::new(
+            "download-in-progress",
+            format!("Resource {} is already downloading", resource.id),
+        ));
+    }
+
+    let path = resolve_resource_path(state.inner(), &resource)?;
+    queue_fresh_download(state.inner(), app, resource, &path).await
+}
+
+/// Trash the local copy at `path` and any leftover `.part`, then queue
+/// `resource` with priority. Shared by `redownload_resource` and
+/// `refresh_resource`. Everything that could keep it out of the queue is
+/// checked before the copy is trashed; trashing after queueing instead would
+/// race a worker that picks the download up straight away.
+async fn queue_fresh_download(
+    state: &AppState,
+    app: AppHandle,
+    resource: Resource,
+    path: &Path,
+) -> Result<(), CommandError> {
+    ensure_work_dir_available(state, &app)?;
+    // The queue turns a malformed URL away, which would leave no copy at all.
+    crate::services::download::normalize_resource_urls(resource.clone(), &api_base_url())?;
+
+    if path.exists() {
+        trash::delete(path).map_err(|source| FileError::TrashFailed {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        tracing::info!("Re-download: moved {:?} to trash", path);
+    }
+    let _ = std::fs::remove_file(crate::services::download::part_path_for(path));
+    let _ = std::fs::remove_file(crate::services::download::meta_path_for(path));
+    let _ = std::fs::remove_file(crate::services::download::staged_part_path_for(path));
+    let _ = std::fs::remove_file(crate::services::download::staged_meta_path_for(path));
+
+    let id = resource.id;
+    // `false` also means it was already queued and only moved to the front.
+    if !state.download_queue.add_task_priority(app, resource).await
+        && !state.download_queue.is_queued(id).await
+    {
+        return Err(CommandError::new(
+            "not-queued",
+            format!("Resource {} could not be queued", id),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Result of `refresh_resource`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RefreshOutcome {
+    /// The server answered 304: the local copy is current, nothing queued.
+    NotModified,
+    /// The local copy was missing or outdated and a download was queued.
+    Queued,
+}
+
+/// When the local copy of `resource` was obtained: the registry's
+/// `downloaded_at`, else the file's modification time (which the download
+/// sets to the server's `Last-Modified`).
+fn local_copy_time(
+    state: &AppState,
+    resource: &Resource,
+    path: &Path,
+) -> Result<Option<chrono::DateTime<chrono::Utc>>, CommandError> {
+    let registered = state
+        .downloaded_files
+        .read()?
+        .iter()
+        .rev()
+        .find(|f| f.resource_id == resource.id && !f.is_superseded && f.local_path == path)
+        .map(|f| f.downloaded_at);
+    Ok(registered.or_else(|| {
+        std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(chrono::DateTime::<chrono::Utc>::from)
+    }))
+}
+
+/// Re-fetch `resource` only if the server copy is newer than ours: a
+/// conditional request (`If-Modified-Since`) decides, so an unchanged large
+/// video costs one 304 instead of a full download. A changed file goes
+/// through the same path as `redownload_resource`.
+#[tauri::command]
+pub async fn refresh_resource(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    resource: Resource,
+) -> Result<RefreshOutcome, CommandError> {
+    if !resource.is_active {
+        return Err(CommandError::new(
+            "resource-inactive",
+            format!("Resource {} has been withdrawn by the server", resource.id),
+        ));
+    }
+    if state.download_queue.is_active(resource.id).await {
+        return Err(CommandError::new(
+            "download-in-progress",
+            format!("Resource {} is already downloading", resource.id),
+        ));
+    }
+    if resource.is_youtube() {
+        // A shortcut has no server copy to compare against.
+        return Ok(RefreshOutcome::NotModified);
+    }
+    if state.is_offline() {
+        return Err(offline_error());
+    }
+
+    let path = resolve_resource_path(state.inner(), &resource)?;
+    if let Some(since) = local_copy_time(state.inner(), &resource, &path)? {
+        let prefer_optimized = state.config.read()?.prefer_optimized;
+        let outcome = crate::services::DownloadService::with_client(state.http_client())
+            .check_if_modified(&resource, prefer_optimized, since)
+            .await?;
+        if outcome == crate::services::download::Outcome::NotModified {
+            tracing::info!("Refresh: {} is up to date", resource.title);
+            return Ok(RefreshOutcome::NotModified);
+        }
+    }
+
+    queue_fresh_download(state.inner(), app, resource, &path).await?;
+    Ok(RefreshOutcome::Queued)
+}
+
+/// Last download failure per resource id, for a persistent "failed — retry?"
+/// state that outlives the `download-failed` event.
+#[tauri::command]
+pub fn get_download_errors(
+    state: State<'_, AppState>,
+) -> Result<HashMap<i64, String>, CommandError> {
+    Ok(state.download_errors.read()?.clone())
+}
+
+/// Queue every resource with a recorded failure again, at normal priority,
+/// for a one-click recovery after an outage. Resources already queued or
+/// downloading are skipped, as are failures of resources no longer loaded;
+/// each one queued has its error cleared. Returns how many were queued.
+#[tauri::command]
+pub async fn retry_all_failed(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<usize, CommandError> {
+    let failed: Vec<Resource> = {
+        let errors = state.download_errors.read()?;
+        state
+            .resources
+            .read()?
+            .iter()
+            .filter(|resource| errors.contains_key(&resource.id))
+            .cloned()
+            .collect()
+    };
+
+    let mut requeued = 0;
+    for resource in failed {
+        let id = resource.id;
+        if state.download_queue.add_task(app.clone(), resource).await {
+            state.clear_download_error(id);
+            requeued += 1;
+        }
+    }
+    tracing::info!("Retry all failed: {} download(s) queued again", requeued);
+    Ok(requeued)
+}
+
+/// Stop the queue from starting new downloads; in-flight ones continue.
+#[tauri::command]
+pub fn pause_queue(state: State<'_, AppState>, app: AppHandle) {
+    state.download_queue.pause_queue(&app);
+}
+
+/// Let the queue start downloads again after `pause_queue`.
+#[tauri::command]
+pub fn resume_queue(state: State<'_, AppState>, app: AppHandle) {
+    state.download_queue.resume_queue(&app);
+}
+
 /// Pause an active download
 #[tauri::command]
 pub fn pause_download(state: State<'_, AppState>, resource_id: i64) -> Result<(), CommandError> {
@@ -519,12 +1904,37 @@ pub fn pause_download(state: State<'_, AppState>, resource_id: i64) -> Result<()
     Ok(())
 }
 
-/// Cancel and delete an active download
+/// Stop an active download but keep its `.part`, so a later manual download
+/// resumes where it left off (unlike cancel, which deletes it). A resource
+/// still waiting in the queue has no progress to keep and is just removed.
+#[tauri::command]
+pub async fn stop_download(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    resource_id: i64,
+) -> Result<(), CommandError> {
+    if state.download_queue.remove_queued(&app, resource_id).await {
+        return Ok(());
+    }
+
+    let signals = state
+        .download_signals
+        .try_read()
+        .map_err(|_| CommandError::new("signals-locked", "Download signals locked, try again"))?;
+    if let Some(signal) = signals.get(&resource_id) {
+        signal.store(STATUS_STOPPED, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Cancel an active download and delete its `.part`, or keep it for a later
+/// resume when `keep_partial` is true (it still ends as `download-cancelled`).
 #[tauri::command]
 pub async fn cancel_download(
     state: State<'_, AppState>,
     app: AppHandle,
     resource_id: i64,
+    keep_partial: Option<bool>,
 ) -> Result<(), CommandError> {
     // A5: if the resource is still waiting in the queue, drop it there.
     // Setting the download signal would be a no-op for something not yet
@@ -540,11 +1950,53 @@ pub async fn cancel_download(
         .try_read()
         .map_err(|_| CommandError::new("signals-locked", "Download signals locked, try again"))?;
     if let Some(signal) = signals.get(&resource_id) {
-        signal.store(STATUS_CANCELLED, Ordering::Relaxed);
+        let status = if keep_partial.unwrap_or(false) {
+            STATUS_CANCELLED_KEEP_PARTIAL
+        } else {
+            STATUS_CANCELLED
+        };
+        signal.store(status, Ordering::Relaxed);
     }
     Ok(())
 }
 
+/// Outcome of `cancel_all_downloads`, for the UI's confirmation message.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CancelAllResult {
+    /// In-flight downloads signalled to cancel (their `.part` files are
+    /// deleted by the download loop).
+    pub cancelled_active: usize,
+    /// Queued downloads removed before they ever started.
+    pub cleared_queued: usize,
+}
+
+/// Stop everything: empty the queue, then cancel every in-flight download.
+/// The queue goes first so the worker can't pull a new task into a slot freed
+/// by a cancellation.
+#[tauri::command]
+pub async fn cancel_all_downloads(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<CancelAllResult, CommandError> {
+    let cleared_queued = state.download_queue.clear(&app).await;
+
+    let signals = state.download_signals.read()?;
+    let cancelled_active = signals
+        .values()
+        .filter(|signal| signal.swap(STATUS_CANCELLED, Ordering::Relaxed) != STATUS_CANCELLED)
+        .count();
+    tracing::info!(
+        "Cancel all: {} active download(s) cancelled, {} queued cleared",
+        cancelled_active,
+        cleared_queued
+    );
+
+    Ok(CancelAllResult {
+        cancelled_active,
+        cleared_queued,
+    })
+}
+
 /// Check if a resource is already downloaded
 #[tauri::command]
 pub fn check_resource_status(
@@ -557,16 +2009,7 @@ pub fn check_resource_status(
         .try_read()
         .map_err(|_| CommandError::new("config-locked", "Config locked, try again"))?;
 
-    if let Some(work_dir) = &config.work_directory {
-        let dest_path = crate::services::download::resolve_dest_path(
-            &resource,
-            work_dir,
-            config.prefer_optimized,
-        );
-        Ok(dest_path.exists())
-    } else {
-        Ok(false)
-    }
+    Ok(crate::services::download::DownloadService::check_file_exists(&resource, &config))
 }
 
 /// Fresh single-resource `downloaded` check with the SAME semantics as the
@@ -584,18 +2027,14 @@ pub fn check_resource_downloaded(
     state: State<'_, AppState>,
     resource: Resource,
 ) -> Result<bool, CommandError> {
-    let (work_dir, prefer_optimized) = {
-        let config = state.config.read()?;
-        (config.work_directory.clone(), config.prefer_optimized)
-    };
+    let config = state.config.read()?.clone();
     let registry = state.downloaded_files.read()?;
     let id = resource.id;
     let statuses = compute_resources_status(
         std::slice::from_ref(&resource),
         &registry,
-        work_dir.as_deref(),
-        prefer_optimized,
-        &HashMap::new(),
+        &config,
+        &FileSizeCache::new(),
     );
     Ok(statuses.get(&id).is_some_and(|s| s.downloaded))
 }
@@ -604,8 +2043,9 @@ pub fn check_resource_downloaded(
 ///
 /// Registry-first: an entry in `downloaded_files` records where the download
 /// actually landed, which stays authoritative even if the URL-derived filename
-/// later changes. Falls back to `resolve_dest_path` (work dir + week + URL
-/// filename) for files that predate the registry or have no entry yet.
+/// later changes. Falls back to `resource_dest_path` (work dir + layout
+/// folder + URL filename) for files that predate the registry or have no
+/// entry yet.
 fn resolve_resource_path(state: &AppState, resource: &Resource) -> Result<PathBuf, CommandError> {
     {
         let registry = state.downloaded_files.read()?;
@@ -623,15 +2063,10 @@ fn resolve_resource_path(state: &AppState, resource: &Resource) -> Result<PathBu
     }
 
     let config = state.config.read()?;
-    let work_dir = config
-        .work_directory
-        .as_ref()
-        .ok_or(FileError::WorkDirectoryNotSet)?;
-    Ok(crate::services::download::resolve_dest_path(
-        resource,
-        work_dir,
-        config.prefer_optimized,
-    ))
+    Ok(
+        crate::services::download::resource_dest_path(&config, resource)
+            .ok_or(FileError::WorkDirectoryNotSet)?,
+    )
 }
 
 /// Guard for `reveal_resource`: a file that vanished from disk must surface as
@@ -649,101 +2084,576 @@ fn ensure_reveal_target_exists(path: &Path) -> Result<(), CommandError> {
     }
 }
 
-/// Reveal a downloaded resource in the system file manager, selecting the file
-/// inside its containing folder. If selection isn't supported (some Linux file
-/// managers) or the reveal otherwise fails, falls back to opening the week
-/// directory that would contain it.
+/// Reveal a downloaded resource in the system file manager, selecting the file
+/// inside its containing folder. If selection isn't supported (some Linux file
+/// managers) or the reveal otherwise fails, falls back to opening the week
+/// directory that would contain it.
+#[tauri::command]
+pub fn reveal_resource(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    resource: Resource,
+) -> Result<(), CommandError> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let path = resolve_resource_path(state.inner(), &resource)?;
+    ensure_reveal_target_exists(&path)?;
+
+    if app.opener().reveal_item_in_dir(&path).is_err() {
+        let dir = path.parent().unwrap_or(path.as_path());
+        app.opener()
+            .open_path(dir.to_string_lossy().into_owned(), None::<&str>)
+            // Bare detail only: the frontend toast already prefixes
+            // "Impossibile aprire la cartella:" (useResource.ts), so prefixing
+            // here too would double it.
+            .map_err(|e| CommandError::new("reveal-failed", e.to_string()))?;
+    }
+
+    Ok(())
+}
+
+/// Whether the configured work directory is reachable right now, for the
+/// UI's "reconnect your drive" banner. Also updates
+/// `AppStatus::work_directory_unavailable`; when the directory has just come
+/// back, the auto-download scan paused meanwhile runs again.
+#[tauri::command]
+pub async fn check_work_directory(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<bool, CommandError> {
+    let work_dir = state
+        .config
+        .read()?
+        .work_directory
+        .clone()
+        .ok_or(FileError::WorkDirectoryNotSet)?;
+
+    let was_unavailable = state.status.read()?.work_directory_unavailable;
+    let available = crate::services::workdir::refresh_availability(&app, &work_dir);
+    if was_unavailable && available {
+        state.download_queue.scan_and_queue(app.clone()).await;
+    }
+    Ok(available)
+}
+
+/// The configured work directory, which must exist on disk (a drive that
+/// isn't plugged in gives `WorkDirectoryNotFound`).
+fn existing_work_directory(config: &AppConfig) -> Result<PathBuf, FileError> {
+    let work_dir = config
+        .work_directory
+        .clone()
+        .ok_or(FileError::WorkDirectoryNotSet)?;
+    if !work_dir.is_dir() {
+        return Err(FileError::WorkDirectoryNotFound(work_dir));
+    }
+    Ok(work_dir)
+}
+
+/// Create each of `dirs` that doesn't exist yet; returns how many were
+/// created.
+fn create_missing_dirs<'a>(
+    dirs: impl IntoIterator<Item = &'a PathBuf>,
+) -> Result<usize, FileError> {
+    let mut created = 0;
+    for dir in dirs {
+        if dir.is_dir() {
+            continue;
+        }
+        std::fs::create_dir_all(dir).map_err(|source| FileError::CreateDirectoryFailed {
+            path: dir.clone(),
+            source,
+        })?;
+        created += 1;
+    }
+    Ok(created)
+}
+
+/// Create the destination folder (`{work_dir}/{week}/` in the default
+/// layout) of every loaded resource up front, so cloud-synced work
+/// directories don't churn as folders appear one download at a time and
+/// files can be dropped in by hand. Existing folders are left alone;
+/// returns how many were created.
+#[tauri::command]
+pub fn ensure_week_directories(state: State<'_, AppState>) -> Result<usize, CommandError> {
+    let config = state.config.read()?.clone();
+    existing_work_directory(&config)?;
+    let dirs: std::collections::BTreeSet<PathBuf> = state
+        .resources
+        .read()?
+        .iter()
+        .filter_map(|resource| crate::services::download::destination_dir(&config, resource))
+        .collect();
+    let created = create_missing_dirs(&dirs)?;
+    tracing::info!("Ensured {} week folder(s), {} created", dirs.len(), created);
+    Ok(created)
+}
+
+/// Bytes available on the work directory's volume, for the pre-download
+/// "only 1 GB free" warning next to the size estimate. `None` while no work
+/// directory is set; a directory that isn't reachable (unplugged drive)
+/// gives the usual `work-dir-not-found`.
+#[tauri::command]
+pub async fn get_work_directory_free_space(
+    state: State<'_, AppState>,
+) -> Result<Option<u64>, CommandError> {
+    let config = state.config.read()?.clone();
+    if config.work_directory.is_none() {
+        return Ok(None);
+    }
+    let work_dir = existing_work_directory(&config)?;
+    // Blocking: a sleeping network drive can take a while to answer.
+    tauri::async_runtime::spawn_blocking(move || fs4::available_space(&work_dir))
+        .await
+        .map_err(|e| CommandError::new("task-join-failed", e.to_string()))?
+        .map(Some)
+        .map_err(|e| CommandError::new("free-space-unavailable", e.to_string()))
+}
+
+/// Open the configured work directory (not a specific week folder) in the
+/// system file manager. Errors with `work-dir-not-set` if the user hasn't
+/// configured one yet, or with the not-found `FileError` code when it's
+/// missing, via the same `FileError` mapping used elsewhere.
+#[tauri::command]
+pub fn open_work_directory(state: State<'_, AppState>, app: AppHandle) -> Result<(), CommandError> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let work_dir = {
+        let config = state.config.read()?;
+        existing_work_directory(&config)?
+    };
+
+    app.opener()
+        .open_path(work_dir.to_string_lossy().into_owned(), None::<&str>)
+        // Bare detail only, same convention as reveal_resource: the frontend
+        // toast supplies its own prefix.
+        .map_err(|e| CommandError::new("open-work-dir-failed", e.to_string()))?;
+
+    Ok(())
+}
+
+/// Open the archive folder (`.archive` in the work directory, or the
+/// configured `archive_directory`) so old weeks can be browsed. Errors with
+/// `archive-dir-missing` while nothing has been archived yet.
+#[tauri::command]
+pub fn open_archive_directory(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), CommandError> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let archive_root = {
+        let config = state.config.read()?;
+        let work_dir = existing_work_directory(&config)?;
+        crate::services::resolve_archive_root(&work_dir, config.effective_archive_directory())
+    };
+    if !archive_root.is_dir() {
+        return Err(CommandError::new(
+            "archive-dir-missing",
+            "No week has been archived yet",
+        ));
+    }
+
+    app.opener()
+        .open_path(archive_root.to_string_lossy().into_owned(), None::<&str>)
+        // Bare detail only, same convention as open_work_directory.
+        .map_err(|e| CommandError::new("open-archive-dir-failed", e.to_string()))?;
+
+    Ok(())
+}
+
+/// Resolved locations of the stores, work directory and logs, each with
+/// whether it exists and is writable, for support requests.
+#[tauri::command]
+pub fn get_diagnostics(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<crate::services::diagnostics::Diagnostics, CommandError> {
+    let work_dir = state.config.read()?.work_directory.clone();
+    Ok(crate::services::diagnostics::collect_diagnostics(
+        &app,
+        work_dir.as_deref(),
+    ))
+}
+
+/// Open the folder holding the log files in the system file manager, so a
+/// user can attach them to a problem report.
+#[tauri::command]
+pub fn open_log_folder(app: AppHandle) -> Result<(), CommandError> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let log_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| CommandError::new("log-dir-unavailable", e.to_string()))?;
+    std::fs::create_dir_all(&log_dir)
+        .map_err(|e| CommandError::new("log-dir-unavailable", e.to_string()))?;
+
+    app.opener()
+        .open_path(log_dir.to_string_lossy().into_owned(), None::<&str>)
+        // Bare detail only, same convention as open_work_directory.
+        .map_err(|e| CommandError::new("open-log-dir-failed", e.to_string()))?;
+
+    Ok(())
+}
+
+/// Ask for the file to export the configuration to.
+#[tauri::command]
+pub async fn pick_config_export_path(app: AppHandle) -> Result<Option<String>, CommandError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let path = app
+        .dialog()
+        .file()
+        .add_filter("JSON", &["json"])
+        .set_file_name("church-helper-config.json")
+        .blocking_save_file();
+
+    Ok(path.map(|p| p.to_string()))
+}
+
+/// Ask for a previously exported configuration file.
+#[tauri::command]
+pub async fn pick_config_import_path(app: AppHandle) -> Result<Option<String>, CommandError> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let path = app
+        .dialog()
+        .file()
+        .add_filter("JSON", &["json"])
+        .blocking_pick_file();
+
+    Ok(path.map(|p| p.to_string()))
+}
+
+/// Write the current configuration as pretty JSON to `path`, to be imported
+/// on another machine.
+#[tauri::command]
+pub fn export_config(state: State<'_, AppState>, path: String) -> Result<(), CommandError> {
+    let config = state.config.read()?.clone();
+    let json = serde_json::to_string_pretty(&config).map_err(|e| {
+        CommandError::new(
+            "config-serialize-failed",
+            format!("Failed to serialize config: {e}"),
+        )
+    })?;
+    std::fs::write(&path, json)
+        .map_err(|e| CommandError::new("config-export-failed", format!("{path}: {e}")))?;
+    tracing::info!("Configuration exported to {}", path);
+    Ok(())
+}
+
+/// Result of `import_config`: the configuration now in effect, and whether
+/// the imported work directory had to be dropped because it doesn't exist
+/// on this machine (the UI should then ask for one).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigImportResult {
+    pub config: AppConfig,
+    pub work_directory_cleared: bool,
+}
+
+/// Clear a `work_directory` that doesn't exist here. Returns whether it was
+/// cleared.
+pub(crate) fn clear_missing_work_directory(config: &mut AppConfig) -> bool {
+    match &config.work_directory {
+        Some(dir) if !dir.is_dir() => {
+            tracing::warn!(
+                "Imported work directory {:?} does not exist on this machine, clearing it",
+                dir
+            );
+            config.work_directory = None;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Read a configuration exported by `export_config` and apply it like
+/// `set_config` would. The OS autostart preference is machine-specific and
+/// kept from the current config; an imported work directory that doesn't
+/// exist here is cleared, leaving none set until the user picks one.
+#[tauri::command]
+pub async fn import_config(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    path: String,
+) -> Result<ConfigImportResult, CommandError> {
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|e| CommandError::new("config-import-failed", format!("{path}: {e}")))?;
+    let mut config: AppConfig = serde_json::from_str(&raw).map_err(|e| {
+        CommandError::new(
+            "config-import-failed",
+            format!("{path} is not a valid configuration file: {e}"),
+        )
+    })?;
+
+    let work_directory_cleared = clear_missing_work_directory(&mut config);
+    config.autostart_enabled = state.config.read()?.autostart_enabled;
+
+    apply_config(&state, app, config).await?;
+    tracing::info!("Configuration imported from {}", path);
+
+    Ok(ConfigImportResult {
+        config: state.config.read()?.clone(),
+        work_directory_cleared,
+    })
+}
+
+/// The `settings.json` or `cache.json` store as one JSON object.
+fn store_snapshot(app: &AppHandle, name: &str) -> Result<serde_json::Value, CommandError> {
+    use tauri_plugin_store::StoreExt;
+    let store = app.store(name)?;
+    Ok(serde_json::Value::Object(
+        store.entries().into_iter().collect(),
+    ))
+}
+
+/// Write a support bundle to `path` (see `services::state_bundle`): both
+/// stores with credentials redacted, every week manifest, the newest logs
+/// and the work directory path. Downloaded files are not included.
 #[tauri::command]
-pub fn reveal_resource(
+pub async fn export_state_bundle(
     state: State<'_, AppState>,
     app: AppHandle,
-    resource: Resource,
-) -> Result<(), CommandError> {
-    use tauri_plugin_opener::OpenerExt;
-
-    let path = resolve_resource_path(state.inner(), &resource)?;
-    ensure_reveal_target_exists(&path)?;
+    path: String,
+) -> Result<crate::services::state_bundle::BundleSummary, CommandError> {
+    use crate::services::state_bundle::{recent_logs, write_bundle, BundleInfo, BUNDLE_LOG_FILES};
 
-    if app.opener().reveal_item_in_dir(&path).is_err() {
-        let dir = path.parent().unwrap_or(path.as_path());
-        app.opener()
-            .open_path(dir.to_string_lossy().into_owned(), None::<&str>)
-            // Bare detail only: the frontend toast already prefixes
-            // "Impossibile aprire la cartella:" (useResource.ts), so prefixing
-            // here too would double it.
-            .map_err(|e| CommandError::new("reveal-failed", e.to_string()))?;
-    }
+    let info = {
+        let config = state.config.read()?;
+        BundleInfo {
+            app_version: app.package_info().version.to_string(),
+            created_at: chrono::Utc::now(),
+            work_directory: config.work_directory.clone(),
+            roots: manifest_roots(&config).unwrap_or_default(),
+        }
+    };
+    let settings = store_snapshot(&app, "settings.json")?;
+    let cache = store_snapshot(&app, "cache.json")?;
+    let logs = app
+        .path()
+        .app_log_dir()
+        .map(|dir| recent_logs(&dir, BUNDLE_LOG_FILES))
+        .unwrap_or_default();
+
+    let dest = PathBuf::from(&path);
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        write_bundle(&dest, &info, &settings, &cache, &logs)
+    })
+    .await
+    .map_err(|e| CommandError::new("task-join-failed", e.to_string()))?
+    .map_err(|e| CommandError::new("state-bundle-export-failed", format!("{path}: {e}")))?;
+
+    tracing::info!(
+        "State bundle exported to {} ({} manifests, {} logs)",
+        path,
+        summary.manifests,
+        summary.logs
+    );
+    Ok(summary)
+}
 
-    Ok(())
+/// Result of `import_state_bundle`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StateBundleImportResult {
+    /// Manifests written into week folders of the current work directory.
+    pub manifests_restored: usize,
+    /// Manifests skipped because the week folder already has one.
+    pub manifests_skipped: usize,
+    pub work_directory_cleared: bool,
 }
 
-/// Open the configured work directory (not a specific week folder) in the
-/// system file manager. Errors with `work-dir-not-set` if the user hasn't
-/// configured one yet, via the same `FileError` mapping used elsewhere.
+/// Load a bundle written by `export_state_bundle` over the current stores,
+/// to reproduce a user's state. Debug builds only. Redacted credentials
+/// stay redacted, the work directory is kept when the bundle's doesn't
+/// exist here, and existing manifests are never overwritten. The app has to
+/// be restarted to pick the restored state up.
 #[tauri::command]
-pub fn open_work_directory(state: State<'_, AppState>, app: AppHandle) -> Result<(), CommandError> {
-    use tauri_plugin_opener::OpenerExt;
+pub async fn import_state_bundle(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    path: String,
+) -> Result<StateBundleImportResult, CommandError> {
+    use tauri_plugin_store::StoreExt;
 
-    let work_dir = {
-        let config = state.config.read()?;
-        config
-            .work_directory
-            .clone()
-            .ok_or(FileError::WorkDirectoryNotSet)?
-    };
+    if !cfg!(debug_assertions) {
+        return Err(CommandError::new(
+            "import-disabled",
+            "State bundles can only be imported in development builds",
+        ));
+    }
 
-    app.opener()
-        .open_path(work_dir.to_string_lossy().into_owned(), None::<&str>)
-        // Bare detail only, same convention as reveal_resource: the frontend
-        // toast supplies its own prefix.
-        .map_err(|e| CommandError::new("open-work-dir-failed", e.to_string()))?;
+    let source = PathBuf::from(&path);
+    let mut bundle = tauri::async_runtime::spawn_blocking(move || {
+        crate::services::state_bundle::read_bundle(&source)
+    })
+    .await
+    .map_err(|e| CommandError::new("task-join-failed", e.to_string()))?
+    .map_err(|e| CommandError::new("state-bundle-import-failed", format!("{path}: {e}")))?;
+
+    let current_work_dir = state.config.read()?.work_directory.clone();
+    let imported_work_dir = bundle
+        .settings
+        .pointer("/config/work_directory")
+        .and_then(|dir| dir.as_str())
+        .map(PathBuf::from);
+    let work_directory_cleared = imported_work_dir.is_some_and(|dir| !dir.is_dir());
+    if work_directory_cleared {
+        if let Some(config) = bundle.settings.get_mut("config") {
+            config["work_directory"] = current_work_dir
+                .as_ref()
+                .map_or(serde_json::Value::Null, |dir| {
+                    serde_json::Value::String(dir.to_string_lossy().into_owned())
+                });
+        }
+    }
 
-    Ok(())
+    for (name, snapshot) in [
+        ("settings.json", bundle.settings),
+        ("cache.json", bundle.cache),
+    ] {
+        let serde_json::Value::Object(entries) = snapshot else {
+            continue;
+        };
+        let store = app.store(name)?;
+        store.clear();
+        for (key, value) in entries {
+            store.set(key, value);
+        }
+        store.save()?;
+    }
+
+    let mut manifests_restored = 0;
+    let mut manifests_skipped = 0;
+    if let Some(work_dir) = &current_work_dir {
+        for (week, bytes) in &bundle.manifests {
+            let week_dir = work_dir.join(week);
+            let manifest = week_dir.join(crate::services::manifest::MANIFEST_FILE_NAME);
+            if manifest.exists() {
+                manifests_skipped += 1;
+                continue;
+            }
+            std::fs::create_dir_all(&week_dir).map_err(|source| {
+                FileError::CreateDirectoryFailed {
+                    path: week_dir.clone(),
+                    source,
+                }
+            })?;
+            std::fs::write(&manifest, bytes).map_err(|e| {
+                CommandError::new(
+                    "state-bundle-import-failed",
+                    format!("{}: {e}", manifest.display()),
+                )
+            })?;
+            manifests_restored += 1;
+        }
+    }
+
+    tracing::info!(
+        "State bundle imported from {} ({} manifests restored, {} skipped); restart to apply",
+        path,
+        manifests_restored,
+        manifests_skipped
+    );
+    Ok(StateBundleImportResult {
+        manifests_restored,
+        manifests_skipped,
+        work_directory_cleared,
+    })
 }
 
 /// Get the size of a file from its URL without downloading it
 #[tauri::command]
-pub async fn get_file_size(state: State<'_, AppState>, url: String) -> Result<u64, CommandError> {
+pub async fn get_file_size(
+    state: State<'_, AppState>,
+    app: AppHandle,
+    url: String,
+) -> Result<u64, CommandError> {
+    let result = resolve_file_size(&state, url.clone()).await;
+    if let Err(e) = &result {
+        if e.code == "rate-limited" {
+            let wait_secs = e
+                .details
+                .as_ref()
+                .and_then(|details| details["wait_secs"].as_u64())
+                .unwrap_or_default();
+            crate::services::http::emit_rate_limited(
+                &app,
+                crate::services::http::RateLimited {
+                    source: crate::services::http::RateLimitedSource::FileSize,
+                    url,
+                    resource_id: None,
+                    wait_secs,
+                },
+            );
+        }
+    }
+    result
+}
+
+/// Size of `url` from `file_size_cache`, or from a HEAD request whose outcome
+/// (size or failure) is cached. Shared by `get_file_size` and
+/// `estimate_download_size`.
+async fn resolve_file_size(state: &AppState, url: String) -> Result<u64, CommandError> {
     // Check cache first
     {
-        let cache = state.file_size_cache.read()?;
-        if let Some(&size) = cache.get(&url) {
-            if size == u64::MAX {
-                // Negative cache hit - this URL previously failed
+        let cached = state.file_size_cache.write()?.get(&url);
+        match cached {
+            Some(CachedSize::Unavailable { .. }) => {
+                // Negative cache hit - this URL failed recently
                 tracing::debug!("Cache hit (negative) for file size: {}", url);
                 return Err(CommandError::new(
                     "file-size-unavailable",
                     "File size unavailable (cached failure)",
                 ));
             }
-            tracing::debug!("Cache hit for file size: {}", url);
-            return Ok(size);
+            Some(CachedSize::Known(size)) => {
+                tracing::debug!("Cache hit for file size: {}", url);
+                return Ok(size);
+            }
+            None => {}
         }
     }
 
-    // Cache miss - fetch from remote
+    // Cache miss - fetch from remote. Offline, a miss is not a failure to
+    // remember: the size is simply unknown for now.
+    if state.is_offline() {
+        return Err(offline_error());
+    }
     tracing::debug!("Cache miss for file size, fetching: {}", url);
-    let response = state
-        .shared_http_client
-        .head(&url)
-        .send()
-        .await
-        .map_err(|e| {
-            // Cache negative result to avoid repeated failures
-            let _ = state.file_size_cache.write().map(|mut cache| {
-                cache.insert(url.clone(), u64::MAX);
-                tracing::debug!("Cached negative result (request failed) for: {}", url);
-            });
-            CommandError::new(
-                "head-request-failed",
-                format!("Failed to fetch headers: {e}"),
-            )
-        })?;
+    let response = state.http_client().head(&url).send().await.map_err(|e| {
+        // Cache negative result to avoid repeated failures
+        let _ = state.file_size_cache.write().map(|mut cache| {
+            cache.record_failure(url.clone());
+            tracing::debug!("Cached negative result (request failed) for: {}", url);
+        });
+        CommandError::new(
+            "head-request-failed",
+            format!("Failed to fetch headers: {e}"),
+        )
+        .with_details(serde_json::json!({ "url": url }))
+    })?;
+
+    // Throttled: the negative cache below keeps this URL from being probed
+    // again for a while; the caller learns how long the server asked for.
+    if let Some(wait) = crate::services::http::retry_after(
+        response.status(),
+        response.headers(),
+        chrono::Utc::now(),
+    ) {
+        let _ = state.file_size_cache.write().map(|mut cache| {
+            cache.record_failure(url.clone());
+        });
+        return Err(CommandError::new(
+            "rate-limited",
+            format!("Server asked to retry after {}s", wait.as_secs()),
+        )
+        .with_details(serde_json::json!({ "url": url, "wait_secs": wait.as_secs() })));
+    }
 
     if !response.status().is_success() {
         // Cache negative result for non-success status
         let _ = state.file_size_cache.write().map(|mut cache| {
-            cache.insert(url.clone(), u64::MAX);
+            cache.record_failure(url.clone());
             tracing::debug!(
                 "Cached negative result (status {}) for: {}",
                 response.status(),
@@ -753,7 +2663,8 @@ pub async fn get_file_size(state: State<'_, AppState>, url: String) -> Result<u6
         return Err(CommandError::new(
             "http-status-error",
             format!("Request failed with status: {}", response.status()),
-        ));
+        )
+        .with_details(serde_json::json!({ "status": response.status().as_u16() })));
     }
 
     let content_length = response
@@ -773,7 +2684,7 @@ pub async fn get_file_size(state: State<'_, AppState>, url: String) -> Result<u6
         None => {
             // Cache negative result for missing/invalid Content-Length
             let _ = state.file_size_cache.write().map(|mut cache| {
-                cache.insert(url.clone(), u64::MAX);
+                cache.record_failure(url.clone());
                 tracing::debug!("Cached negative result (no Content-Length) for: {}", url);
             });
             Err(CommandError::new(
@@ -784,17 +2695,101 @@ pub async fn get_file_size(state: State<'_, AppState>, url: String) -> Result<u6
     }
 }
 
+/// Upper bound on concurrent HEAD requests issued by `estimate_download_size`.
+const SIZE_ESTIMATE_CONCURRENCY: usize = 6;
+
+/// Result of [`estimate_download_size`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DownloadSizeEstimate {
+    /// Sum of every size that could be determined.
+    pub total_bytes: u64,
+    /// Resources whose size couldn't be determined (HEAD failed or no
+    /// Content-Length); not included in `total_bytes`.
+    pub unknown_count: usize,
+}
+
+/// Estimate how many bytes downloading `resources` would take, for the bulk
+/// download confirmation dialog. Sizes come from `file_size_cache` or a HEAD
+/// request (at most [`SIZE_ESTIMATE_CONCURRENCY`] in flight); YouTube links
+/// are shortcuts, not downloads, and count as zero. Fails with
+/// `size-estimate-cancelled` when `cancel_download_size_estimate` (or a newer
+/// estimate) supersedes this one; in-flight HEADs are dropped.
+#[tauri::command]
+pub async fn estimate_download_size(
+    state: State<'_, AppState>,
+    resources: Vec<Resource>,
+) -> Result<DownloadSizeEstimate, CommandError> {
+    let prefer_optimized = state.config.read()?.prefer_optimized;
+    estimate_resources_size(&state, &resources, prefer_optimized).await
+}
+
+async fn estimate_resources_size(
+    state: &AppState,
+    resources: &[Resource],
+    prefer_optimized: bool,
+) -> Result<DownloadSizeEstimate, CommandError> {
+    use futures_util::StreamExt;
+
+    let generation = state
+        .size_estimate_generation
+        .fetch_add(1, Ordering::SeqCst)
+        + 1;
+    let urls: Vec<String> = resources
+        .iter()
+        .filter(|r| !r.is_youtube())
+        .map(|r| r.get_effective_download_url(prefer_optimized).to_string())
+        .collect();
+
+    let mut sizes = futures_util::stream::iter(urls)
+        .map(|url| resolve_file_size(state, url))
+        .buffer_unordered(SIZE_ESTIMATE_CONCURRENCY);
+
+    let mut estimate = DownloadSizeEstimate {
+        total_bytes: 0,
+        unknown_count: 0,
+    };
+    while let Some(size) = sizes.next().await {
+        if state.size_estimate_generation.load(Ordering::SeqCst) != generation {
+            return Err(CommandError::new(
+                "size-estimate-cancelled",
+                "Download size estimate cancelled",
+            ));
+        }
+        match size {
+            Ok(size) => estimate.total_bytes = estimate.total_bytes.saturating_add(size),
+            Err(_) => estimate.unknown_count += 1,
+        }
+    }
+    Ok(estimate)
+}
+
+/// Stop the running `estimate_download_size` (the confirmation dialog was
+/// dismissed). No-op when none is running.
+#[tauri::command]
+pub fn cancel_download_size_estimate(state: State<'_, AppState>) {
+    state
+        .size_estimate_generation
+        .fetch_add(1, Ordering::SeqCst);
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ResourceSummary {
     pub total: usize,
     pub downloaded: usize,
+    /// Not downloaded yet but with a `.part` on disk: in progress, paused
+    /// or interrupted.
+    pub downloading: usize,
     pub active: usize,
     pub queued: usize,
 }
 
+/// Upper bound on the blocking tasks `get_resource_summary` spreads its
+/// existence checks over; each `exists()` can take a while on network drives.
+const SUMMARY_FS_CHECK_TASKS: usize = 8;
+
 /// Batched per-resource status for the UI. `file_size`/`optimized_file_size`
 /// come exclusively from the cached HEAD sizes (never a network request); a
-/// missing or sentinel-cached (`u64::MAX`) entry serializes as `None`.
+/// missing or failed (negative-cached) entry serializes as `None`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ResourceStatus {
     pub downloaded: bool,
@@ -802,13 +2797,37 @@ pub struct ResourceStatus {
     pub optimized_file_size: Option<u64>,
 }
 
-/// Read a cached HEAD size, treating the `u64::MAX` failure sentinel (negative
-/// cache) as "unknown" so it never leaks to the UI as a real size.
-fn cached_size(size_cache: &HashMap<String, u64>, url: &str) -> Option<u64> {
-    size_cache
-        .get(url)
-        .copied()
-        .filter(|&size| size != u64::MAX)
+/// Registry-first-OR-fs `downloaded` check shared by the status and summary
+/// commands; see [`compute_resources_status`].
+fn is_downloaded(resource: &Resource, registry: &[DownloadedFile], config: &AppConfig) -> bool {
+    let week = resource.week();
+    let registry_hit = registry.iter().any(|entry| {
+        entry.resource_id == resource.id
+            && entry.week == week
+            && !entry.is_superseded
+            && entry.local_path.exists()
+    });
+    registry_hit || crate::services::download::DownloadService::check_file_exists(resource, config)
+}
+
+/// `(downloaded, downloading)` counts over one chunk of resources: a
+/// resource is `downloading` when it isn't downloaded but its `.part` exists.
+pub(crate) fn count_local_files(
+    resources: &[Resource],
+    registry: &[DownloadedFile],
+    config: &AppConfig,
+) -> (usize, usize) {
+    let mut downloaded = 0;
+    let mut downloading = 0;
+    for resource in resources {
+        if is_downloaded(resource, registry, config) {
+            downloaded += 1;
+        } else if crate::services::download::DownloadService::check_partial_exists(resource, config)
+        {
+            downloading += 1;
+        }
+    }
+    (downloaded, downloading)
 }
 
 /// Pure computation of per-resource status. A resource counts as `downloaded`
@@ -816,42 +2835,25 @@ fn cached_size(size_cache: &HashMap<String, u64>, url: &str) -> Option<u64> {
 /// (`resource_id` + `week`) whose `local_path` still exists on disk, OR — as a
 /// fallback when no such registry entry exists — the effective destination file
 /// is present (`check_file_exists`). Sizes are looked up only in `size_cache`;
-/// no network I/O happens here. With `work_dir` `None` every resource is
-/// `downloaded = false`.
+/// no network I/O happens here. Without a configured work directory every
+/// resource is `downloaded = false`.
 pub(crate) fn compute_resources_status(
     resources: &[Resource],
     registry: &[DownloadedFile],
-    work_dir: Option<&Path>,
-    prefer_optimized: bool,
-    size_cache: &HashMap<String, u64>,
+    config: &AppConfig,
+    size_cache: &FileSizeCache,
 ) -> HashMap<i64, ResourceStatus> {
     let mut statuses = HashMap::with_capacity(resources.len());
 
     for resource in resources {
-        let downloaded = match work_dir {
-            Some(work_dir) => {
-                let week = resource.week();
-                let registry_hit = registry.iter().any(|entry| {
-                    entry.resource_id == resource.id
-                        && entry.week == week
-                        && !entry.is_superseded
-                        && entry.local_path.exists()
-                });
-                registry_hit
-                    || crate::services::download::DownloadService::check_file_exists(
-                        resource,
-                        work_dir,
-                        prefer_optimized,
-                    )
-            }
-            None => false,
-        };
+        let downloaded =
+            config.work_directory.is_some() && is_downloaded(resource, registry, config);
 
-        let file_size = cached_size(size_cache, &resource.download_url);
+        let file_size = size_cache.known_size(&resource.download_url);
         let optimized_file_size = resource
             .optimized_video_url
             .as_deref()
-            .and_then(|url| cached_size(size_cache, url));
+            .and_then(|url| size_cache.known_size(url));
 
         statuses.insert(
             resource.id,
@@ -872,25 +2874,16 @@ pub async fn get_resources_status(
 ) -> Result<HashMap<i64, ResourceStatus>, CommandError> {
     // Snapshot everything under short read locks, then compute off the async
     // runtime. No lock guard is ever held across the await (spawn_blocking).
-    let (resources, registry, work_dir, prefer_optimized, size_cache) = {
+    let (resources, registry, config, size_cache) = {
         let resources = state.resources.read()?.clone();
         let registry = state.downloaded_files.read()?.clone();
-        let (work_dir, prefer_optimized) = {
-            let config = state.config.read()?;
-            (config.work_directory.clone(), config.prefer_optimized)
-        };
+        let config = state.config.read()?.clone();
         let size_cache = state.file_size_cache.read()?.clone();
-        (resources, registry, work_dir, prefer_optimized, size_cache)
+        (resources, registry, config, size_cache)
     };
 
     tauri::async_runtime::spawn_blocking(move || {
-        compute_resources_status(
-            &resources,
-            &registry,
-            work_dir.as_deref(),
-            prefer_optimized,
-            &size_cache,
-        )
+        compute_resources_status(&resources, &registry, &config, &size_cache)
     })
     .await
     .map_err(|e| CommandError::new("task-join-failed", e.to_string()))
@@ -902,14 +2895,19 @@ pub async fn get_resource_summary(
 ) -> Result<ResourceSummary, CommandError> {
     // Clone data that needs to be used after await points or potentially long operations
     // This avoids holding non-Send RwLockGuard across await points
-    let (resources, registry, work_dir, prefer_optimized) = {
-        let resources = state.resources.read()?.clone();
+    let (resources, registry, config) = {
+        // Withdrawn resources can't be downloaded, so they don't count
+        // towards the total either.
+        let resources: Vec<Resource> = state
+            .resources
+            .read()?
+            .iter()
+            .filter(|r| r.is_active)
+            .cloned()
+            .collect();
         let registry = state.downloaded_files.read()?.clone();
-        let (work_dir, prefer_optimized) = {
-            let config = state.config.read()?;
-            (config.work_directory.clone(), config.prefer_optimized)
-        };
-        (resources, registry, work_dir, prefer_optimized)
+        let config = state.config.read()?.clone();
+        (resources, registry, config)
     };
 
     // Now we can await without holding the lock guards
@@ -917,27 +2915,38 @@ pub async fn get_resource_summary(
     let queued = state.download_queue.queue_len().await;
     let total = resources.len();
 
-    // Reuse the same registry-first-OR-fs logic as the batched status command;
-    // the size cache is irrelevant to the downloaded count, so pass an empty one.
-    let downloaded = tauri::async_runtime::spawn_blocking(move || {
-        let empty_cache = HashMap::new();
-        compute_resources_status(
-            &resources,
-            &registry,
-            work_dir.as_deref(),
-            prefer_optimized,
-            &empty_cache,
-        )
-        .values()
-        .filter(|status| status.downloaded)
-        .count()
-    })
-    .await
-    .map_err(|e| CommandError::new("task-join-failed", e.to_string()))?;
+    // Same registry-first-OR-fs logic as the batched status command, split
+    // into chunks checked on parallel blocking tasks.
+    let mut downloaded = 0;
+    let mut downloading = 0;
+    if config.work_directory.is_some() {
+        let registry = Arc::new(registry);
+        let config = Arc::new(config);
+        let chunk_size = resources.len().div_ceil(SUMMARY_FS_CHECK_TASKS).max(1);
+        let tasks: Vec<_> = resources
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let chunk = chunk.to_vec();
+                let registry = registry.clone();
+                let config = config.clone();
+                tauri::async_runtime::spawn_blocking(move || {
+                    count_local_files(&chunk, &registry, &config)
+                })
+            })
+            .collect();
+        for task in tasks {
+            let (chunk_downloaded, chunk_downloading) = task
+                .await
+                .map_err(|e| CommandError::new("task-join-failed", e.to_string()))?;
+            downloaded += chunk_downloaded;
+            downloading += chunk_downloading;
+        }
+    }
 
     Ok(ResourceSummary {
         total,
         downloaded,
+        downloading,
         active,
         queued,
     })
@@ -967,6 +2976,14 @@ mod tests {
         }
     }
 
+    /// Default config (by-week layout, `prefer_optimized`) rooted at `wd`.
+    fn work_config(wd: &Path) -> AppConfig {
+        AppConfig {
+            work_directory: Some(wd.to_path_buf()),
+            ..AppConfig::default()
+        }
+    }
+
     fn make_downloaded(
         resource: &Resource,
         local_path: PathBuf,
@@ -985,7 +3002,8 @@ mod tests {
     /// Write a real file at the resource's derived destination path so that
     /// `check_file_exists` (the fs fallback) sees it.
     fn create_dest_file(work_dir: &Path, resource: &Resource) -> PathBuf {
-        let dest = crate::services::download::resolve_dest_path(resource, work_dir, true);
+        let dest = crate::services::download::resource_dest_path(&work_config(work_dir), resource)
+            .unwrap();
         std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
         std::fs::write(&dest, b"x").unwrap();
         dest
@@ -1028,7 +3046,8 @@ mod tests {
         std::fs::write(&reg_path, b"x").unwrap();
         let registry = vec![make_downloaded(&r, reg_path, false)];
 
-        let out = compute_resources_status(&[r], &registry, Some(wd), true, &HashMap::new());
+        let out =
+            compute_resources_status(&[r], &registry, &work_config(wd), &FileSizeCache::new());
         assert!(out[&1].downloaded);
     }
 
@@ -1041,7 +3060,8 @@ mod tests {
         // Registry points at a non-existent path and no derived dest exists.
         let registry = vec![make_downloaded(&r, wd.join("missing.mp4"), false)];
 
-        let out = compute_resources_status(&[r], &registry, Some(wd), true, &HashMap::new());
+        let out =
+            compute_resources_status(&[r], &registry, &work_config(wd), &FileSizeCache::new());
         assert!(!out[&2].downloaded);
     }
 
@@ -1070,14 +3090,13 @@ mod tests {
         let out = compute_resources_status(
             std::slice::from_ref(&r),
             &[],
-            Some(wd),
-            true,
-            &HashMap::new(),
+            &work_config(wd),
+            &FileSizeCache::new(),
         );
         assert!(out[&9].downloaded);
 
         std::fs::remove_file(&dest).unwrap();
-        let out = compute_resources_status(&[r], &[], Some(wd), true, &HashMap::new());
+        let out = compute_resources_status(&[r], &[], &work_config(wd), &FileSizeCache::new());
         assert!(!out[&9].downloaded);
     }
 
@@ -1092,7 +3111,8 @@ mod tests {
         std::fs::write(&sup_path, b"x").unwrap();
         let registry = vec![make_downloaded(&r, sup_path, true)];
 
-        let out = compute_resources_status(&[r], &registry, Some(wd), true, &HashMap::new());
+        let out =
+            compute_resources_status(&[r], &registry, &work_config(wd), &FileSizeCache::new());
         assert!(!out[&3].downloaded);
     }
 
@@ -1103,7 +3123,7 @@ mod tests {
         let r = make_resource(4, "https://example.com/file4.mp4");
         create_dest_file(wd, &r);
 
-        let out = compute_resources_status(&[r], &[], Some(wd), true, &HashMap::new());
+        let out = compute_resources_status(&[r], &[], &work_config(wd), &FileSizeCache::new());
         assert!(out[&4].downloaded);
     }
 
@@ -1132,9 +3152,8 @@ mod tests {
         let out = compute_resources_status(
             std::slice::from_ref(&r),
             &registry,
-            Some(wd),
-            true,
-            &HashMap::new(),
+            &work_config(wd),
+            &FileSizeCache::new(),
         );
         assert!(
             !out[&5].downloaded,
@@ -1143,7 +3162,8 @@ mod tests {
 
         // Now the fs fallback finds the file in the resource's own week.
         create_dest_file(wd, &r);
-        let out = compute_resources_status(&[r], &registry, Some(wd), true, &HashMap::new());
+        let out =
+            compute_resources_status(&[r], &registry, &work_config(wd), &FileSizeCache::new());
         assert!(out[&5].downloaded, "fs fallback finds the file");
     }
 
@@ -1155,12 +3175,12 @@ mod tests {
         let mut r = make_resource(6, "https://example.com/file6.mp4");
         r.optimized_video_url = Some("https://example.com/file6-opt.mp4".to_string());
 
-        let mut cache = HashMap::new();
-        // Real size for the original, sentinel (failed HEAD) for the optimized.
-        cache.insert(r.download_url.clone(), 1234u64);
-        cache.insert("https://example.com/file6-opt.mp4".to_string(), u64::MAX);
+        let mut cache = FileSizeCache::new();
+        // Real size for the original, failed HEAD for the optimized.
+        cache.insert(r.download_url.clone(), 1234);
+        cache.record_failure("https://example.com/file6-opt.mp4".to_string());
 
-        let out = compute_resources_status(&[r], &[], Some(wd), true, &cache);
+        let out = compute_resources_status(&[r], &[], &work_config(wd), &cache);
         assert_eq!(out[&6].file_size, Some(1234));
         assert_eq!(out[&6].optimized_file_size, None);
     }
@@ -1177,7 +3197,12 @@ mod tests {
         std::fs::write(&reg_path, b"x").unwrap();
         let registry = vec![make_downloaded(&r, reg_path, false)];
 
-        let out = compute_resources_status(&[r], &registry, None, true, &HashMap::new());
+        let out = compute_resources_status(
+            &[r],
+            &registry,
+            &AppConfig::default(),
+            &FileSizeCache::new(),
+        );
         assert!(!out[&7].downloaded);
     }
 
@@ -1225,10 +3250,11 @@ mod tests {
         // dest path in the same week (a real-world collision).
         let a = make_resource(20, "https://a.example.com/shared.mp4");
         let b = make_resource(21, "https://b.example.com/shared.mp4");
-        let shared_dest = crate::services::download::resolve_dest_path(&a, wd, true);
+        let shared_dest =
+            crate::services::download::resource_dest_path(&work_config(wd), &a).unwrap();
         assert_eq!(
             shared_dest,
-            crate::services::download::resolve_dest_path(&b, wd, true),
+            crate::services::download::resource_dest_path(&work_config(wd), &b).unwrap(),
             "test premise: both resources derive the same dest path"
         );
 
@@ -1240,9 +3266,8 @@ mod tests {
         let legacy = compute_resources_status(
             &[a.clone(), b.clone()],
             &[],
-            Some(wd),
-            true,
-            &HashMap::new(),
+            &work_config(wd),
+            &FileSizeCache::new(),
         );
         assert!(legacy[&20].downloaded);
         assert!(
@@ -1258,11 +3283,412 @@ mod tests {
         std::fs::write(&actual_a, b"x").unwrap();
         let registry = vec![make_downloaded(&a, actual_a, false)];
 
-        let out = compute_resources_status(&[a, b], &registry, Some(wd), true, &HashMap::new());
+        let out =
+            compute_resources_status(&[a, b], &registry, &work_config(wd), &FileSizeCache::new());
         assert!(out[&20].downloaded, "registry hit for A");
         assert!(
             !out[&21].downloaded,
             "B has no registry entry and no file at its derived path"
         );
     }
+
+    #[test]
+    fn test_filter_resources_by_category_text_and_youtube() {
+        let mut sermon = make_resource(1, "https://example.com/sermone.mp4");
+        sermon.title = "Sermone del Sabato".to_string();
+        let mut clip = make_resource(2, "https://www.youtube.com/watch?v=abc");
+        clip.title = "Clip".to_string();
+        clip.description = Some("Highlights del SABATO".to_string());
+        let mut offering = make_resource(3, "https://example.com/decime.pdf");
+        offering.category = "decime".to_string();
+        let resources = vec![sermon, clip, offering];
+
+        let ids = |filter: ResourceFilter| -> Vec<i64> {
+            filter_resources(&resources, &filter)
+                .iter()
+                .map(|r| r.id)
+                .collect()
+        };
+
+        assert_eq!(ids(ResourceFilter::default()), vec![1, 2, 3]);
+        assert_eq!(
+            ids(ResourceFilter {
+                category: Some("video".to_string()),
+                ..Default::default()
+            }),
+            vec![1, 2]
+        );
+        // Case-insensitive, over title and description, order preserved.
+        assert_eq!(
+            ids(ResourceFilter {
+                text: Some("sabato".to_string()),
+                ..Default::default()
+            }),
+            vec![1, 2]
+        );
+        assert_eq!(
+            ids(ResourceFilter {
+                text: Some("   ".to_string()),
+                ..Default::default()
+            }),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            ids(ResourceFilter {
+                youtube_only: Some(true),
+                ..Default::default()
+            }),
+            vec![2]
+        );
+        assert_eq!(
+            ids(ResourceFilter {
+                category: Some("video".to_string()),
+                youtube_only: Some(false),
+                ..Default::default()
+            }),
+            vec![1]
+        );
+    }
+
+    #[test]
+    fn test_tally_categories_sorted_and_marked() {
+        let mut resources: Vec<Resource> = (1..=5)
+            .map(|id| make_resource(id, &format!("https://example.com/{id}.mp4")))
+            .collect();
+        resources[3].category = "decime".to_string();
+        resources[4].category = "audio".to_string();
+
+        let out = tally_categories(&resources, &["decime".to_string()]);
+        assert_eq!(
+            out,
+            vec![
+                ResourceCategory {
+                    category: "video".to_string(),
+                    count: 3,
+                    auto_download: false,
+                },
+                ResourceCategory {
+                    category: "audio".to_string(),
+                    count: 1,
+                    auto_download: false,
+                },
+                ResourceCategory {
+                    category: "decime".to_string(),
+                    count: 1,
+                    auto_download: true,
+                },
+            ]
+        );
+        assert!(tally_categories(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_categories() {
+        let resources = vec![make_resource(1, "https://example.com/1.mp4")];
+        let categories = vec!["video".to_string(), "decime".to_string()];
+        assert_eq!(unknown_categories(&categories, &resources), vec!["decime"]);
+        assert_eq!(
+            unknown_categories(&categories, &[]),
+            vec!["video", "decime"]
+        );
+        assert!(unknown_categories(&[], &resources).is_empty());
+    }
+
+    #[test]
+    fn test_count_local_files_distinguishes_partial_downloads() {
+        let tmp = TempDir::new().unwrap();
+        let wd = tmp.path();
+        let done = make_resource(1, "https://example.com/done.mp4");
+        let partial = make_resource(2, "https://example.com/partial.mp4");
+        let missing = make_resource(3, "https://example.com/missing.mp4");
+
+        create_dest_file(wd, &done);
+        let partial_dest =
+            crate::services::download::resource_dest_path(&work_config(wd), &partial).unwrap();
+        std::fs::write(
+            crate::services::download::part_path_for(&partial_dest),
+            b"half",
+        )
+        .unwrap();
+
+        let counts = count_local_files(&[done, partial, missing], &[], &work_config(wd));
+        assert_eq!(counts, (1, 1));
+    }
+
+    #[test]
+    fn test_clear_missing_work_directory() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = AppConfig {
+            work_directory: Some(tmp.path().to_path_buf()),
+            ..AppConfig::default()
+        };
+        assert!(!clear_missing_work_directory(&mut config));
+        assert_eq!(config.work_directory.as_deref(), Some(tmp.path()));
+
+        config.work_directory = Some(tmp.path().join("on-another-machine"));
+        assert!(clear_missing_work_directory(&mut config));
+        assert_eq!(config.work_directory, None);
+
+        config.work_directory = None;
+        assert!(!clear_missing_work_directory(&mut config));
+    }
+
+    #[test]
+    fn test_download_errors_recorded_and_cleared_per_resource() {
+        let state = AppState::default();
+        state.record_download_error(1, "HTTP request failed");
+        state.record_download_error(2, "disk full");
+        state.record_download_error(1, "timed out");
+
+        state.clear_download_error(2);
+        let errors = state.download_errors.read().unwrap().clone();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors.get(&1).map(String::as_str), Some("timed out"));
+    }
+
+    /// Cached sizes are summed, a cached failure is tallied as unknown and
+    /// YouTube links count as zero: no network request is made.
+    #[tokio::test]
+    async fn test_estimate_resources_size_from_cache() {
+        let state = AppState::default();
+        let a = make_resource(1, "https://example.com/a.mp4");
+        let b = make_resource(2, "https://example.com/b.mp4");
+        let broken = make_resource(3, "https://example.com/broken.mp4");
+        let clip = make_resource(4, "https://www.youtube.com/watch?v=abc");
+        {
+            let mut cache = state.file_size_cache.write().unwrap();
+            cache.insert(a.download_url.clone(), 1_000);
+            cache.insert(b.download_url.clone(), 2_500);
+            cache.record_failure(broken.download_url.clone());
+        }
+
+        let estimate = estimate_resources_size(&state, &[a, b, broken, clip], false)
+            .await
+            .unwrap();
+        assert_eq!(
+            estimate,
+            DownloadSizeEstimate {
+                total_bytes: 3_500,
+                unknown_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_week_download_candidates_skips_inactive_and_downloaded() {
+        let tmp = TempDir::new().unwrap();
+        let fresh = make_resource(1, "https://example.com/fresh.mp4");
+        let mut withdrawn = make_resource(2, "https://example.com/withdrawn.mp4");
+        withdrawn.is_active = false;
+        let done = make_resource(3, "https://example.com/done.mp4");
+        let done_path = tmp.path().join("done.mp4");
+        std::fs::write(&done_path, b"video").unwrap();
+        let registry = vec![make_downloaded(&done, done_path, false)];
+        let mut last_week = make_resource(4, "https://example.com/last-week.mp4");
+        last_week.created_at = Utc.with_ymd_and_hms(2026, 1, 12, 12, 0, 0).unwrap();
+
+        let candidates = week_download_candidates(
+            vec![last_week, fresh, withdrawn, done],
+            &registry,
+            &work_config(tmp.path()),
+        );
+        let ids: Vec<i64> = candidates.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1]);
+    }
+
+    /// With the flat layout the week's files share the work directory with
+    /// other weeks' files: only the week's own are listed.
+    #[test]
+    fn test_collect_week_files_flat_layout_lists_only_the_weeks_files() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = work_config(tmp.path());
+        config.folder_layout = FolderLayout::Flat;
+        let this_week = make_resource(1, "https://example.com/this.mp4");
+        let mut other_week = make_resource(2, "https://example.com/other.mp4");
+        other_week.created_at = Utc.with_ymd_and_hms(2026, 1, 12, 12, 0, 0).unwrap();
+        for resource in [&this_week, &other_week] {
+            let dest = crate::services::download::resource_dest_path(&config, resource).unwrap();
+            std::fs::write(dest, b"video").unwrap();
+        }
+        std::fs::write(tmp.path().join("stray.txt"), b"notes").unwrap();
+
+        let week = this_week.week();
+        let paths = week_file_paths(&config, &[this_week, other_week], &[], &week);
+        let names: Vec<String> = collect_week_files(&config, &week, &paths)
+            .into_iter()
+            .map(|file| file.filename)
+            .collect();
+        assert_eq!(names, vec!["this.mp4".to_string()]);
+    }
+
+    /// Offline, cached sizes are still served but a miss fails with
+    /// `offline` without a HEAD request or a negative-cache entry.
+    #[tokio::test]
+    async fn test_resolve_file_size_offline_serves_cache_only() {
+        let state = AppState::default();
+        state.config.write().unwrap().offline_mode = true;
+        state
+            .file_size_cache
+            .write()
+            .unwrap()
+            .insert("https://example.com/a.mp4".to_string(), 42);
+
+        let cached = resolve_file_size(&state, "https://example.com/a.mp4".to_string()).await;
+        assert_eq!(cached.unwrap(), 42);
+
+        let err = resolve_file_size(&state, "https://example.com/b.mp4".to_string())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, "offline");
+        assert!(state
+            .file_size_cache
+            .write()
+            .unwrap()
+            .get("https://example.com/b.mp4")
+            .is_none());
+    }
+
+    /// Serve a single response with `status_line` to one request; returns
+    /// the latest-week URL.
+    fn serve_status_once(status_line: &'static str) -> String {
+        let base = crate::services::mock_http::serve(1, move |_, _| {
+            crate::services::mock_http::Response::new(status_line)
+        });
+        format!("{base}/api/resources/latest-week")
+    }
+
+    #[tokio::test]
+    async fn test_probe_connectivity_separates_server_errors_from_network_errors() {
+        let client = reqwest::Client::new();
+        let timeout = std::time::Duration::from_secs(5);
+
+        let ok = probe_connectivity(&client, &serve_status_once("200 OK"), timeout).await;
+        assert!(ok.reachable);
+        assert_eq!(ok.status, Some(200));
+        assert!(ok.latency_ms.is_some());
+        assert_eq!(ok.failure, None);
+
+        let down = probe_connectivity(
+            &client,
+            &serve_status_once("503 Service Unavailable"),
+            timeout,
+        )
+        .await;
+        assert!(!down.reachable);
+        assert_eq!(down.status, Some(503));
+        assert_eq!(down.failure, None);
+
+        // Bind then drop a listener: nothing accepts on that port anymore.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let refused =
+            probe_connectivity(&client, &format!("http://127.0.0.1:{port}/"), timeout).await;
+        assert!(!refused.reachable);
+        assert_eq!(refused.status, None);
+        assert_eq!(refused.failure, Some(ConnectivityFailure::Connection));
+    }
+
+    #[test]
+    fn test_existing_work_directory_requires_set_and_present_dir() {
+        let mut config = AppConfig::default();
+        assert!(matches!(
+            existing_work_directory(&config),
+            Err(FileError::WorkDirectoryNotSet)
+        ));
+
+        let tmp = TempDir::new().unwrap();
+        config.work_directory = Some(tmp.path().join("unplugged"));
+        assert!(matches!(
+            existing_work_directory(&config),
+            Err(FileError::WorkDirectoryNotFound(_))
+        ));
+
+        config.work_directory = Some(tmp.path().to_path_buf());
+        assert_eq!(existing_work_directory(&config).unwrap(), tmp.path());
+    }
+
+    #[test]
+    fn test_group_by_week_sorts_weeks_descending_and_keeps_order() {
+        let at = |id, day| Resource {
+            created_at: Utc.with_ymd_and_hms(2026, 1, day, 12, 0, 0).unwrap(),
+            ..make_resource(id, "https://example.com/x.pdf")
+        };
+        // Jan 12 = W03, Jan 19 = W04, Dec 31 2025 = 2026-W01.
+        let mut resources = vec![at(1, 12), at(2, 19), at(3, 12), at(4, 19)];
+        resources.push(Resource {
+            created_at: Utc.with_ymd_and_hms(2025, 12, 31, 12, 0, 0).unwrap(),
+            ..make_resource(5, "https://example.com/y.pdf")
+        });
+
+        let groups = group_by_week(&resources);
+        let weeks: Vec<(i32, u32)> = groups
+            .iter()
+            .map(|g| (g.week.year, g.week.week_number))
+            .collect();
+        assert_eq!(weeks, vec![(2026, 4), (2026, 3), (2026, 1)]);
+        let ids: Vec<Vec<i64>> = groups
+            .iter()
+            .map(|g| g.resources.iter().map(|r| r.id).collect())
+            .collect();
+        assert_eq!(ids, vec![vec![2, 4], vec![1, 3], vec![5]]);
+    }
+
+    #[test]
+    fn test_available_weeks_distinct_and_descending() {
+        let at = |id, day| Resource {
+            created_at: Utc.with_ymd_and_hms(2026, 1, day, 12, 0, 0).unwrap(),
+            ..make_resource(id, "https://example.com/x.pdf")
+        };
+        let resources = vec![at(1, 12), at(2, 19), at(3, 13), at(4, 5)];
+        assert_eq!(
+            available_weeks(&resources),
+            vec![
+                WeekIdentifier::new(2026, 4),
+                WeekIdentifier::new(2026, 3),
+                WeekIdentifier::new(2026, 2)
+            ]
+        );
+        assert!(available_weeks(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_setup_state_needs_directory_and_poll_until_completed() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = AppConfig::default();
+        let polled = Some(Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap());
+
+        let fresh = setup_state(&config, None);
+        assert!(fresh.needs_work_directory && fresh.needs_first_poll);
+
+        config.work_directory = Some(tmp.path().join("missing"));
+        assert!(setup_state(&config, polled).needs_work_directory);
+
+        config.work_directory = Some(tmp.path().to_path_buf());
+        let ready = setup_state(&config, polled);
+        assert!(!ready.needs_work_directory && !ready.needs_first_poll);
+
+        // After a restart nothing has polled yet, but setup stays done.
+        config.setup_completed = true;
+        assert!(!setup_state(&config, None).needs_first_poll);
+    }
+
+    #[test]
+    fn test_create_missing_dirs_counts_only_new_ones() {
+        let tmp = TempDir::new().unwrap();
+        let existing = tmp.path().join("W03-2026-01-17");
+        std::fs::create_dir(&existing).unwrap();
+        let missing = tmp.path().join("W04-2026-01-24");
+
+        assert_eq!(create_missing_dirs([&existing, &missing]).unwrap(), 1);
+        assert!(missing.is_dir());
+        assert_eq!(create_missing_dirs([&existing, &missing]).unwrap(), 0);
+
+        let blocked = tmp.path().join("file");
+        std::fs::write(&blocked, b"x").unwrap();
+        let err = create_missing_dirs([&blocked.join("W05-2026-01-31")]).unwrap_err();
+        assert!(matches!(err, FileError::CreateDirectoryFailed { .. }));
+    }
 }
@@ -4,6 +4,41 @@
 /// polling service and by the manual "force poll" command.
 pub const API_BASE_URL: &str = "https://api.adventistyouth.it";
 
+/// User-Agent sent on every request of the shared HTTP client, so the API
+/// side can tell desktop traffic (and its version) apart in its logs.
+pub const USER_AGENT: &str = concat!("church-helper-desktop/", env!("CARGO_PKG_VERSION"));
+
+/// Seconds allowed to establish a TCP/TLS connection before a request fails.
+pub const HTTP_CONNECT_TIMEOUT_SECS: u64 = 15;
+
+/// Seconds a response may go without delivering any bytes before it is
+/// considered stalled. Per-read, not per-request: long downloads that keep
+/// streaming are never cut off.
+pub const HTTP_READ_TIMEOUT_SECS: u64 = 60;
+
+/// Default of `AppConfig::request_timeout_secs`: how long a poll or HEAD
+/// request may take in total before it fails.
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Largest `AppConfig::request_timeout_secs` accepted.
+pub const MAX_REQUEST_TIMEOUT_SECS: u64 = 600;
+
+/// Longest `Retry-After` honored, in seconds; a server asking for more is
+/// waited out this long and then tried again.
+pub const MAX_RETRY_AFTER_SECS: u64 = 3600;
+
+/// Times a download waits out a `Retry-After` before giving up on the
+/// attempt.
+pub const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Seconds `check_api_connectivity` waits for the API before reporting it
+/// unreachable; kept short since the UI polls it for a status dot.
+pub const HEALTH_CHECK_TIMEOUT_SECS: u64 = 5;
+
+/// Redirect hops followed before a request fails. Download links 302 to a
+/// signed CDN URL once; a longer chain means a loop or a misconfigured host.
+pub const HTTP_MAX_REDIRECTS: usize = 5;
+
 /// Environment variable read by [`api_base_url`] to override [`API_BASE_URL`]
 /// at runtime. Dev-only: see the README section on pointing the desktop at
 /// the local `api-stub` for how to use it.
@@ -47,6 +47,16 @@ pub enum FileError {
         #[source]
         source: trash::Error,
     },
+
+    #[error("File not found: {0}")]
+    FileNotFound(PathBuf),
+
+    #[error("Failed to read file {path}: {source}")]
+    ReadFileFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
 }
 
 /// Errors that can occur during downloads
@@ -73,6 +83,25 @@ pub enum DownloadError {
 
     #[error("Download cancelled")]
     Cancelled,
+
+    #[error("Download stopped")]
+    Stopped,
+
+    #[error("Server sent {got} instead of the expected {expected}")]
+    UnexpectedContentType { expected: String, got: String },
+
+    #[error("Failed to create directory: {path}: {source}")]
+    CreateDirectoryFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid download URL {url:?}: {reason}")]
+    InvalidUrl { url: String, reason: String },
+
+    #[error("Server is rate-limiting downloads; retry after {wait_secs}s")]
+    RateLimited { wait_secs: u64 },
 }
 
 /// Errors that can occur during polling
@@ -101,6 +130,22 @@ pub enum ConfigError {
     ValidationFailed(String),
 }
 
+/// Errors from fetching a thumbnail (see `services::thumbnails`)
+#[derive(Debug, Error)]
+pub enum ThumbnailError {
+    #[error("Thumbnail request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Thumbnail request failed with status {0}")]
+    Status(u16),
+
+    #[error("Thumbnail is not an image (content type \"{0}\")")]
+    NotAnImage(String),
+
+    #[error("Thumbnail is larger than {0} bytes")]
+    TooLarge(u64),
+}
+
 /// Unified error type for Tauri commands
 #[derive(Debug, Error)]
 pub enum AppError {
@@ -129,10 +174,14 @@ pub enum AppError {
 /// `code` is a stable kebab-case identifier (e.g. `work-dir-not-set`,
 /// `api-unreachable`, `store-failed`, `lock-poisoned`). `message` preserves the
 /// human-readable detail the commands previously produced with `format!`.
+/// `details`, when present, carries structured context (e.g. the `path` or
+/// `url` involved, an HTTP `status`) and is omitted from the JSON otherwise.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct CommandError {
     pub code: String,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
 }
 
 impl CommandError {
@@ -140,8 +189,15 @@ impl CommandError {
         Self {
             code: code.into(),
             message: message.into(),
+            details: None,
         }
     }
+
+    /// Attach structured context for the UI.
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
 }
 
 impl std::fmt::Display for CommandError {
@@ -152,9 +208,11 @@ impl std::fmt::Display for CommandError {
 
 impl std::error::Error for CommandError {}
 
-impl From<AppError> for CommandError {
-    fn from(err: AppError) -> Self {
-        let code = match &err {
+impl AppError {
+    /// Stable kebab-case identifier of this error, the `code` the frontend
+    /// branches on.
+    pub fn code(&self) -> &'static str {
+        match self {
             AppError::File(e) => match e {
                 FileError::WorkDirectoryNotSet => "work-dir-not-set",
                 FileError::WorkDirectoryNotFound(_) => "work-dir-not-found",
@@ -163,6 +221,8 @@ impl From<AppError> for CommandError {
                 FileError::DeleteFileFailed { .. } => "delete-file-failed",
                 FileError::ReadDirectoryFailed { .. } => "read-directory-failed",
                 FileError::TrashFailed { .. } => "trash-failed",
+                FileError::FileNotFound(_) => "file-not-found",
+                FileError::ReadFileFailed { .. } => "read-file-failed",
             },
             AppError::Download(e) => match e {
                 DownloadError::HttpError(_) => "http-error",
@@ -171,6 +231,11 @@ impl From<AppError> for CommandError {
                 DownloadError::InvalidFilename => "invalid-filename",
                 DownloadError::Paused => "download-paused",
                 DownloadError::Cancelled => "download-cancelled",
+                DownloadError::Stopped => "download-stopped",
+                DownloadError::UnexpectedContentType { .. } => "unexpected-content-type",
+                DownloadError::CreateDirectoryFailed { .. } => "create-directory-failed",
+                DownloadError::InvalidUrl { .. } => "invalid-url",
+                DownloadError::RateLimited { .. } => "rate-limited",
             },
             AppError::Polling(e) => match e {
                 PollingError::ApiError(_) => "api-unreachable",
@@ -182,8 +247,47 @@ impl From<AppError> for CommandError {
                 ConfigError::SaveFailed(_) => "config-save-failed",
                 ConfigError::ValidationFailed(_) => "config-invalid",
             },
+        }
+    }
+
+    /// Machine-readable context for the UI: the path or URL the error is
+    /// about, where there is one.
+    pub fn details(&self) -> Option<serde_json::Value> {
+        let path = match self {
+            AppError::File(
+                FileError::WorkDirectoryNotFound(path)
+                | FileError::FileNotFound(path)
+                | FileError::CreateDirectoryFailed { path, .. }
+                | FileError::DeleteFileFailed { path, .. }
+                | FileError::ReadDirectoryFailed { path, .. }
+                | FileError::TrashFailed { path, .. }
+                | FileError::ReadFileFailed { path, .. },
+            )
+            | AppError::Download(
+                DownloadError::WriteError { path, .. }
+                | DownloadError::CreateDirectoryFailed { path, .. },
+            ) => path,
+            AppError::File(FileError::MoveFileFailed { from, to, .. }) => {
+                return Some(serde_json::json!({ "from": from, "to": to }));
+            }
+            AppError::Download(DownloadError::InvalidUrl { url, .. }) => {
+                return Some(serde_json::json!({ "url": url }));
+            }
+            AppError::Download(DownloadError::RateLimited { wait_secs }) => {
+                return Some(serde_json::json!({ "wait_secs": wait_secs }));
+            }
+            _ => return None,
         };
-        CommandError::new(code, err.to_string())
+        Some(serde_json::json!({ "path": path }))
+    }
+}
+
+impl From<AppError> for CommandError {
+    fn from(err: AppError) -> Self {
+        let details = err.details();
+        let mut command_error = CommandError::new(err.code(), err.to_string());
+        command_error.details = details;
+        command_error
     }
 }
 
@@ -211,6 +315,17 @@ impl From<ConfigError> for CommandError {
     }
 }
 
+impl From<ThumbnailError> for CommandError {
+    fn from(err: ThumbnailError) -> Self {
+        let code = match &err {
+            ThumbnailError::Http(_) | ThumbnailError::Status(_) => "thumbnail-fetch-failed",
+            ThumbnailError::NotAnImage(_) => "thumbnail-not-image",
+            ThumbnailError::TooLarge(_) => "thumbnail-too-large",
+        };
+        CommandError::new(code, err.to_string())
+    }
+}
+
 // A poisoned lock is a non-recoverable internal invariant break; collapse every
 // `RwLock`/`Mutex` guard into one stable code rather than one per call site.
 impl<T> From<std::sync::PoisonError<T>> for CommandError {
@@ -262,6 +377,26 @@ mod tests {
         assert_eq!(err.message, "Work directory not configured");
     }
 
+    #[test]
+    fn test_app_error_details_carry_path_or_url() {
+        let err: CommandError =
+            AppError::File(FileError::WorkDirectoryNotFound(PathBuf::from("/music"))).into();
+        assert_eq!(err.code, "work-dir-not-found");
+        let value = serde_json::to_value(&err).unwrap();
+        assert_eq!(value["details"]["path"], "/music");
+
+        let err = AppError::Download(DownloadError::InvalidUrl {
+            url: "ftp://x".to_string(),
+            reason: "unsupported scheme".to_string(),
+        });
+        assert_eq!(err.code(), "invalid-url");
+        assert_eq!(err.details(), Some(serde_json::json!({ "url": "ftp://x" })));
+        assert_eq!(
+            AppError::File(FileError::WorkDirectoryNotSet).details(),
+            None
+        );
+    }
+
     #[test]
     fn test_polling_api_error_maps_to_api_unreachable() {
         let err: CommandError = PollingError::PollingDisabled.into();
@@ -280,4 +415,28 @@ mod tests {
         let err: CommandError = lock.read().unwrap_err().into();
         assert_eq!(err.code, "lock-poisoned");
     }
+
+    #[test]
+    fn test_unexpected_content_type_maps_to_stable_code() {
+        let err: CommandError = DownloadError::UnexpectedContentType {
+            expected: "mp4".to_string(),
+            got: "text/html".to_string(),
+        }
+        .into();
+        assert_eq!(err.code, "unexpected-content-type");
+        assert_eq!(
+            err.message,
+            "Server sent text/html instead of the expected mp4"
+        );
+    }
+
+    #[test]
+    fn test_thumbnail_errors_map_to_stable_codes() {
+        let err: CommandError = ThumbnailError::NotAnImage("text/html".to_string()).into();
+        assert_eq!(err.code, "thumbnail-not-image");
+        let err: CommandError = ThumbnailError::Status(404).into();
+        assert_eq!(err.code, "thumbnail-fetch-failed");
+        let err: CommandError = ThumbnailError::TooLarge(10).into();
+        assert_eq!(err.code, "thumbnail-too-large");
+    }
 }
@@ -21,11 +21,9 @@ use tauri::Manager;
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Initialize tracing for logging. Honor RUST_LOG when set (e.g.
-    // `church_helper_desktop_lib=debug`), defaulting to `info` otherwise.
-    use tracing_subscriber::EnvFilter;
-    tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()))
-        .init();
+    // `church_helper_desktop_lib=debug`), defaulting to `info` until the
+    // configured `log_level` is loaded in `setup`.
+    services::logging::init_logging();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
@@ -43,6 +41,12 @@ pub fn run() {
             Some(vec!["--autostart"]),
         ))
         .setup(|app| {
+            // First, so everything from here on also lands in the log file.
+            match app.path().app_log_dir() {
+                Ok(log_dir) => services::logging::attach_log_file(&log_dir),
+                Err(e) => tracing::error!("Failed to resolve log directory: {}", e),
+            }
+
             // Initialize application state
             let app_state = AppState::default();
 
@@ -107,6 +111,35 @@ pub fn run() {
                 .config
                 .write()
                 .map_err(|e| format!("Failed to write initial config: {}", e))? = config.clone();
+            services::logging::apply_log_level(&config.log_level);
+
+            // AppState::default built direct clients without headers and
+            // with the default timeout; rebuild them once the persisted
+            // proxy, API headers and timeout are known so the first poll
+            // already uses them.
+            if config.effective_proxy_url().is_some()
+                || !config.api_headers.is_empty()
+                || config.request_timeout_secs != constants::DEFAULT_REQUEST_TIMEOUT_SECS
+            {
+                *app_state
+                    .shared_http_client
+                    .write()
+                    .map_err(|e| format!("Failed to write HTTP client: {}", e))? =
+                    services::http::build_http_client(
+                        config.effective_proxy_url(),
+                        &config.api_headers,
+                        Some(config.request_timeout()),
+                    );
+                *app_state
+                    .download_http_client
+                    .write()
+                    .map_err(|e| format!("Failed to write HTTP client: {}", e))? =
+                    services::http::build_http_client(
+                        config.effective_proxy_url(),
+                        &config.api_headers,
+                        None,
+                    );
+            }
 
             // Sync status with config
             app_state
@@ -189,22 +222,28 @@ pub fn run() {
                 .map_err(|e| format!("Failed to write initial stats: {}", e))? = stats_total;
             tracing::info!("Loaded savings stats: {} bytes saved total", stats_total);
 
-            // Try to load cached file sizes
+            // Try to load cached file sizes, so a restart doesn't re-HEAD
+            // everything. Only successful sizes are persisted (failures are
+            // session-only, see `services::size_cache`); a malformed value
+            // just means a cold cache.
             if let Some(json) = cache_store.get("file_size_cache") {
-                if let Ok(cached_sizes) =
-                    serde_json::from_value::<std::collections::HashMap<String, u64>>(json.clone())
-                {
-                    *app_state
-                        .file_size_cache
-                        .write()
-                        .map_err(|e| format!("Failed to write cached file sizes: {}", e))? =
-                        cached_sizes;
-                    let cached_file_sizes_len = app_state
-                        .file_size_cache
-                        .read()
-                        .map_err(|e| format!("Failed to read cached file sizes: {}", e))?
-                        .len();
-                    tracing::info!("Loaded {} cached file sizes", cached_file_sizes_len);
+                match serde_json::from_value::<std::collections::HashMap<String, u64>>(
+                    json.clone(),
+                ) {
+                    Ok(cached_sizes) => {
+                        let cached_sizes =
+                            services::size_cache::FileSizeCache::from_sizes(cached_sizes);
+                        let count = cached_sizes.len();
+                        *app_state
+                            .file_size_cache
+                            .write()
+                            .map_err(|e| format!("Failed to write cached file sizes: {}", e))? =
+                            cached_sizes;
+                        tracing::info!("Loaded {} cached file sizes", count);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to parse cached file sizes, starting cold: {}", e);
+                    }
                 }
             }
 
@@ -231,6 +270,15 @@ pub fn run() {
                 }
             }
 
+            // Download history lives in its own store; like the registry, a
+            // missing or corrupt file just starts an empty history.
+            let history = services::history::load_history(app.handle());
+            tracing::info!("Loaded {} download history entries", history.len());
+            *app_state
+                .download_history
+                .write()
+                .map_err(|e| format!("Failed to write download_history: {}", e))? = history;
+
             // Reconcile has_superseded_files against the freshly loaded registry
             // so a supersession recorded in a previous session is reflected in
             // the status at startup, using the same week the status derives from
@@ -278,6 +326,35 @@ pub fn run() {
                 });
             }
 
+            // Re-queue downloads interrupted by the last exit, after the same
+            // settle delay so their progress events reach the frontend.
+            if config.work_directory.is_some() {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                    let state = app_handle.state::<AppState>();
+                    state
+                        .download_queue
+                        .resume_interrupted_downloads(app_handle.clone())
+                        .await;
+                });
+            }
+
+            // Opt-in integrity pass over the current week's downloads, after
+            // the same settle delay so corrupt files are re-queued behind
+            // the startup scan.
+            if config.verify_on_startup && config.work_directory.is_some() {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+                    let bad = services::integrity::verify_current_week(&app_handle).await;
+                    tracing::info!(
+                        "Startup verification done: {} corrupt or unreadable files",
+                        bad
+                    );
+                });
+            }
+
             // Auto-start polling if enabled. The handle is stored in
             // AppState (below) so it can be stopped cleanly when the user
             // exits from the tray menu instead of leaking an unstoppable
@@ -416,27 +493,75 @@ pub fn run() {
             commands::set_config,
             commands::get_status,
             commands::get_resources,
+            commands::get_resources_grouped,
+            commands::get_available_weeks,
+            commands::get_resources_for_week,
+            commands::query_resources,
             commands::get_all_categories,
+            commands::get_categories,
+            commands::get_category_labels,
             commands::force_poll,
+            commands::cancel_poll,
+            commands::is_resource_stale,
+            commands::check_api_connectivity,
             commands::select_work_directory,
             commands::set_work_directory,
+            commands::get_setup_state,
             commands::set_polling_enabled,
             commands::set_polling_interval,
             commands::set_retention_days,
+            commands::set_auto_download_categories,
             commands::set_autostart_enabled,
             commands::get_archived_weeks,
+            commands::enforce_retention_now,
+            commands::pin_week,
+            commands::unpin_week,
+            commands::get_week_files,
+            commands::get_week_stats,
+            commands::hash_file,
+            commands::rebuild_manifests,
+            commands::find_duplicate_files,
+            commands::get_thumbnail,
+            commands::clear_caches,
+            commands::check_work_directory,
             commands::is_resource_youtube,
             commands::download_resource,
+            commands::download_current_week,
+            commands::redownload_resource,
+            commands::refresh_resource,
+            commands::get_download_errors,
+            commands::retry_all_failed,
             commands::pause_download,
+            commands::stop_download,
+            commands::pause_queue,
+            commands::resume_queue,
+            commands::is_idle,
+            commands::get_active_progress,
+            commands::get_download_signals,
             commands::cancel_download,
+            commands::cancel_all_downloads,
             commands::check_resource_status,
             commands::check_resource_downloaded,
             commands::get_file_size,
+            commands::estimate_download_size,
+            commands::cancel_download_size_estimate,
             commands::get_resource_summary,
             commands::get_resources_status,
             commands::reveal_resource,
             commands::open_work_directory,
+            commands::get_work_directory_free_space,
+            commands::ensure_week_directories,
+            commands::open_archive_directory,
             commands::get_savings_stats,
+            commands::get_download_history,
+            commands::get_diagnostics,
+            commands::open_log_folder,
+            commands::pick_config_export_path,
+            commands::pick_config_import_path,
+            commands::export_config,
+            commands::import_config,
+            commands::export_state_bundle,
+            commands::import_state_bundle,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
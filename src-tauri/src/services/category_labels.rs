@@ -0,0 +1,84 @@
+//! Category display names
+//!
+//! The API names categories with raw Italian slugs (`"decime"`, `"video"`).
+//! Their display names live here rather than in the frontend, so a new
+//! category or language only needs a row in [`CATEGORY_LABELS`]. Categories
+//! missing from the table are shown as their raw name.
+
+use std::collections::HashMap;
+
+/// Locale used when the requested one isn't supported: the content comes
+/// from adventistyouth.it.
+pub const DEFAULT_LOCALE: &str = "it";
+
+/// `(category, Italian, English)`.
+const CATEGORY_LABELS: &[(&str, &str, &str)] = &[
+    ("video", "Video", "Videos"),
+    ("audio", "Audio", "Audio"),
+    ("decime", "Decime e offerte", "Tithes and offerings"),
+    ("bollettino", "Bollettino", "Bulletin"),
+    ("immagini", "Immagini", "Images"),
+];
+
+/// Language column of [`CATEGORY_LABELS`] for `locale` (`"en"`, `"en-US"`,
+/// `"it_IT"`…); unsupported or blank locales fall back to
+/// [`DEFAULT_LOCALE`].
+fn language_index(locale: &str) -> usize {
+    let language = locale
+        .trim()
+        .split(['-', '_'])
+        .next()
+        .unwrap_or(DEFAULT_LOCALE)
+        .to_ascii_lowercase();
+    match language.as_str() {
+        "en" => 1,
+        _ => 0,
+    }
+}
+
+/// Display name of `category` in `locale`; the raw name when the table has
+/// no entry for it.
+pub fn category_label(category: &str, locale: &str) -> String {
+    let index = language_index(locale);
+    CATEGORY_LABELS
+        .iter()
+        .find(|(raw, _, _)| raw.eq_ignore_ascii_case(category))
+        .map(|(_, italian, english)| [*italian, *english][index].to_string())
+        .unwrap_or_else(|| category.to_string())
+}
+
+/// Display names in `locale` for every category in the table plus
+/// `categories` (those currently known from the API), keyed by raw name.
+pub fn category_labels<'a>(
+    locale: &str,
+    categories: impl IntoIterator<Item = &'a str>,
+) -> HashMap<String, String> {
+    CATEGORY_LABELS
+        .iter()
+        .map(|(raw, _, _)| *raw)
+        .chain(categories)
+        .map(|category| (category.to_string(), category_label(category, locale)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_category_label_follows_locale_and_defaults_to_italian() {
+        assert_eq!(category_label("decime", "en-US"), "Tithes and offerings");
+        assert_eq!(category_label("decime", "it"), "Decime e offerte");
+        assert_eq!(category_label("decime", "fr"), "Decime e offerte");
+        assert_eq!(category_label("decime", ""), "Decime e offerte");
+        assert_eq!(category_label("Video", "EN"), "Videos");
+    }
+
+    #[test]
+    fn test_unknown_category_falls_back_to_raw_name() {
+        assert_eq!(category_label("campeggio", "en"), "campeggio");
+        let labels = category_labels("en", ["campeggio"]);
+        assert_eq!(labels["campeggio"], "campeggio");
+        assert_eq!(labels["video"], "Videos");
+    }
+}
@@ -0,0 +1,159 @@
+//! Diagnostics
+//!
+//! Answers "where does the app keep its data?" for support requests: the
+//! resolved location of every store, the work directory and the log
+//! directory, each with whether it exists and is writable. Read-only apart
+//! from a short-lived probe file used to test writability.
+
+use crate::services::history::HISTORY_STORE;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+/// Probe file created and removed again to test a directory's writability.
+const WRITE_PROBE_NAME: &str = ".church-helper-write-probe";
+
+/// One location reported by [`collect_diagnostics`]. `path` is `None` when
+/// it couldn't be resolved (or, for the work directory, isn't configured).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathDiagnostic {
+    pub path: Option<PathBuf>,
+    pub exists: bool,
+    pub writable: bool,
+}
+
+impl PathDiagnostic {
+    fn unresolved() -> Self {
+        Self {
+            path: None,
+            exists: false,
+            writable: false,
+        }
+    }
+}
+
+/// Result of the `get_diagnostics` command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostics {
+    pub app_version: String,
+    pub settings_store: PathDiagnostic,
+    pub cache_store: PathDiagnostic,
+    pub history_store: PathDiagnostic,
+    pub work_directory: PathDiagnostic,
+    pub log_directory: PathDiagnostic,
+    /// File currently written by the file logger; unresolved when file
+    /// logging couldn't be set up.
+    pub log_file: PathDiagnostic,
+}
+
+/// Whether a file could be created in `dir` right now.
+fn dir_is_writable(dir: &Path) -> bool {
+    let probe = dir.join(WRITE_PROBE_NAME);
+    match std::fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+/// Diagnose a directory.
+pub fn probe_dir(dir: &Path) -> PathDiagnostic {
+    let exists = dir.is_dir();
+    PathDiagnostic {
+        path: Some(dir.to_path_buf()),
+        exists,
+        writable: exists && dir_is_writable(dir),
+    }
+}
+
+/// Diagnose a file. A file that doesn't exist yet (a store before its first
+/// save) counts as writable when its parent directory is.
+pub fn probe_file(file: &Path) -> PathDiagnostic {
+    let exists = file.is_file();
+    let writable = if exists {
+        std::fs::OpenOptions::new().append(true).open(file).is_ok()
+    } else {
+        file.parent()
+            .is_some_and(|parent| parent.is_dir() && dir_is_writable(parent))
+    };
+    PathDiagnostic {
+        path: Some(file.to_path_buf()),
+        exists,
+        writable,
+    }
+}
+
+fn probe_store(app: &AppHandle, name: &str) -> PathDiagnostic {
+    match tauri_plugin_store::resolve_store_path(app, name) {
+        Ok(path) => probe_file(&path),
+        Err(e) => {
+            tracing::warn!("Diagnostics: failed to resolve store {}: {}", name, e);
+            PathDiagnostic::unresolved()
+        }
+    }
+}
+
+/// Gather the diagnostics for `work_dir` (the configured work directory).
+pub fn collect_diagnostics(app: &AppHandle, work_dir: Option<&Path>) -> Diagnostics {
+    let log_directory = match app.path().app_log_dir() {
+        Ok(dir) => probe_dir(&dir),
+        Err(e) => {
+            tracing::warn!("Diagnostics: failed to resolve log directory: {}", e);
+            PathDiagnostic::unresolved()
+        }
+    };
+
+    Diagnostics {
+        app_version: app.package_info().version.to_string(),
+        settings_store: probe_store(app, "settings.json"),
+        cache_store: probe_store(app, "cache.json"),
+        history_store: probe_store(app, HISTORY_STORE),
+        work_directory: work_dir
+            .map(probe_dir)
+            .unwrap_or_else(PathDiagnostic::unresolved),
+        log_directory,
+        log_file: crate::services::logging::current_log_file()
+            .map(|file| probe_file(&file))
+            .unwrap_or_else(PathDiagnostic::unresolved),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_probe_dir_existing_is_writable_and_leaves_no_probe() {
+        let tmp = TempDir::new().unwrap();
+        let diag = probe_dir(tmp.path());
+        assert!(diag.exists);
+        assert!(diag.writable);
+        assert!(!tmp.path().join(WRITE_PROBE_NAME).exists());
+    }
+
+    #[test]
+    fn test_probe_dir_missing() {
+        let tmp = TempDir::new().unwrap();
+        let diag = probe_dir(&tmp.path().join("gone"));
+        assert!(!diag.exists);
+        assert!(!diag.writable);
+    }
+
+    #[test]
+    fn test_probe_file_not_yet_created_uses_parent() {
+        let tmp = TempDir::new().unwrap();
+        let store = tmp.path().join("settings.json");
+
+        let diag = probe_file(&store);
+        assert!(!diag.exists);
+        assert!(diag.writable);
+
+        std::fs::write(&store, b"{}").unwrap();
+        let diag = probe_file(&store);
+        assert!(diag.exists);
+        assert!(diag.writable);
+    }
+}
@@ -2,8 +2,13 @@
 //!
 //! Handles downloading resources, creating URL shortcuts, and calculating integrity hashes.
 
-use crate::error::DownloadError;
-use crate::models::Resource;
+use crate::error::{DownloadError, FileError};
+use crate::models::{AppConfig, FolderLayout, Resource};
+use crate::services::manifest::{
+    claim_dest_path, clear_download_record, record_download_done, record_download_started,
+    record_file_hash, record_transfer, unique_dest_path, TransferRecord,
+};
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -13,33 +18,175 @@ use std::time::{Duration, Instant};
 use tauri::AppHandle;
 use urlencoding;
 
-// Download status constants
+// Download status constants, stored in a download's signal
+// (`AppState::download_signals`). The three ways to halt a download differ
+// only in what they leave behind and how the UI treats it:
+//
+// - PAUSED: stops the transfer and keeps the `.part`; the item stays in the
+//   UI's active downloads as "paused", and resuming re-queues it from there.
+// - STOPPED: stops the transfer and keeps the `.part`, but the item leaves
+//   the active downloads; a later manual download resumes from the `.part`.
+// - CANCELLED: stops the transfer and deletes the `.part` (and its sidecar).
+// - CANCELLED_KEEP_PARTIAL: a cancel (`download-cancelled`) that keeps the
+//   `.part` like STOPPED, for `cancel_download(keep_partial: true)`.
 pub const STATUS_RUNNING: u8 = 0;
 pub const STATUS_PAUSED: u8 = 1;
 pub const STATUS_CANCELLED: u8 = 2;
+pub const STATUS_STOPPED: u8 = 3;
+pub const STATUS_CANCELLED_KEEP_PARTIAL: u8 = 4;
+
+/// Name of a signal value, as reported by `get_download_signals`.
+pub fn status_name(status: u8) -> &'static str {
+    match status {
+        STATUS_RUNNING => "running",
+        STATUS_PAUSED => "paused",
+        STATUS_CANCELLED => "cancelled",
+        STATUS_STOPPED => "stopped",
+        STATUS_CANCELLED_KEEP_PARTIAL => "cancelled-keep-partial",
+        _ => "unknown",
+    }
+}
+
+/// How often the signal is checked while waiting out a `Retry-After`.
+const RETRY_AFTER_SIGNAL_POLL: Duration = Duration::from_millis(250);
+
+/// Minimum time between two `download-progress` events for one download.
+const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Decides when the streaming loop may emit `download-progress`: only once
+/// [`PROGRESS_EMIT_INTERVAL`] has passed AND the percentage moved by at
+/// least one point, so a fast download doesn't flood the webview with IPC
+/// messages. The final 100% is emitted separately, unthrottled.
+#[derive(Debug)]
+struct ProgressThrottle {
+    last_emit: Instant,
+    last_percent: Option<u8>,
+}
+
+impl ProgressThrottle {
+    fn new(now: Instant) -> Self {
+        Self {
+            last_emit: now,
+            last_percent: None,
+        }
+    }
+
+    /// `Some(percent)` when an event is due at `now`; records it as emitted.
+    fn poll(&mut self, now: Instant, downloaded: u64, total: u64) -> Option<u8> {
+        if now.duration_since(self.last_emit) < PROGRESS_EMIT_INTERVAL {
+            return None;
+        }
+        let percent = progress_percent(downloaded, total);
+        if self.last_percent == Some(percent) {
+            return None;
+        }
+        self.last_emit = now;
+        self.last_percent = Some(percent);
+        Some(percent)
+    }
+}
+
+fn progress_percent(downloaded: u64, total: u64) -> u8 {
+    if total == 0 {
+        return 0;
+    }
+    ((downloaded as f64 / total as f64) * 100.0).min(100.0) as u8
+}
+
+/// Average rate of `received` bytes over `elapsed`; 0 before any time has
+/// passed.
+fn bytes_per_second(received: u64, elapsed: Duration) -> u64 {
+    let secs = elapsed.as_secs_f64();
+    if secs <= 0.0 {
+        return 0;
+    }
+    (received as f64 / secs) as u64
+}
+
+/// Last progress reported by an active download, kept in
+/// `AppState::active_progress` so a download panel opened mid-download
+/// renders its bar right away (see `commands::get_active_progress`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ActiveProgress {
+    pub progress: u8,
+    pub bytes: u64,
+    pub total: u64,
+    /// Average transfer rate of this attempt (resumed bytes excluded).
+    pub bps: u64,
+}
 
 /// Service for downloading resources
 pub struct DownloadService {
     client: reqwest::Client,
+    /// Write `.part` files into the destination's [`STAGING_DIR_NAME`]
+    /// folder instead of next to the finished file.
+    stage_incomplete: bool,
 }
 
 impl DownloadService {
-    /// Create a new DownloadService with default client
+    /// Create a new DownloadService with its own client, configured like the
+    /// app's shared one. Prefer `with_client` where `AppState` is reachable.
     pub fn new() -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: crate::services::http::build_http_client(
+                None,
+                &std::collections::HashMap::new(),
+                None,
+            ),
+            stage_incomplete: false,
         }
     }
 
     /// Create a new DownloadService with custom client
     pub fn with_client(client: reqwest::Client) -> Self {
-        Self { client }
+        Self {
+            client,
+            stage_incomplete: false,
+        }
+    }
+
+    /// Stage `.part` files in a hidden [`STAGING_DIR_NAME`] subfolder of the
+    /// destination (`AppConfig::stage_incomplete_downloads`), so only
+    /// finished files ever show up in the visible folder.
+    pub fn with_staging(mut self, stage_incomplete: bool) -> Self {
+        self.stage_incomplete = stage_incomplete;
+        self
     }
 
-    /// Check if a resource file already exists
-    /// Uses the effective download URL based on prefer_optimized setting
-    pub fn check_file_exists(resource: &Resource, work_dir: &Path, prefer_optimized: bool) -> bool {
-        resolve_dest_path(resource, work_dir, prefer_optimized).exists()
+    /// Check if a resource file already exists at its
+    /// [`resource_dest_path`]; `false` while no work directory is set.
+    pub fn check_file_exists(resource: &Resource, config: &AppConfig) -> bool {
+        resource_dest_path(config, resource).is_some_and(|path| path.exists())
+    }
+
+    /// Whether an unfinished `.part` sits next to the resource's destination:
+    /// a download running right now, or one paused/interrupted earlier.
+    pub fn check_partial_exists(resource: &Resource, config: &AppConfig) -> bool {
+        resource_dest_path(config, resource).is_some_and(|path| existing_part_path(&path).is_some())
+    }
+
+    /// Ask the server whether the resource changed after `since` (a
+    /// conditional GET with `If-Modified-Since`, no `Range`). Only the
+    /// status is read: on `Modified` the response is dropped unread and the
+    /// caller queues the real download.
+    pub async fn check_if_modified(
+        &self,
+        resource: &Resource,
+        prefer_optimized: bool,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Outcome, DownloadError> {
+        let url = resource.get_effective_download_url(prefer_optimized);
+        let response = self
+            .client
+            .get(url)
+            .header(reqwest::header::IF_MODIFIED_SINCE, http_date(since))
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            return Ok(Outcome::NotModified);
+        }
+        response.error_for_status()?;
+        Ok(Outcome::Modified)
     }
 
     /// Download a resource to the destination directory
@@ -55,6 +202,17 @@ impl DownloadService {
         signal: Option<Arc<AtomicU8>>,
         prefer_optimized: bool,
     ) -> Result<(PathBuf, String), DownloadError> {
+        let resource =
+            &normalize_resource_urls(resource.clone(), &crate::constants::api_base_url())?;
+        // Created here, right before writing, so every download path (queue
+        // worker, manual, re-download) fails the same typed way on a
+        // read-only or full destination.
+        tokio::fs::create_dir_all(dest_dir)
+            .await
+            .map_err(|source| DownloadError::CreateDirectoryFailed {
+                path: dest_dir.to_path_buf(),
+                source,
+            })?;
         if resource.is_youtube() {
             let path = self.create_youtube_shortcut(resource, dest_dir)?;
             Ok((path, "youtube-shortcut".to_string()))
@@ -77,15 +235,7 @@ impl DownloadService {
         use tauri::Emitter;
         use tokio::io::AsyncWriteExt;
 
-        // Determine which URL to use
-        let download_url = if prefer_optimized {
-            resource
-                .optimized_video_url
-                .as_ref()
-                .unwrap_or(&resource.download_url)
-        } else {
-            &resource.download_url
-        };
+        let download_url = resource.get_effective_download_url(prefer_optimized);
 
         tracing::debug!(
             "Starting download_file for resource: {} ({})",
@@ -93,34 +243,88 @@ impl DownloadService {
             download_url
         );
 
-        // Extract filename
-        let filename = extract_filename_from_url(download_url)
-            .unwrap_or_else(|| sanitize_filename(&resource.title));
-
-        let dest_path = dest_dir.join(&filename);
-        let part_path = dest_dir.join(format!("{}.part", filename));
+        // Where an earlier attempt would have left its partial: the name
+        // recorded for this URL in the week manifest, else the URL's own
+        // filename (stepping to ` (2)` when another resource owns it). The
+        // same resolution as `resolve_dest_path`, so the status checks look
+        // where the download writes.
+        let url_filename = dest_filename(resource, prefer_optimized);
+        let resume_path = dest_path_in(dest_dir, resource, prefer_optimized);
+        // A partial is resumed wherever it was left, whatever the staging
+        // setting is now.
+        let resume_staged =
+            !part_path_for(&resume_path).exists() && staged_part_path_for(&resume_path).exists();
+        let (resume_part_path, resume_meta_path) = part_paths(&resume_path, resume_staged);
 
         // Defensive path-traversal guard: the resolved filename must stay directly
         // inside dest_dir. If join() escaped the base (absolute path or `..`), reject.
-        if dest_path.parent() != Some(dest_dir) || part_path.parent() != Some(dest_dir) {
+        if resume_path.parent() != Some(dest_dir) || !in_part_dir(&resume_part_path, dest_dir) {
             return Err(DownloadError::InvalidFilename);
         }
 
-        tracing::debug!("Destination path: {:?}", dest_path);
-
         // Check for existing partial download
         let mut resume_offset = 0;
-        if let Ok(metadata) = tokio::fs::metadata(&part_path).await {
+        if let Ok(metadata) = tokio::fs::metadata(&resume_part_path).await {
             resume_offset = metadata.len();
         }
 
-        // Build request
-        let mut request = self.client.get(download_url);
+        // Only resume a partial whose sidecar proves it came from this same
+        // URL; its validator then rides along as `If-Range`, so a remote file
+        // replaced since (errata corrige) answers 200 and we restart instead
+        // of stitching old and new bytes together.
+        let mut if_range: Option<String> = None;
         if resume_offset > 0 {
-            request = request.header("Range", format!("bytes={}-", resume_offset));
+            match read_part_meta(&resume_meta_path) {
+                Some(meta) if meta.url == download_url => if_range = meta.validator,
+                _ => {
+                    tracing::info!(
+                        "Discarding unverifiable partial download for {}",
+                        resource.title
+                    );
+                    resume_offset = 0;
+                }
+            }
+        }
+
+        let partial = (
+            resume_path.as_path(),
+            resume_part_path.as_path(),
+            resume_meta_path.as_path(),
+        );
+        let mut response = self
+            .send_rate_limited(
+                resource,
+                download_url,
+                (resume_offset > 0).then_some((resume_offset, if_range.as_deref())),
+                app,
+                signal.as_deref(),
+                partial,
+            )
+            .await?;
+
+        // The partial is longer than the server's file (e.g. it shrank after
+        // an errata corrige): nothing to resume, so drop it and start over
+        // rather than leaving a `.part` that fails the same way every time.
+        if resume_offset > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+            tracing::info!(
+                "Partial download of {} is past the end of the remote file; restarting",
+                resource.title
+            );
+            let _ = tokio::fs::remove_file(&resume_part_path).await;
+            let _ = tokio::fs::remove_file(&resume_meta_path).await;
+            resume_offset = 0;
+            response = self
+                .send_rate_limited(
+                    resource,
+                    download_url,
+                    None,
+                    app,
+                    signal.as_deref(),
+                    partial,
+                )
+                .await?;
         }
 
-        let response = request.send().await?;
         let status = response.status();
         tracing::debug!(
             "Download response status: {} for {}",
@@ -133,13 +337,86 @@ impl DownloadService {
         if !is_partial && resume_offset > 0 {
             // Server ignored range, restart download
             resume_offset = 0;
-            // Truncate file if it existed
-            if let Ok(file) = tokio::fs::File::create(&part_path).await {
-                let _ = file.set_len(0).await;
-            }
         }
 
+        // reqwest follows redirects on its own; the URL it ended on (often a
+        // signed CDN link) is the one that carries the real filename.
+        let final_url = response.url().clone();
+        if final_url.as_str() != download_url {
+            tracing::info!("Download of {} redirected to {}", resource.title, final_url);
+        }
+
+        // A resumed partial keeps its name. A fresh download is named after
+        // the final URL, and the choice is recorded in the week manifest
+        // against `download_url` so later lookups find it again.
+        let filename_hint = if resume_offset > 0 {
+            resume_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(str::to_string)
+                .ok_or(DownloadError::InvalidFilename)?
+        } else {
+            extract_filename_from_url(final_url.as_str()).unwrap_or(url_filename)
+        };
+
+        // An expired signed URL answers 200 with a "link expired" page; saved
+        // under the video's name it would pass for a finished download.
+        let content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok());
+        if let Some(expected) =
+            unexpected_html(content_type, resource.file_type.as_deref(), &filename_hint)
+        {
+            tracing::warn!(
+                "Download of {} returned an HTML page instead of {}",
+                resource.title,
+                expected
+            );
+            return Err(DownloadError::UnexpectedContentType {
+                expected,
+                got: content_type.unwrap_or_default().to_string(),
+            });
+        }
+
+        let dest_path = claim_dest_path(dest_dir, &filename_hint, resource.id, download_url);
+        let staged = if resume_offset > 0 {
+            resume_staged
+        } else {
+            self.stage_incomplete && prepare_staging_dir(dest_dir)
+        };
+        let (part_path, meta_path) = part_paths(&dest_path, staged);
+        if dest_path.parent() != Some(dest_dir) || !in_part_dir(&part_path, dest_dir) {
+            return Err(DownloadError::InvalidFilename);
+        }
+        if part_path != resume_part_path {
+            // A stale partial under the old name would never be picked up again.
+            let _ = tokio::fs::remove_file(&resume_part_path).await;
+            let _ = tokio::fs::remove_file(&resume_meta_path).await;
+        }
+
+        tracing::debug!("Destination path: {:?}", dest_path);
+
+        // Fresh start (first attempt, or the server refused the resume):
+        // record what this `.part` is being filled from.
+        let validator = if resume_offset == 0 {
+            let validator = response_validator(response.headers());
+            write_part_meta(
+                &meta_path,
+                &PartMeta {
+                    url: download_url.to_string(),
+                    validator: validator.clone(),
+                },
+            );
+            validator
+        } else {
+            if_range
+        };
+
         let content_length = response.content_length().map(|len| len + resume_offset);
+        let server_mtime = last_modified_time(response.headers());
+        // Noted in the manifest so a restart finds and resumes it.
+        record_download_started(&dest_path, content_length, validator);
 
         // Open file
         let mut file = tokio::fs::OpenOptions::new()
@@ -156,8 +433,8 @@ impl DownloadService {
 
         let mut stream = response.bytes_stream();
         let mut downloaded = resume_offset;
-        let mut last_progress_emit = Instant::now();
-        const PROGRESS_EMIT_INTERVAL: Duration = Duration::from_millis(100);
+        let transfer_started = Instant::now();
+        let mut throttle = ProgressThrottle::new(Instant::now());
 
         tracing::debug!(
             "Starting download stream for {} (total size: {:?})",
@@ -169,19 +446,28 @@ impl DownloadService {
             // Check cancellation signal
             if let Some(sig) = &signal {
                 let status = sig.load(Ordering::Relaxed);
-                if status == STATUS_PAUSED {
+                if status == STATUS_PAUSED
+                    || status == STATUS_STOPPED
+                    || status == STATUS_CANCELLED_KEEP_PARTIAL
+                {
                     // Flush and close before returning so the on-disk length
                     // matches what resume reads back from `metadata.len()`;
                     // otherwise still-buffered bytes would be re-fetched and
                     // duplicated past the resume offset. Keep the .part file.
                     let _ = file.flush().await;
                     drop(file);
-                    return Err(DownloadError::Paused);
+                    return Err(match status {
+                        STATUS_PAUSED => DownloadError::Paused,
+                        STATUS_STOPPED => DownloadError::Stopped,
+                        _ => DownloadError::Cancelled,
+                    });
                 } else if status == STATUS_CANCELLED {
                     // Close the handle before deleting so no in-flight write
                     // races the remove and leaves a zombie .part behind.
                     drop(file);
                     let _ = tokio::fs::remove_file(&part_path).await;
+                    let _ = tokio::fs::remove_file(&meta_path).await;
+                    clear_download_record(&dest_path);
                     return Err(DownloadError::Cancelled);
                 }
             }
@@ -196,22 +482,22 @@ impl DownloadService {
 
             downloaded += chunk.len() as u64;
 
-            // Throttle progress events to max 10/second (100ms interval)
             if let Some(app) = app {
                 if let Some(total) = content_length {
-                    let now = Instant::now();
-                    if now.duration_since(last_progress_emit) >= PROGRESS_EMIT_INTERVAL {
-                        let progress = ((downloaded as f64 / total as f64) * 100.0) as u8;
-                        let _ = app.emit(
-                            "download-progress",
-                            serde_json::json!({
-                                "id": resource.id,
-                                "progress": progress,
-                                "current_bytes": downloaded,
-                                "total_bytes": total
-                            }),
+                    if let Some(progress) = throttle.poll(Instant::now(), downloaded, total) {
+                        report_progress(
+                            app,
+                            resource.id,
+                            ActiveProgress {
+                                progress,
+                                bytes: downloaded,
+                                total,
+                                bps: bytes_per_second(
+                                    downloaded - resume_offset,
+                                    transfer_started.elapsed(),
+                                ),
+                            },
                         );
-                        last_progress_emit = now;
                     }
                 }
             }
@@ -225,14 +511,18 @@ impl DownloadService {
         // Emit final progress event to ensure 100% is shown
         if let Some(app) = app {
             if let Some(total) = content_length {
-                let _ = app.emit(
-                    "download-progress",
-                    serde_json::json!({
-                        "id": resource.id,
-                        "progress": 100,
-                        "current_bytes": downloaded,
-                        "total_bytes": total
-                    }),
+                report_progress(
+                    app,
+                    resource.id,
+                    ActiveProgress {
+                        progress: 100,
+                        bytes: downloaded,
+                        total,
+                        bps: bytes_per_second(
+                            downloaded - resume_offset,
+                            transfer_started.elapsed(),
+                        ),
+                    },
                 );
             }
         }
@@ -245,32 +535,159 @@ impl DownloadService {
             source: e,
         })?;
         drop(file);
+        let transfer = TransferRecord {
+            bytes: downloaded.saturating_sub(resume_offset),
+            duration_secs: transfer_started.elapsed().as_secs_f64(),
+        };
+
+        // Hash the completed file before it is moved into place, off the
+        // async runtime: the chunked read is blocking I/O, so run it on a
+        // blocking thread. Hashing a multi-GB video takes seconds, so it
+        // reports `hashing-progress` and a pause, stop or cancel stops it
+        // between chunks.
+        let hash_path = part_path.clone();
+        let hash_signal = signal.clone();
+        let hash_app = app.cloned();
+        let resource_id = resource.id;
+        let hash = tokio::task::spawn_blocking(move || {
+            let mut throttle = ProgressThrottle::new(Instant::now());
+            calculate_file_hash_cancellable(&hash_path, hash_signal.as_deref(), |hashed, total| {
+                if let Some(app) = &hash_app {
+                    if let Some(progress) = throttle.poll(Instant::now(), hashed, total) {
+                        let _ = app.emit(
+                            "hashing-progress",
+                            serde_json::json!({
+                                "id": resource_id,
+                                "progress": progress,
+                                "hashed_bytes": hashed,
+                                "total_bytes": total
+                            }),
+                        );
+                    }
+                }
+            })
+        })
+        .await
+        .map_err(|e| DownloadError::WriteError {
+            path: part_path.clone(),
+            source: std::io::Error::other(e),
+        })?
+        .map_err(|e| DownloadError::WriteError {
+            path: part_path.clone(),
+            source: e,
+        })?;
+        let Some(hash) = hash else {
+            // The whole file is in the `.part`; only a plain cancel drops it,
+            // everything else resumes (and just re-hashes) later.
+            let status = signal
+                .as_deref()
+                .map_or(STATUS_CANCELLED, |sig| sig.load(Ordering::Relaxed));
+            return Err(match status {
+                STATUS_PAUSED => DownloadError::Paused,
+                STATUS_STOPPED => DownloadError::Stopped,
+                STATUS_CANCELLED_KEEP_PARTIAL => DownloadError::Cancelled,
+                _ => {
+                    let _ = tokio::fs::remove_file(&part_path).await;
+                    let _ = tokio::fs::remove_file(&meta_path).await;
+                    clear_download_record(&dest_path);
+                    DownloadError::Cancelled
+                }
+            });
+        };
 
-        // Rename .part file upon success
+        // Rename .part file upon success (same volume, so atomic)
         tokio::fs::rename(&part_path, &dest_path)
             .await
             .map_err(|e| DownloadError::WriteError {
                 path: dest_path.clone(),
                 source: e,
             })?;
+        let _ = tokio::fs::remove_file(&meta_path).await;
+        if staged {
+            // Only succeeds once no other download is staged here.
+            let _ = tokio::fs::remove_dir(dest_dir.join(STAGING_DIR_NAME)).await;
+        }
 
-        // Calculate hash of the completed file off the async runtime: the
-        // chunked read is blocking I/O, so run it on a blocking thread.
-        let hash_path = dest_path.clone();
-        let hash = tokio::task::spawn_blocking(move || calculate_file_hash(&hash_path))
-            .await
-            .map_err(|e| DownloadError::WriteError {
-                path: dest_path.clone(),
-                source: std::io::Error::other(e),
-            })?
-            .map_err(|e| DownloadError::WriteError {
-                path: dest_path.clone(),
-                source: e,
-            })?;
+        // Stamp the publication time rather than the download time, so the
+        // archive sorts by when content was released.
+        if let Some(mtime) = server_mtime {
+            set_file_mtime(&dest_path, mtime);
+        }
+
+        record_file_hash(&dest_path, &hash);
+        record_download_done(&dest_path);
+        record_transfer(&dest_path, transfer);
 
         Ok((dest_path, hash))
     }
 
+    /// GET `download_url`; `range` resumes from an offset, with the
+    /// partial's `If-Range` validator if it has one. A throttled answer (429/503 with `Retry-After`) is retried after the
+    /// wait the server asked for, up to `MAX_RATE_LIMIT_RETRIES` times; a
+    /// pause, stop or cancel during the wait applies at once, a cancel also
+    /// dropping the `partial` (destination, `.part`, sidecar).
+    async fn send_rate_limited(
+        &self,
+        resource: &Resource,
+        download_url: &str,
+        range: Option<(u64, Option<&str>)>,
+        app: Option<&AppHandle>,
+        signal: Option<&AtomicU8>,
+        partial: (&Path, &Path, &Path),
+    ) -> Result<reqwest::Response, DownloadError> {
+        let (resume_path, resume_part_path, resume_meta_path) = partial;
+        let mut rate_limit_retries = 0;
+        loop {
+            let mut request = self.client.get(download_url);
+            if let Some((offset, if_range)) = range {
+                request = request.header("Range", format!("bytes={}-", offset));
+                if let Some(validator) = if_range {
+                    request = request.header("If-Range", validator);
+                }
+            }
+
+            let response = request.send().await?;
+            let Some(wait) = crate::services::http::retry_after(
+                response.status(),
+                response.headers(),
+                chrono::Utc::now(),
+            ) else {
+                return Ok(response);
+            };
+            if rate_limit_retries == crate::constants::MAX_RATE_LIMIT_RETRIES {
+                return Err(DownloadError::RateLimited {
+                    wait_secs: wait.as_secs(),
+                });
+            }
+            rate_limit_retries += 1;
+            drop(response);
+
+            if let Some(app) = app {
+                crate::services::http::emit_rate_limited(
+                    app,
+                    crate::services::http::RateLimited {
+                        source: crate::services::http::RateLimitedSource::Download,
+                        url: download_url.to_string(),
+                        resource_id: Some(resource.id),
+                        wait_secs: wait.as_secs(),
+                    },
+                );
+            }
+            match wait_unless_interrupted(wait, signal).await {
+                None => {}
+                Some(STATUS_CANCELLED) => {
+                    let _ = tokio::fs::remove_file(resume_part_path).await;
+                    let _ = tokio::fs::remove_file(resume_meta_path).await;
+                    clear_download_record(resume_path);
+                    return Err(DownloadError::Cancelled);
+                }
+                Some(STATUS_PAUSED) => return Err(DownloadError::Paused),
+                Some(STATUS_STOPPED) => return Err(DownloadError::Stopped),
+                Some(_) => return Err(DownloadError::Cancelled),
+            }
+        }
+    }
+
     /// Create a platform-specific URL shortcut for YouTube links
     fn create_youtube_shortcut(
         &self,
@@ -300,21 +717,138 @@ impl DownloadService {
     }
 }
 
+/// Sidecar (`<file>.part.meta`) describing where a `.part` file's bytes came
+/// from, written when the partial is (re)started and removed on completion
+/// or cancellation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PartMeta {
+    /// Effective download URL the partial was started from.
+    url: String,
+    /// `ETag` (strong only) or `Last-Modified` of that response, sent back as
+    /// `If-Range` on resume. `None` when the server offered neither: the
+    /// resume then goes out unconditional, as before sidecars existed.
+    validator: Option<String>,
+}
+
+/// Sleep `wait` unless `signal` leaves `STATUS_RUNNING` first, returning that status.
+async fn wait_unless_interrupted(wait: Duration, signal: Option<&AtomicU8>) -> Option<u8> {
+    let deadline = tokio::time::Instant::now() + wait;
+    loop {
+        let status = signal.map_or(STATUS_RUNNING, |sig| sig.load(Ordering::Relaxed));
+        if status != STATUS_RUNNING {
+            return Some(status);
+        }
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        tokio::time::sleep((deadline - now).min(RETRY_AFTER_SIGNAL_POLL)).await;
+    }
+}
+
+/// Validator usable in `If-Range`: a strong `ETag`, else `Last-Modified`.
+/// Weak ETags (`W/"..."`) are skipped, RFC 9110 forbids them in `If-Range`.
+fn response_validator(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    let header =
+        move |name: reqwest::header::HeaderName| headers.get(name).and_then(|v| v.to_str().ok());
+    header(reqwest::header::ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(reqwest::header::LAST_MODIFIED))
+        .map(str::to_string)
+}
+
+/// `Last-Modified` as a timestamp, when present and a valid HTTP-date
+/// (e.g. `Wed, 21 Oct 2015 07:28:00 GMT`).
+pub(crate) fn last_modified_time(
+    headers: &reqwest::header::HeaderMap,
+) -> Option<std::time::SystemTime> {
+    let value = headers.get(reqwest::header::LAST_MODIFIED)?.to_str().ok()?;
+    chrono::DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(std::time::SystemTime::from)
+}
+
+/// Best-effort: a file keeping its download time is only cosmetic.
+fn set_file_mtime(path: &Path, mtime: std::time::SystemTime) {
+    let result = std::fs::OpenOptions::new()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(mtime));
+    if let Err(e) = result {
+        tracing::warn!("Failed to set modification time of {:?}: {}", path, e);
+    }
+}
+
+fn read_part_meta(path: &Path) -> Option<PartMeta> {
+    let bytes = std::fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Best-effort: without the sidecar the next resume just restarts from zero.
+fn write_part_meta(path: &Path, meta: &PartMeta) {
+    let result = serde_json::to_vec(meta)
+        .map_err(std::io::Error::other)
+        .and_then(|json| std::fs::write(path, json));
+    if let Err(e) = result {
+        tracing::warn!("Failed to write partial download sidecar {:?}: {}", path, e);
+    }
+}
+
 /// Calculate SHA-256 hash of a file
-fn calculate_file_hash(path: &Path) -> std::io::Result<String> {
+pub(crate) fn calculate_file_hash(path: &Path) -> std::io::Result<String> {
+    match calculate_file_hash_cancellable(path, None, |_, _| {})? {
+        Some(hash) => Ok(hash),
+        // Unreachable: only a signal can interrupt the hash.
+        None => Err(std::io::ErrorKind::Interrupted.into()),
+    }
+}
+
+/// Bytes read per step of [`calculate_file_hash_cancellable`].
+const HASH_CHUNK_SIZE: usize = 64 * 1024;
+
+/// [`calculate_file_hash`] that calls `on_progress(hashed, total)` after
+/// each chunk and gives up with `Ok(None)` as soon as `signal` leaves
+/// [`STATUS_RUNNING`] (pause, stop or either cancel); the caller reads the
+/// signal to tell which.
+pub(crate) fn calculate_file_hash_cancellable(
+    path: &Path,
+    signal: Option<&AtomicU8>,
+    mut on_progress: impl FnMut(u64, u64),
+) -> std::io::Result<Option<String>> {
     use std::io::Read;
     let mut file = std::fs::File::open(path)?;
+    let total = file.metadata()?.len();
     let mut hasher = Sha256::new();
-    let mut buffer = [0u8; 8192];
+    let mut buffer = vec![0u8; HASH_CHUNK_SIZE];
+    let mut hashed = 0u64;
     loop {
+        if signal.is_some_and(|sig| sig.load(Ordering::Relaxed) != STATUS_RUNNING) {
+            return Ok(None);
+        }
         let read = file.read(&mut buffer)?;
         if read == 0 {
             break;
         }
         hasher.update(&buffer[..read]);
+        hashed += read as u64;
+        on_progress(hashed, total);
     }
-    let hash = hasher.finalize();
-    Ok(hex::encode(hash))
+    Ok(Some(hex::encode(hasher.finalize())))
+}
+
+/// SHA-256 of an arbitrary local file, in the same hex form recorded for
+/// downloads, so a file copied in by hand can be compared against them.
+pub fn hash_file(path: &Path) -> Result<String, FileError> {
+    calculate_file_hash(path).map_err(|source| {
+        if source.kind() == std::io::ErrorKind::NotFound {
+            FileError::FileNotFound(path.to_path_buf())
+        } else {
+            FileError::ReadFileFailed {
+                path: path.to_path_buf(),
+                source,
+            }
+        }
+    })
 }
 
 impl Default for DownloadService {
@@ -389,34 +923,252 @@ fn is_windows_reserved_stem(file_name: &str) -> bool {
     false
 }
 
-/// Resolve `<work_dir>/<week-dir>/<filename>` for a resource, deriving the
-/// filename from its effective download URL (honoring `prefer_optimized`) with
-/// a fallback to the sanitized title. Single source of truth for the
+/// `Some(expected)` when the server answered with an HTML page for a resource
+/// that isn't one. `expected` is the resource's `file_type`, else the
+/// extension of `filename`, for the error message.
+fn unexpected_html(
+    content_type: Option<&str>,
+    file_type: Option<&str>,
+    filename: &str,
+) -> Option<String> {
+    let mime = content_type?.split(';').next()?.trim();
+    if !mime.eq_ignore_ascii_case("text/html") {
+        return None;
+    }
+    let expected = file_type
+        .map(|t| t.trim_start_matches('.').to_ascii_lowercase())
+        .filter(|t| !t.is_empty())
+        .or_else(|| {
+            Path::new(filename)
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_ascii_lowercase)
+        });
+    match expected.as_deref() {
+        Some("html") | Some("htm") => None,
+        Some(_) => expected,
+        None => Some("file".to_string()),
+    }
+}
+
+/// Emit `download-progress` (and its `download-event`) for download `id`,
+/// record it as the download's live snapshot and feed the queue's
+/// `overall-progress` aggregate.
+fn report_progress(app: &AppHandle, id: i64, snapshot: ActiveProgress) {
+    use crate::services::download_events::{DownloadEvent, DownloadPhase};
+    use tauri::{Emitter, Manager};
+    let _ = app.emit(
+        "download-progress",
+        serde_json::json!({
+            "id": id,
+            "progress": snapshot.progress,
+            "current_bytes": snapshot.bytes,
+            "total_bytes": snapshot.total
+        }),
+    );
+    DownloadEvent::new(id, DownloadPhase::Progress)
+        .with_progress(
+            Some(snapshot.progress),
+            Some(snapshot.bytes),
+            Some(snapshot.total),
+        )
+        .emit(app);
+    let state = app.state::<crate::commands::AppState>();
+    state.set_active_progress(id, snapshot);
+    state
+        .download_queue
+        .progress()
+        .report(app, id, snapshot.bytes, snapshot.total);
+}
+
+/// Result of [`DownloadService::check_if_modified`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    /// 304: the local copy is current.
+    NotModified,
+    /// The server copy changed (or the server ignores conditional requests).
+    Modified,
+}
+
+/// `at` as an HTTP date (`Sun, 06 Nov 1994 08:49:37 GMT`).
+fn http_date(at: chrono::DateTime<chrono::Utc>) -> String {
+    at.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// In-progress sibling of `dest_path`: `video.mp4` → `video.mp4.part`.
+pub(crate) fn part_path_for(dest_path: &Path) -> PathBuf {
+    sibling_with_suffix(dest_path, ".part")
+}
+
+/// Sidecar of the `.part`: `video.mp4` → `video.mp4.part.meta`.
+pub(crate) fn meta_path_for(dest_path: &Path) -> PathBuf {
+    sibling_with_suffix(dest_path, ".part.meta")
+}
+
+/// Whether `file_name` belongs to an unfinished download: a `.part` or its
+/// `.part.meta` sidecar. Such files are never listed or archived as if they
+/// were complete.
+pub(crate) fn is_partial_download_name(file_name: &str) -> bool {
+    file_name.ends_with(".part") || file_name.ends_with(".part.meta")
+}
+
+/// Hidden subfolder of a destination folder holding its staged `.part`
+/// files (see [`DownloadService::with_staging`]).
+pub(crate) const STAGING_DIR_NAME: &str = ".incomplete";
+
+/// Staged `.part` of `dest_path`: `week/video.mp4` →
+/// `week/.incomplete/video.mp4.part`.
+pub(crate) fn staged_part_path_for(dest_path: &Path) -> PathBuf {
+    part_path_for(&staged_sibling(dest_path))
+}
+
+/// Sidecar of the staged `.part`: `week/.incomplete/video.mp4.part.meta`.
+pub(crate) fn staged_meta_path_for(dest_path: &Path) -> PathBuf {
+    meta_path_for(&staged_sibling(dest_path))
+}
+
+/// The `.part` of `dest_path` on disk, in place or staged; `None` if there
+/// is none.
+pub(crate) fn existing_part_path(dest_path: &Path) -> Option<PathBuf> {
+    [part_path_for(dest_path), staged_part_path_for(dest_path)]
+        .into_iter()
+        .find(|path| path.exists())
+}
+
+/// `(part, sidecar)` paths of `dest_path`, staged or in place.
+fn part_paths(dest_path: &Path, staged: bool) -> (PathBuf, PathBuf) {
+    if staged {
+        (
+            staged_part_path_for(dest_path),
+            staged_meta_path_for(dest_path),
+        )
+    } else {
+        (part_path_for(dest_path), meta_path_for(dest_path))
+    }
+}
+
+fn staged_sibling(dest_path: &Path) -> PathBuf {
+    let dir = dest_path.parent().unwrap_or_else(|| Path::new(""));
+    dir.join(STAGING_DIR_NAME)
+        .join(dest_path.file_name().unwrap_or_default())
+}
+
+/// Whether `part_path` sits directly in `dest_dir` or its staging folder.
+fn in_part_dir(part_path: &Path, dest_dir: &Path) -> bool {
+    let parent = part_path.parent();
+    parent == Some(dest_dir) || parent == Some(dest_dir.join(STAGING_DIR_NAME).as_path())
+}
+
+/// Create `dest_dir`'s staging folder (hidden on Windows, like the archive)
+/// and tell whether `.part` files can go there: the final rename is only atomic within one volume, so a staging
+/// folder that can't be created or that is a mount point of another volume
+/// means falling back to an in-place `.part`.
+fn prepare_staging_dir(dest_dir: &Path) -> bool {
+    let staging = dest_dir.join(STAGING_DIR_NAME);
+    let existed = staging.exists();
+    if let Err(e) = std::fs::create_dir_all(&staging) {
+        tracing::warn!("Cannot create staging folder {:?}: {}", staging, e);
+        return false;
+    }
+    if !existed {
+        hide_dir(&staging);
+    }
+    if !same_volume(dest_dir, &staging) {
+        tracing::warn!(
+            "Staging folder {:?} is on another volume; downloading in place",
+            staging
+        );
+        return false;
+    }
+    true
+}
+
+/// Set the hidden and system attributes on `dir` (Windows only), for the
+/// app's own dot folders (`.incomplete`, the default archive). Best-effort:
+/// a failure only leaves the folder visible.
+#[cfg(target_os = "windows")]
+pub(crate) fn hide_dir(dir: &Path) {
+    use std::os::windows::process::CommandExt;
+    /// Keeps `attrib` from flashing a console window.
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    match std::process::Command::new("attrib")
+        .args(["+h", "+s"])
+        .arg(dir)
+        .creation_flags(CREATE_NO_WINDOW)
+        .status()
+    {
+        Ok(status) if status.success() => {}
+        Ok(status) => tracing::warn!("attrib on {:?} exited with {}", dir, status),
+        Err(e) => tracing::warn!("Failed to hide {:?}: {}", dir, e),
+    }
+}
+
+/// Dot-prefixed folders are already hidden outside Windows.
+#[cfg(not(target_os = "windows"))]
+pub(crate) fn hide_dir(_dir: &Path) {}
+
+#[cfg(unix)]
+fn same_volume(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev(),
+        _ => false,
+    }
+}
+
+/// No stable volume id on other platforms; a subfolder is on its parent's
+/// volume unless it is a mount point, which the app never creates.
+#[cfg(not(unix))]
+fn same_volume(_a: &Path, _b: &Path) -> bool {
+    true
+}
+
+fn sibling_with_suffix(dest_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = dest_path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    dest_path.with_file_name(name)
+}
+
+/// Resolve `<work_dir>/<week-dir>/<filename>` for a resource (or the
+/// `layout`'s folder instead of the week dir), deriving the filename from
+/// its effective download URL (honoring `prefer_optimized`) with a fallback
+/// to the sanitized title. Single source of truth for the
 /// existence/status/summary checks.
 ///
-/// Read-fallback for the week-dir naming migration (self-explanatory
+/// With [`FolderLayout::ByWeek`], read-fallback for the week-dir naming
+/// migration (self-explanatory
 /// Saturday-dated folders, e.g. "W19-2026-05-09", replacing the old
 /// "2026-W19"): tries the new-format path first, then the legacy-format path.
 /// If the file exists under the legacy name, that IS the effective dest path
 /// (so pause/resume, reveal-in-folder and downloaded-count keep working for
 /// files saved by older builds) — otherwise (including a brand-new download,
 /// where neither exists yet) the new-format path is used.
+///
+/// Within each folder the name goes through `manifest::unique_dest_path`, so
+/// a resource whose filename another resource of the same week already owns
+/// resolves to its own ` (2)`-style file instead of the other's, and a file
+/// named after a redirect target is found through the URL it came from.
 pub(crate) fn resolve_dest_path(
     resource: &Resource,
     work_dir: &Path,
+    layout: FolderLayout,
     prefer_optimized: bool,
 ) -> PathBuf {
-    let effective_url = resource.get_effective_download_url(prefer_optimized);
-    let filename = extract_filename_from_url(effective_url)
-        .unwrap_or_else(|| sanitize_filename(&resource.title));
-
-    let week = resource.week();
-    let new_path = work_dir.join(week.as_dir_name()).join(&filename);
-    if new_path.exists() {
+    let new_path = dest_path_in(
+        &layout_dir(resource, work_dir, layout),
+        resource,
+        prefer_optimized,
+    );
+    if layout != FolderLayout::ByWeek || new_path.exists() {
         return new_path;
     }
 
-    let legacy_path = work_dir.join(week.legacy_dir_name()).join(&filename);
+    let legacy_path = dest_path_in(
+        &work_dir.join(resource.week().legacy_dir_name()),
+        resource,
+        prefer_optimized,
+    );
     if legacy_path.exists() {
         return legacy_path;
     }
@@ -424,23 +1176,143 @@ pub(crate) fn resolve_dest_path(
     new_path
 }
 
-/// Resolve the week directory a resource's download should be written into
-/// (the containing folder of `resolve_dest_path`'s result): the legacy
-/// folder if the file already lives there, otherwise the new-format folder
-/// — including for a brand-new download, which always lands in the new
-/// format. Single source of truth for callers that need to create/ensure the
-/// destination directory before starting a download (`services::queue`,
-/// `commands::download_resource`), so they never drift from
-/// `resolve_dest_path`'s own resolution.
-pub(crate) fn resolve_week_dir(
+/// [`resolve_dest_path`] for the configured work directory, layout and
+/// `prefer_optimized`; `None` while no work directory is set. What every
+/// "is it downloaded / where is it" check goes through, so they can't
+/// disagree with each other or with where `download_file` writes.
+pub(crate) fn resource_dest_path(config: &AppConfig, resource: &Resource) -> Option<PathBuf> {
+    download_root(config, resource)
+        .map(|(root, layout)| resolve_dest_path(resource, root, layout, config.prefer_optimized))
+}
+
+/// The folder `resource` is laid out under and the layout used inside it:
+/// the category's `category_destinations` override with week subfolders
+/// (the category is already implied by the folder), else the work directory
+/// with the configured layout.
+fn download_root<'a>(
+    config: &'a AppConfig,
+    resource: &Resource,
+) -> Option<(&'a Path, FolderLayout)> {
+    match config.category_destination(&resource.category) {
+        Some(dir) => Some((dir, FolderLayout::ByWeek)),
+        None => config
+            .work_directory
+            .as_deref()
+            .map(|work_dir| (work_dir, config.folder_layout)),
+    }
+}
+
+/// The file `resource` downloads to inside `dir`: [`dest_filename`] through
+/// `manifest::unique_dest_path`. Shared by [`resolve_dest_path`] and
+/// `download_file`.
+fn dest_path_in(dir: &Path, resource: &Resource, prefer_optimized: bool) -> PathBuf {
+    let effective_url = resource.get_effective_download_url(prefer_optimized);
+    unique_dest_path(
+        dir,
+        &dest_filename(resource, prefer_optimized),
+        resource.id,
+        effective_url,
+    )
+}
+
+/// Base filename for `resource`: from its effective download URL, else its
+/// sanitized title.
+fn dest_filename(resource: &Resource, prefer_optimized: bool) -> String {
+    extract_filename_from_url(resource.get_effective_download_url(prefer_optimized))
+        .unwrap_or_else(|| sanitize_filename(&resource.title))
+}
+
+/// The folder `layout` assigns to `resource`, before any legacy fallback:
+/// the (new-format) week dir, the work directory itself, or the sanitized
+/// category name.
+fn layout_dir(resource: &Resource, work_dir: &Path, layout: FolderLayout) -> PathBuf {
+    match layout {
+        FolderLayout::ByWeek => work_dir.join(resource.week().as_dir_name()),
+        FolderLayout::Flat => work_dir.to_path_buf(),
+        FolderLayout::ByCategory => work_dir.join(sanitize_filename(&resource.category)),
+    }
+}
+
+/// Resolve the directory a resource's download should be written into
+/// (the containing folder of `resolve_dest_path`'s result). For
+/// [`FolderLayout::ByWeek`]: the legacy folder if the file already lives
+/// there, otherwise the new-format folder — including for a brand-new
+/// download, which always lands in the new format. Single source of truth
+/// for callers that need to create/ensure the destination directory before
+/// starting a download (`services::queue`, `commands::download_resource`),
+/// so they never drift from `resolve_dest_path`'s own resolution.
+pub(crate) fn resolve_dest_dir(
     resource: &Resource,
     work_dir: &Path,
+    layout: FolderLayout,
     prefer_optimized: bool,
 ) -> PathBuf {
-    resolve_dest_path(resource, work_dir, prefer_optimized)
+    resolve_dest_path(resource, work_dir, layout, prefer_optimized)
         .parent()
         .map(Path::to_path_buf)
-        .unwrap_or_else(|| work_dir.join(resource.week().as_dir_name()))
+        .unwrap_or_else(|| layout_dir(resource, work_dir, layout))
+}
+
+/// [`resolve_dest_dir`] for the configured destination (the category's
+/// override or the work directory), layout and `prefer_optimized`; `None`
+/// while neither is set.
+pub(crate) fn destination_dir(config: &AppConfig, resource: &Resource) -> Option<PathBuf> {
+    download_root(config, resource)
+        .map(|(root, layout)| resolve_dest_dir(resource, root, layout, config.prefer_optimized))
+}
+
+/// Schemes a download URL may use.
+const DOWNLOAD_SCHEMES: [&str; 2] = ["http", "https"];
+
+/// Check a download URL before anything is fetched: trimmed, a relative URL
+/// (from a buggy API response) resolved against `api_base`, and anything
+/// but `http`/`https` rejected with [`DownloadError::InvalidUrl`] instead of
+/// a cryptic reqwest error mid-download. Absolute URLs come back exactly as
+/// given (bar whitespace), so manifest entries keyed by URL still match.
+pub(crate) fn normalize_download_url(url: &str, api_base: &str) -> Result<String, DownloadError> {
+    let invalid = |reason: &str| DownloadError::InvalidUrl {
+        url: url.to_string(),
+        reason: reason.to_string(),
+    };
+    let trimmed = url.trim();
+    if trimmed.is_empty() {
+        return Err(invalid("empty URL"));
+    }
+    let (parsed, normalized) = match reqwest::Url::parse(trimmed) {
+        Ok(parsed) => (parsed, trimmed.to_string()),
+        // Not absolute: try it as relative to the API base.
+        Err(e) => {
+            let joined = reqwest::Url::parse(&format!("{}/", api_base.trim_end_matches('/')))
+                .and_then(|base| base.join(trimmed))
+                .map_err(|_| invalid(&e.to_string()))?;
+            let normalized = joined.to_string();
+            (joined, normalized)
+        }
+    };
+    if !DOWNLOAD_SCHEMES.contains(&parsed.scheme()) {
+        return Err(invalid(&format!(
+            "unsupported scheme {:?}",
+            parsed.scheme()
+        )));
+    }
+    if parsed.host_str().is_none() {
+        return Err(invalid("no host"));
+    }
+    Ok(normalized)
+}
+
+/// `resource` with `download_url` and `optimized_video_url` passed through
+/// [`normalize_download_url`]. Used by the queue on enqueue and again by
+/// `download_resource` for direct callers.
+pub(crate) fn normalize_resource_urls(
+    mut resource: Resource,
+    api_base: &str,
+) -> Result<Resource, DownloadError> {
+    resource.download_url = normalize_download_url(&resource.download_url, api_base)?;
+    if let Some(optimized) = resource.optimized_video_url.take() {
+        resource.optimized_video_url = Some(normalize_download_url(&optimized, api_base)?);
+    }
+    Ok(resource)
 }
 
 /// Extract filename from URL with URL decoding support
@@ -452,7 +1324,10 @@ pub(crate) fn resolve_week_dir(
 ///    final path component (`Path::file_name`), rejecting `..`, `.`, empty or
 ///    separator-bearing results so an encoded path (e.g. `..%2F..%2Fevil.sh`
 ///    or `%2Fetc%2Fx`) can never escape the destination directory.
-/// 5. Returns None if the result is invalid/empty (callers fall back to
+/// 5. Cleans the name like [`sanitize_filename`]: reserved characters (e.g. a
+///    decoded `%3A`) are mapped, trailing dots and spaces trimmed and the
+///    name truncated to [`MAX_FILENAME_BYTES`] keeping its extension.
+/// 6. Returns None if the result is invalid/empty (callers fall back to
 ///    `sanitize_filename(title)`).
 pub(crate) fn extract_filename_from_url(url: &str) -> Option<String> {
     url.split('/')
@@ -479,17 +1354,36 @@ pub(crate) fn extract_filename_from_url(url: &str) -> Option<String> {
             {
                 None
             } else {
-                Some(file_name.to_string())
+                sanitize_component(file_name)
             }
         })
 }
 
+/// Byte budget for a sanitized filename. Well under the 255-byte component
+/// limit of NTFS/ext4/APFS, leaving room for the `.part` suffix of in-flight
+/// downloads and for SMB shares that count the limit more strictly.
+const MAX_FILENAME_BYTES: usize = 200;
+
+/// Longest trailing `.xyz` still treated as an extension (and so kept intact)
+/// when a name has to be truncated.
+const MAX_EXTENSION_BYTES: usize = 16;
+
 /// Sanitize a string to be a valid filename
 ///
 /// Neutralizes reserved characters, path separators and `..` traversal
-/// sequences anywhere in the name, and never returns an empty string
-/// (falls back to `"download"`).
+/// sequences anywhere in the name, then makes the result safe on Windows
+/// too (work directories are often synced to Windows shares): trailing dots
+/// and spaces (which Windows silently strips) are removed, reserved device
+/// names get a `_` prefix, and the name is truncated to
+/// [`MAX_FILENAME_BYTES`] keeping its extension. Never returns an empty
+/// string (falls back to `"download"`).
 pub(crate) fn sanitize_filename(name: &str) -> String {
+    sanitize_component(name).unwrap_or_else(|| "download".to_string())
+}
+
+/// [`sanitize_filename`] without the fallback: `None` when nothing usable is
+/// left, so a name taken from a URL can fall back to the title instead.
+fn sanitize_component(name: &str) -> Option<String> {
     let mapped = name
         .chars()
         .map(|c| match c {
@@ -502,19 +1396,50 @@ pub(crate) fn sanitize_filename(name: &str) -> String {
     // Neutralize `..` traversal sequences left after separator mapping.
     let neutralized = mapped.replace("..", "_");
 
-    let trimmed = neutralized.trim();
-    if trimmed.is_empty() {
-        "download".to_string()
+    let trimmed = neutralized.trim().trim_end_matches(['.', ' ']);
+    let truncated = truncate_preserving_extension(trimmed, MAX_FILENAME_BYTES);
+    if truncated.is_empty() {
+        None
+    } else if is_windows_reserved_stem(&truncated) {
+        Some(format!("_{truncated}"))
     } else {
-        trimmed.to_string()
+        Some(truncated)
     }
 }
 
+/// Cut `name` down to at most `max_bytes` bytes on a char boundary. A short
+/// trailing extension is kept whole and the stem shortened instead, so a long
+/// title still opens with the right application.
+fn truncate_preserving_extension(name: &str, max_bytes: usize) -> String {
+    if name.len() <= max_bytes {
+        return name.to_string();
+    }
+    let (stem, extension) = match name.rfind('.') {
+        Some(dot) if dot > 0 && name.len() - dot <= MAX_EXTENSION_BYTES => name.split_at(dot),
+        _ => (name, ""),
+    };
+    let mut end = max_bytes.saturating_sub(extension.len()).min(stem.len());
+    while !stem.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}{}", stem[..end].trim_end_matches(['.', ' ']), extension)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::mock_http::{self, Response};
     use chrono::{TimeZone, Utc};
 
+    fn config_for(work_dir: &Path, layout: FolderLayout, prefer_optimized: bool) -> AppConfig {
+        AppConfig {
+            work_directory: Some(work_dir.to_path_buf()),
+            folder_layout: layout,
+            prefer_optimized,
+            ..AppConfig::default()
+        }
+    }
+
     fn make_resource(id: i64, url: &str, created_at: chrono::DateTime<Utc>) -> Resource {
         Resource {
             id,
@@ -549,14 +1474,18 @@ mod tests {
         std::fs::create_dir_all(&legacy_dir).unwrap();
         std::fs::write(legacy_dir.join("file.mp4"), b"x").unwrap();
 
-        let resolved = resolve_dest_path(&resource, work_dir, true);
+        let resolved = resolve_dest_path(&resource, work_dir, FolderLayout::ByWeek, true);
         assert_eq!(resolved, legacy_dir.join("file.mp4"));
         assert!(DownloadService::check_file_exists(
-            &resource, work_dir, true
+            &resource,
+            &config_for(work_dir, FolderLayout::ByWeek, true)
         ));
 
         // The directory-creation helper must agree with resolve_dest_path.
-        assert_eq!(resolve_week_dir(&resource, work_dir, true), legacy_dir);
+        assert_eq!(
+            resolve_dest_dir(&resource, work_dir, FolderLayout::ByWeek, true),
+            legacy_dir
+        );
     }
 
     /// A brand-new download (neither the new- nor legacy-format file exists
@@ -569,16 +1498,17 @@ mod tests {
         let resource = make_resource(2, "https://example.com/file.mp4", created_at);
         let week = resource.week();
 
-        let resolved = resolve_dest_path(&resource, work_dir, true);
+        let resolved = resolve_dest_path(&resource, work_dir, FolderLayout::ByWeek, true);
         let expected_new_dir = work_dir.join(week.as_dir_name());
         assert_eq!(resolved, expected_new_dir.join("file.mp4"));
         assert_ne!(week.as_dir_name(), week.legacy_dir_name());
         assert!(!DownloadService::check_file_exists(
-            &resource, work_dir, true
+            &resource,
+            &config_for(work_dir, FolderLayout::ByWeek, true)
         ));
 
         assert_eq!(
-            resolve_week_dir(&resource, work_dir, true),
+            resolve_dest_dir(&resource, work_dir, FolderLayout::ByWeek, true),
             expected_new_dir
         );
     }
@@ -601,10 +1531,191 @@ mod tests {
         std::fs::create_dir_all(&legacy_dir).unwrap();
         std::fs::write(legacy_dir.join("file.mp4"), b"legacy").unwrap();
 
-        let resolved = resolve_dest_path(&resource, work_dir, true);
+        let resolved = resolve_dest_path(&resource, work_dir, FolderLayout::ByWeek, true);
         assert_eq!(resolved, new_dir.join("file.mp4"));
     }
 
+    #[test]
+    fn test_resolve_dest_path_follows_folder_layout() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let work_dir = tmp.path();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let mut resource = make_resource(7, "https://example.com/file.mp4", created_at);
+        resource.category = "Video/Sermoni".to_string();
+
+        assert_eq!(
+            resolve_dest_path(&resource, work_dir, FolderLayout::Flat, true),
+            work_dir.join("file.mp4")
+        );
+        assert_eq!(
+            resolve_dest_dir(&resource, work_dir, FolderLayout::ByCategory, true),
+            work_dir.join("Video_Sermoni")
+        );
+
+        // A flat layout never falls back to the week folders.
+        let week_dir = work_dir.join(resource.week().as_dir_name());
+        std::fs::create_dir_all(&week_dir).unwrap();
+        std::fs::write(week_dir.join("file.mp4"), b"x").unwrap();
+        assert!(!DownloadService::check_file_exists(
+            &resource,
+            &config_for(work_dir, FolderLayout::Flat, true)
+        ));
+    }
+
+    /// The status checks and the download itself must agree on the file:
+    /// `download_file` writes where `resource_dest_path` says, whichever of
+    /// the URL filename or the title fallback applies.
+    #[tokio::test]
+    async fn test_download_lands_at_resource_dest_path() {
+        let base = serve_with_redirect(2, b"audio bytes");
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let config = config_for(tmp.path(), FolderLayout::ByWeek, true);
+
+        for (id, url) in [(1, format!("{base}/sermon.mp3")), (2, format!("{base}/"))] {
+            let resource = make_resource(id, &url, created_at);
+            let expected = resource_dest_path(&config, &resource).unwrap();
+            let dest_dir = destination_dir(&config, &resource).unwrap();
+
+            let (path, _hash) = DownloadService::new()
+                .download_resource(&resource, &dest_dir, None, None, true)
+                .await
+                .unwrap();
+
+            assert_eq!(path, expected);
+            assert!(DownloadService::check_file_exists(&resource, &config));
+        }
+    }
+
+    #[test]
+    fn test_resource_dest_path_needs_work_directory() {
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(3, "https://example.com/file.mp4", created_at);
+        let config = AppConfig::default();
+        assert_eq!(resource_dest_path(&config, &resource), None);
+        assert!(!DownloadService::check_file_exists(&resource, &config));
+        assert!(!DownloadService::check_partial_exists(&resource, &config));
+    }
+
+    /// The destination folder is created on demand, and a folder that can't
+    /// be created fails typed before any request is made.
+    #[tokio::test]
+    async fn test_download_resource_creates_dest_dir_or_fails_typed() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(9, "https://www.youtube.com/watch?v=abc", created_at);
+
+        let dest_dir = tmp.path().join("2026-W04");
+        let (path, _) = DownloadService::new()
+            .download_resource(&resource, &dest_dir, None, None, true)
+            .await
+            .unwrap();
+        assert!(path.starts_with(&dest_dir));
+
+        let blocker = tmp.path().join("not-a-dir");
+        std::fs::write(&blocker, b"file").unwrap();
+        let err = DownloadService::new()
+            .download_resource(&resource, &blocker.join("week"), None, None, true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DownloadError::CreateDirectoryFailed { .. }));
+    }
+
+    #[test]
+    fn test_destination_dir_uses_config() {
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(8, "https://example.com/file.mp4", created_at);
+        let mut config = AppConfig::default();
+        assert_eq!(destination_dir(&config, &resource), None);
+
+        config.work_directory = Some(PathBuf::from("/nonexistent/work"));
+        config.folder_layout = FolderLayout::Flat;
+        assert_eq!(
+            destination_dir(&config, &resource),
+            Some(PathBuf::from("/nonexistent/work"))
+        );
+
+        // An override keeps week subfolders whatever the layout.
+        config.category_destinations.insert(
+            resource.category.clone(),
+            PathBuf::from("/nonexistent/videos"),
+        );
+        assert_eq!(
+            destination_dir(&config, &resource),
+            Some(PathBuf::from("/nonexistent/videos").join(resource.week().as_dir_name()))
+        );
+    }
+
+    /// Two resources of one week whose URLs end in the same filename: once A
+    /// has claimed it in the week manifest, B resolves to `shared (2).mp4`
+    /// and A's file no longer makes B look downloaded.
+    #[test]
+    fn test_resolve_dest_path_steps_past_filename_owned_by_other_resource() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let work_dir = tmp.path();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap(); // 2026-W04
+        let a = make_resource(10, "https://a.example.com/shared.mp4", created_at);
+        let b = make_resource(11, "https://b.example.com/shared.mp4", created_at);
+        let week_dir = work_dir.join(a.week().as_dir_name());
+        std::fs::create_dir_all(&week_dir).unwrap();
+
+        let a_path = claim_dest_path(&week_dir, "shared.mp4", a.id, &a.download_url);
+        std::fs::write(&a_path, b"a").unwrap();
+
+        assert_eq!(
+            resolve_dest_path(&a, work_dir, FolderLayout::ByWeek, true),
+            a_path
+        );
+        assert_eq!(
+            resolve_dest_path(&b, work_dir, FolderLayout::ByWeek, true),
+            week_dir.join("shared (2).mp4")
+        );
+        assert!(DownloadService::check_file_exists(
+            &a,
+            &config_for(work_dir, FolderLayout::ByWeek, true)
+        ));
+        assert!(!DownloadService::check_file_exists(
+            &b,
+            &config_for(work_dir, FolderLayout::ByWeek, true)
+        ));
+    }
+
+    #[test]
+    fn test_response_validator_prefers_strong_etag() {
+        use reqwest::header::{HeaderMap, HeaderValue, ETAG, LAST_MODIFIED};
+        let last_modified = "Wed, 21 Oct 2026 07:28:00 GMT";
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(response_validator(&headers), None);
+
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static(last_modified));
+        assert_eq!(response_validator(&headers).as_deref(), Some(last_modified));
+
+        headers.insert(ETAG, HeaderValue::from_static("\"abc123\""));
+        assert_eq!(response_validator(&headers).as_deref(), Some("\"abc123\""));
+
+        // A weak ETag can't be used in If-Range: fall back to Last-Modified.
+        headers.insert(ETAG, HeaderValue::from_static("W/\"abc123\""));
+        assert_eq!(response_validator(&headers).as_deref(), Some(last_modified));
+    }
+
+    #[test]
+    fn test_part_meta_roundtrip_and_missing() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("video.mp4.part.meta");
+        assert_eq!(read_part_meta(&path), None);
+
+        let meta = PartMeta {
+            url: "https://example.com/video.mp4".to_string(),
+            validator: Some("\"v1\"".to_string()),
+        };
+        write_part_meta(&path, &meta);
+        assert_eq!(read_part_meta(&path), Some(meta));
+
+        std::fs::write(&path, b"garbage").unwrap();
+        assert_eq!(read_part_meta(&path), None);
+    }
+
     #[test]
     fn test_extract_filename_from_url_decoded() {
         // Test URL-encoded spaces
@@ -717,6 +1828,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_filename_is_sanitized_like_titles() {
+        // Decoded reserved characters are mapped, trailing dots and spaces
+        // (which Windows strips) are trimmed.
+        assert_eq!(
+            extract_filename_from_url("https://host/Q%3A%20why%3F.pdf"),
+            Some("Q_ why_.pdf".to_string())
+        );
+        assert_eq!(
+            extract_filename_from_url("https://host/notes.txt.%20."),
+            Some("notes.txt".to_string())
+        );
+        // Nothing usable left: the caller falls back to the title.
+        assert!(extract_filename_from_url("https://host/.%20.").is_none());
+
+        // A 300-character name is truncated keeping its extension.
+        let url = format!("https://host/{}.mp4", "v".repeat(300));
+        let name = extract_filename_from_url(&url).unwrap();
+        assert!(name.len() <= MAX_FILENAME_BYTES, "{}", name.len());
+        assert!(name.ends_with("v.mp4"), "{name}");
+    }
+
     #[test]
     fn test_sanitize_filename_neutralizes_traversal() {
         // `..` sequences and separators are neutralized anywhere in the name:
@@ -748,6 +1881,41 @@ mod tests {
         assert_eq!(sanitize_filename("  Test  "), "Test");
     }
 
+    #[test]
+    fn test_sanitize_filename_prefixes_windows_reserved_names() {
+        assert_eq!(sanitize_filename("CON.txt"), "_CON.txt");
+        assert_eq!(sanitize_filename("con"), "_con");
+        assert_eq!(sanitize_filename("LPT1.mp4"), "_LPT1.mp4");
+        // Only the exact device names are reserved.
+        assert_eq!(sanitize_filename("CONTENT.txt"), "CONTENT.txt");
+        assert_eq!(sanitize_filename("COM10.txt"), "COM10.txt");
+    }
+
+    #[test]
+    fn test_sanitize_filename_strips_trailing_dots_and_spaces() {
+        assert_eq!(sanitize_filename("name."), "name");
+        assert_eq!(sanitize_filename("name . . "), "name");
+        assert_eq!(sanitize_filename("..."), "_");
+        assert_eq!(sanitize_filename(". "), "download");
+    }
+
+    #[test]
+    fn test_sanitize_filename_truncates_long_titles() {
+        let long_title = "a".repeat(300);
+        let out = sanitize_filename(&long_title);
+        assert_eq!(out.len(), MAX_FILENAME_BYTES);
+
+        // The extension survives truncation.
+        let out = sanitize_filename(&format!("{}.mp4", "b".repeat(300)));
+        assert!(out.len() <= MAX_FILENAME_BYTES);
+        assert!(out.ends_with("b.mp4"), "{out}");
+
+        // Multi-byte titles are cut on a char boundary.
+        let out = sanitize_filename(&"è".repeat(300));
+        assert!(out.len() <= MAX_FILENAME_BYTES);
+        assert!(out.chars().all(|c| c == 'è'));
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn test_linux_desktop_shortcut_format() {
@@ -818,4 +1986,509 @@ mod tests {
         assert_eq!(signal.load(Ordering::Relaxed), STATUS_CANCELLED);
         assert_ne!(signal.load(Ordering::Relaxed), STATUS_PAUSED);
     }
+
+    #[test]
+    fn test_last_modified_time_parses_http_date() {
+        use reqwest::header::{HeaderMap, HeaderValue, LAST_MODIFIED};
+        let mut headers = HeaderMap::new();
+        assert_eq!(last_modified_time(&headers), None);
+
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        let expected = std::time::UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+        assert_eq!(last_modified_time(&headers), Some(expected));
+
+        headers.insert(LAST_MODIFIED, HeaderValue::from_static("yesterday"));
+        assert_eq!(last_modified_time(&headers), None);
+    }
+
+    #[test]
+    fn test_set_file_mtime_applies_server_time() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("video.mp4");
+        std::fs::write(&path, b"data").unwrap();
+        let mtime = std::time::UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+
+        set_file_mtime(&path, mtime);
+
+        assert_eq!(std::fs::metadata(&path).unwrap().modified().unwrap(), mtime);
+    }
+
+    #[test]
+    fn test_progress_throttle_waits_for_interval_and_percent_change() {
+        let start = Instant::now();
+        let mut throttle = ProgressThrottle::new(start);
+        let total = 1_000;
+
+        // Too soon, whatever the progress.
+        assert_eq!(
+            throttle.poll(start + Duration::from_millis(50), 500, total),
+            None
+        );
+
+        let later = start + PROGRESS_EMIT_INTERVAL;
+        assert_eq!(throttle.poll(later, 500, total), Some(50));
+
+        // Interval elapsed but still 50%: nothing new to report.
+        let much_later = later + PROGRESS_EMIT_INTERVAL * 5;
+        assert_eq!(throttle.poll(much_later, 505, total), None);
+        assert_eq!(throttle.poll(much_later, 510, total), Some(51));
+
+        // A whole point moved, but within the interval.
+        assert_eq!(
+            throttle.poll(much_later + Duration::from_millis(10), 900, total),
+            None
+        );
+    }
+
+    #[test]
+    fn test_progress_percent_bounds() {
+        assert_eq!(progress_percent(0, 0), 0);
+        assert_eq!(progress_percent(999, 1_000), 99);
+        assert_eq!(progress_percent(1_200, 1_000), 100);
+    }
+
+    /// Serve `n` requests: `/download` redirects to a signed CDN-style URL,
+    /// anything else serves `body`.
+    fn serve_with_redirect(n: usize, body: &'static [u8]) -> String {
+        mock_http::serve(n, move |_, request| {
+            if request.target == "/download" {
+                Response::new("302 Found").header("Location", "/cdn/real-name.mp4?sig=abc")
+            } else {
+                Response::ok(body)
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_download_follows_redirect_and_names_file_after_final_url() {
+        let base = serve_with_redirect(2, b"sermon bytes");
+        let tmp = tempfile::TempDir::new().unwrap();
+        let work_dir = tmp.path();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(42, &format!("{base}/download"), created_at);
+        let week_dir = work_dir.join(resource.week().as_dir_name());
+        std::fs::create_dir_all(&week_dir).unwrap();
+
+        let (path, _hash) = DownloadService::new()
+            .download_resource(&resource, &week_dir, None, None, false)
+            .await
+            .unwrap();
+
+        assert_eq!(path, week_dir.join("real-name.mp4"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"sermon bytes");
+        // The status checks only know the API URL, and still find it.
+        assert_eq!(
+            resolve_dest_path(&resource, work_dir, FolderLayout::ByWeek, false),
+            path
+        );
+        assert!(DownloadService::check_file_exists(
+            &resource,
+            &config_for(work_dir, FolderLayout::ByWeek, false)
+        ));
+    }
+
+    #[test]
+    fn test_unexpected_html_flags_pages_served_for_media() {
+        let flagged = unexpected_html(Some("text/html; charset=utf-8"), None, "sermon.mp4");
+        assert_eq!(flagged.as_deref(), Some("mp4"));
+        let flagged = unexpected_html(Some("TEXT/HTML"), Some("zip"), "download");
+        assert_eq!(flagged.as_deref(), Some("zip"));
+        assert_eq!(
+            unexpected_html(Some("text/html"), None, "download").as_deref(),
+            Some("file")
+        );
+    }
+
+    #[test]
+    fn test_unexpected_html_accepts_real_content_and_html_resources() {
+        assert_eq!(unexpected_html(Some("video/mp4"), None, "sermon.mp4"), None);
+        assert_eq!(unexpected_html(None, None, "sermon.mp4"), None);
+        assert_eq!(unexpected_html(Some("text/html"), None, "page.html"), None);
+        assert_eq!(unexpected_html(Some("text/html"), Some("HTM"), "x"), None);
+    }
+
+    #[test]
+    fn test_http_date_format() {
+        let at = Utc.with_ymd_and_hms(1994, 11, 6, 8, 49, 37).unwrap();
+        assert_eq!(http_date(at), "Sun, 06 Nov 1994 08:49:37 GMT");
+    }
+
+    /// Answer the first `throttled` requests with 429 and `Retry-After: 0`,
+    /// then serve `body`. Returns the base URL.
+    fn serve_throttled(throttled: usize, body: &'static [u8]) -> String {
+        mock_http::serve(throttled + 1, move |i, _| {
+            if i < throttled {
+                Response::new("429 Too Many Requests").header("Retry-After", "0")
+            } else {
+                Response::ok(body)
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_download_waits_out_retry_after_then_gives_up() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+
+        let base = serve_throttled(2, b"sermon bytes");
+        let resource = make_resource(1, &format!("{base}/sermon.mp3"), created_at);
+        let (path, _hash) = DownloadService::new()
+            .download_resource(&resource, tmp.path(), None, None, true)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(path).unwrap(), b"sermon bytes");
+
+        let throttled = crate::constants::MAX_RATE_LIMIT_RETRIES as usize + 1;
+        let base = serve_throttled(throttled, b"never sent");
+        let resource = make_resource(2, &format!("{base}/other.mp3"), created_at);
+        let err = DownloadService::new()
+            .download_resource(&resource, tmp.path(), None, None, true)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, DownloadError::RateLimited { wait_secs: 0 }),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_wait_stops_on_signal() {
+        let signal = AtomicU8::new(STATUS_PAUSED);
+        assert_eq!(
+            wait_unless_interrupted(Duration::from_secs(3600), Some(&signal)).await,
+            Some(STATUS_PAUSED)
+        );
+        assert_eq!(
+            wait_unless_interrupted(Duration::from_millis(10), None).await,
+            None
+        );
+    }
+
+    /// Answers 304 when the request carries `If-Modified-Since` equal to
+    /// `unchanged_since`, 200 otherwise, for `n` requests.
+    fn serve_conditional(n: usize, unchanged_since: String) -> String {
+        mock_http::serve(n, move |_, request| {
+            if request.header("If-Modified-Since") == Some(unchanged_since.as_str()) {
+                Response::new("304 Not Modified")
+            } else {
+                Response::new("200 OK")
+            }
+        })
+    }
+
+    /// A partial whose remote file changed since (new `ETag`) is resumed
+    /// with `If-Range`; the server answers 200 with the whole new file, so
+    /// the old bytes are discarded rather than stitched to the new ones.
+    #[tokio::test]
+    async fn test_changed_validator_restarts_instead_of_resuming() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let requests = seen.clone();
+        let base = mock_http::serve(1, move |_, request| {
+            requests.lock().unwrap().push(request.clone());
+            if request.header("If-Range") == Some("\"v2\"") {
+                Response::new("206 Partial Content").body(&b"never sent"[..])
+            } else {
+                Response::ok(&b"corrected sermon"[..]).header("ETag", "\"v2\"")
+            }
+        });
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let url = format!("{base}/cdn/video.mp4");
+        let resource = make_resource(47, &url, created_at);
+        std::fs::write(tmp.path().join("video.mp4.part"), b"old ser").unwrap();
+        write_part_meta(
+            &tmp.path().join("video.mp4.part.meta"),
+            &PartMeta {
+                url,
+                validator: Some("\"v1\"".to_string()),
+            },
+        );
+
+        let (path, _hash) = DownloadService::new()
+            .download_resource(&resource, tmp.path(), None, None, false)
+            .await
+            .unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen[0].header("Range"), Some("bytes=7-"));
+        assert_eq!(seen[0].header("If-Range"), Some("\"v1\""));
+        assert_eq!(std::fs::read(&path).unwrap(), b"corrected sermon");
+        assert!(!tmp.path().join("video.mp4.part").exists());
+    }
+
+    #[tokio::test]
+    async fn test_check_if_modified_maps_304_to_not_modified() {
+        let since = Utc.with_ymd_and_hms(2026, 1, 20, 9, 0, 0).unwrap();
+        let base = serve_conditional(2, http_date(since));
+        let resource = make_resource(7, &format!("{base}/video.mp4"), since);
+        let service = DownloadService::new();
+
+        let outcome = service.check_if_modified(&resource, false, since).await;
+        assert_eq!(outcome.unwrap(), Outcome::NotModified);
+
+        let earlier = since - chrono::Duration::days(1);
+        let outcome = service.check_if_modified(&resource, false, earlier).await;
+        assert_eq!(outcome.unwrap(), Outcome::Modified);
+    }
+
+    #[tokio::test]
+    async fn test_stop_signal_keeps_part_for_later_resume() {
+        let base = serve_with_redirect(1, b"sermon bytes");
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(43, &format!("{base}/cdn/video.mp4"), created_at);
+        let signal = Arc::new(AtomicU8::new(STATUS_STOPPED));
+
+        let result = DownloadService::new()
+            .download_resource(&resource, tmp.path(), None, Some(signal), false)
+            .await;
+
+        assert!(matches!(result, Err(DownloadError::Stopped)));
+        assert!(tmp.path().join("video.mp4.part").exists());
+        assert!(tmp.path().join("video.mp4.part.meta").exists());
+        assert!(!tmp.path().join("video.mp4").exists());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_keeping_partial_leaves_part_on_disk() {
+        let base = serve_with_redirect(1, b"sermon bytes");
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(44, &format!("{base}/cdn/video.mp4"), created_at);
+        let signal = Arc::new(AtomicU8::new(STATUS_CANCELLED_KEEP_PARTIAL));
+
+        let result = DownloadService::new()
+            .download_resource(&resource, tmp.path(), None, Some(signal), false)
+            .await;
+
+        assert!(matches!(result, Err(DownloadError::Cancelled)));
+        assert!(tmp.path().join("video.mp4.part").exists());
+        assert!(tmp.path().join("video.mp4.part.meta").exists());
+        assert!(!tmp.path().join("video.mp4").exists());
+    }
+
+    /// Identical contents hash identically whatever the filename (the
+    /// dedup check), and a missing file is a typed error, not an I/O string.
+    #[test]
+    fn test_hash_file_matches_contents_and_reports_missing() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let a = tmp.path().join("a.mp4");
+        let b = tmp.path().join("copy of a.mp4");
+        std::fs::write(&a, b"same bytes").unwrap();
+        std::fs::write(&b, b"same bytes").unwrap();
+
+        let hash = hash_file(&a).unwrap();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, hash_file(&b).unwrap());
+
+        std::fs::write(&b, b"other bytes").unwrap();
+        assert_ne!(hash, hash_file(&b).unwrap());
+
+        let missing = tmp.path().join("missing.mp4");
+        assert!(matches!(
+            hash_file(&missing),
+            Err(FileError::FileNotFound(path)) if path == missing
+        ));
+    }
+
+    /// Answers 416 to any request with a `Range` header; otherwise 429 with
+    /// `Retry-After: 0` to the first `throttled` ones, then 200 with `body`.
+    /// For `n` requests.
+    fn serve_rejecting_ranges(n: usize, mut throttled: usize, body: &'static [u8]) -> String {
+        mock_http::serve(n, move |_, request| {
+            if request.header("Range").is_some() {
+                Response::new("416 Range Not Satisfiable")
+                    .header("Content-Range", format!("bytes */{}", body.len()))
+            } else if throttled > 0 {
+                throttled -= 1;
+                Response::new("429 Too Many Requests").header("Retry-After", "0")
+            } else {
+                Response::ok(body)
+            }
+        })
+    }
+
+    /// A partial longer than the remote file gets a 416 on resume: it is
+    /// discarded and the file fetched again from zero. That fresh request
+    /// waits out a `Retry-After` like the first one.
+    #[tokio::test]
+    async fn test_range_not_satisfiable_restarts_from_zero() {
+        for throttled in [0, 1] {
+            let base = serve_rejecting_ranges(2 + throttled, throttled, b"shorter errata");
+            let tmp = tempfile::TempDir::new().unwrap();
+            let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+            let url = format!("{base}/cdn/video.mp4");
+            let resource = make_resource(44, &url, created_at);
+            std::fs::write(
+                tmp.path().join("video.mp4.part"),
+                b"a much longer partial from before the errata",
+            )
+            .unwrap();
+            write_part_meta(
+                &tmp.path().join("video.mp4.part.meta"),
+                &PartMeta {
+                    url,
+                    validator: None,
+                },
+            );
+
+            let (path, _hash) = DownloadService::new()
+                .download_resource(&resource, tmp.path(), None, None, false)
+                .await
+                .unwrap();
+
+            assert_eq!(path, tmp.path().join("video.mp4"), "throttled: {throttled}");
+            assert_eq!(std::fs::read(&path).unwrap(), b"shorter errata");
+            assert!(!tmp.path().join("video.mp4.part").exists());
+        }
+    }
+
+    #[test]
+    fn test_bytes_per_second_averages_over_elapsed() {
+        assert_eq!(bytes_per_second(1_000, Duration::ZERO), 0);
+        assert_eq!(bytes_per_second(1_000, Duration::from_millis(500)), 2_000);
+        assert_eq!(bytes_per_second(0, Duration::from_secs(3)), 0);
+    }
+
+    /// With staging on, the `.part` lives in `.incomplete/` while the
+    /// download runs and only the finished file lands in the folder.
+    #[tokio::test]
+    async fn test_staged_download_moves_finished_file_into_place() {
+        let base = serve_with_redirect(1, b"sermon bytes");
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(45, &format!("{base}/cdn/video.mp4"), created_at);
+
+        let (path, _hash) = DownloadService::new()
+            .with_staging(true)
+            .download_resource(&resource, tmp.path(), None, None, false)
+            .await
+            .unwrap();
+
+        assert_eq!(path, tmp.path().join("video.mp4"));
+        assert_eq!(std::fs::read(&path).unwrap(), b"sermon bytes");
+        assert!(!tmp.path().join(STAGING_DIR_NAME).exists());
+    }
+
+    #[tokio::test]
+    async fn test_stopped_staged_download_keeps_part_in_staging_folder() {
+        let base = serve_with_redirect(1, b"sermon bytes");
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(46, &format!("{base}/cdn/video.mp4"), created_at);
+        let signal = Arc::new(AtomicU8::new(STATUS_STOPPED));
+
+        let result = DownloadService::new()
+            .with_staging(true)
+            .download_resource(&resource, tmp.path(), None, Some(signal), false)
+            .await;
+
+        assert!(matches!(result, Err(DownloadError::Stopped)));
+        let dest = tmp.path().join("video.mp4");
+        assert!(!part_path_for(&dest).exists());
+        assert_eq!(existing_part_path(&dest), Some(staged_part_path_for(&dest)));
+        assert_eq!(
+            staged_part_path_for(&dest),
+            tmp.path().join(".incomplete").join("video.mp4.part")
+        );
+    }
+
+    #[test]
+    fn test_cancellable_hash_reports_progress_and_stops_when_halted() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = tmp.path().join("big.mp4");
+        std::fs::write(&path, vec![7u8; HASH_CHUNK_SIZE * 2 + 10]).unwrap();
+
+        let running = AtomicU8::new(STATUS_RUNNING);
+        let mut reports = Vec::new();
+        let hash = calculate_file_hash_cancellable(&path, Some(&running), |hashed, total| {
+            reports.push((hashed, total))
+        })
+        .unwrap();
+        assert_eq!(hash, Some(calculate_file_hash(&path).unwrap()));
+        let total = (HASH_CHUNK_SIZE * 2 + 10) as u64;
+        assert_eq!(reports.last(), Some(&(total, total)));
+
+        for status in [
+            STATUS_CANCELLED,
+            STATUS_PAUSED,
+            STATUS_STOPPED,
+            STATUS_CANCELLED_KEEP_PARTIAL,
+        ] {
+            let signal = AtomicU8::new(status);
+            let hash = calculate_file_hash_cancellable(&path, Some(&signal), |_, _| {
+                panic!("no chunk may be read once {}", status_name(status))
+            })
+            .unwrap();
+            assert_eq!(hash, None, "{}", status_name(status));
+        }
+    }
+
+    #[test]
+    fn test_normalize_download_url_resolves_relative_against_api_base() {
+        let base = "https://api.example.com/v1";
+        assert_eq!(
+            normalize_download_url("files/sermon.mp4", base).unwrap(),
+            "https://api.example.com/v1/files/sermon.mp4"
+        );
+        assert_eq!(
+            normalize_download_url("/files/sermon.mp4", base).unwrap(),
+            "https://api.example.com/files/sermon.mp4"
+        );
+        // Absolute URLs are left exactly as given.
+        assert_eq!(
+            normalize_download_url(" https://cdn.example.com/a b.mp4 ", base).unwrap(),
+            "https://cdn.example.com/a b.mp4"
+        );
+    }
+
+    #[test]
+    fn test_normalize_download_url_rejects_ftp_and_empty() {
+        let base = "https://api.example.com";
+        let err = normalize_download_url("ftp://files.example.com/sermon.mp4", base).unwrap_err();
+        assert!(
+            matches!(&err, DownloadError::InvalidUrl { reason, .. } if reason.contains("ftp")),
+            "{err:?}"
+        );
+        assert!(matches!(
+            normalize_download_url("   ", base),
+            Err(DownloadError::InvalidUrl { .. })
+        ));
+        assert!(matches!(
+            normalize_download_url("file:///etc/passwd", base),
+            Err(DownloadError::InvalidUrl { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_download_resource_rejects_invalid_url_before_touching_disk() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let created_at = Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap();
+        let resource = make_resource(47, "ftp://files.example.com/sermon.mp4", created_at);
+        let dest = tmp.path().join("week");
+
+        let err = DownloadService::new()
+            .download_resource(&resource, &dest, None, None, false)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, DownloadError::InvalidUrl { .. }));
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn test_status_name() {
+        assert_eq!(status_name(STATUS_RUNNING), "running");
+        assert_eq!(status_name(STATUS_PAUSED), "paused");
+        assert_eq!(status_name(STATUS_CANCELLED), "cancelled");
+        assert_eq!(status_name(STATUS_STOPPED), "stopped");
+        assert_eq!(
+            status_name(STATUS_CANCELLED_KEEP_PARTIAL),
+            "cancelled-keep-partial"
+        );
+        assert_eq!(status_name(42), "unknown");
+    }
 }
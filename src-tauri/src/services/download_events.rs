@@ -0,0 +1,131 @@
+//! Unified download lifecycle event
+//!
+//! Every step of a download is also announced on the single `download-event`
+//! channel with one payload shape, [`DownloadEvent`], so the frontend can
+//! follow a download with one listener. The older per-step events
+//! (`download-started`, `download-progress`, `download-complete`,
+//! `download-failed`, `download-paused`, `download-cancelled`,
+//! `download-stopped`) are still emitted next to it during the deprecation
+//! window, unchanged.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Name of the unified channel.
+pub const DOWNLOAD_EVENT: &str = "download-event";
+
+/// Which step of a download a [`DownloadEvent`] reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPhase {
+    Started,
+    Progress,
+    Completed,
+    Failed,
+    Paused,
+    Stopped,
+    Cancelled,
+}
+
+/// Payload of `download-event`. Optional fields are left out when they
+/// don't apply to the phase.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DownloadEvent {
+    pub id: i64,
+    pub phase: DownloadPhase,
+    /// Percentage, 0–100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress: Option<u8>,
+    /// Bytes received so far (or, once completed, the file's size).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub total: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl DownloadEvent {
+    pub fn new(id: i64, phase: DownloadPhase) -> Self {
+        Self {
+            id,
+            phase,
+            progress: None,
+            bytes: None,
+            total: None,
+            error: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn with_progress(
+        mut self,
+        progress: Option<u8>,
+        bytes: Option<u64>,
+        total: Option<u64>,
+    ) -> Self {
+        self.progress = progress;
+        self.bytes = bytes;
+        self.total = total;
+        self
+    }
+
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Emit on [`DOWNLOAD_EVENT`]; a failed emit is only logged.
+    pub fn emit(self, app: &AppHandle) {
+        if let Err(e) = app.emit(DOWNLOAD_EVENT, &self) {
+            tracing::error!(
+                "Failed to emit {} ({:?}) for {}: {:?}",
+                DOWNLOAD_EVENT,
+                self.phase,
+                self.id,
+                e
+            );
+        }
+    }
+}
+
+/// Emit a `download-event` that carries nothing but its phase.
+pub fn emit_phase(app: &AppHandle, id: i64, phase: DownloadPhase) {
+    DownloadEvent::new(id, phase).emit(app);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_omits_fields_that_do_not_apply() {
+        let event = DownloadEvent::new(7, DownloadPhase::Started);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["id"], 7);
+        assert_eq!(json["phase"], "started");
+        assert!(json.get("progress").is_none());
+        assert!(json.get("error").is_none());
+        assert!(json["timestamp"].is_string());
+
+        let event = DownloadEvent::new(7, DownloadPhase::Progress).with_progress(
+            Some(50),
+            Some(512),
+            Some(1024),
+        );
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["phase"], "progress");
+        assert_eq!(
+            (json["progress"].as_u64(), json["bytes"].as_u64()),
+            (Some(50), Some(512))
+        );
+        assert_eq!(json["total"], 1024);
+
+        let json =
+            serde_json::to_value(DownloadEvent::new(7, DownloadPhase::Failed).with_error("boom"))
+                .unwrap();
+        assert_eq!(json["error"], "boom");
+    }
+}
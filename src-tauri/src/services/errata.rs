@@ -7,7 +7,7 @@
 //! (`process_errata`).
 
 use crate::models::{DownloadedFile, ErrataChange, Resource, WeekIdentifier};
-use crate::services::FileRetentionService;
+use crate::services::{resolve_archive_root, FileRetentionService};
 use chrono::Utc;
 use std::path::PathBuf;
 use tauri::{AppHandle, Emitter, Manager};
@@ -25,14 +25,7 @@ pub fn detect_errata_changes(
     remote_resources
         .iter()
         .filter_map(|remote| {
-            // Find local file with same resource ID in the same week
-            local_files
-                .iter()
-                .find(|local| {
-                    local.resource_id == remote.id
-                        && local.week == remote.week()
-                        && !local.is_superseded
-                })
+            live_download(local_files, remote)
                 .filter(|local| local.downloaded_at < remote.created_at)
                 .map(|local| ErrataChange {
                     resource_id: remote.id,
@@ -43,6 +36,24 @@ pub fn detect_errata_changes(
         .collect()
 }
 
+/// The registry entry for `remote`'s current local copy: same resource id,
+/// same week, not superseded.
+pub fn live_download<'a>(
+    local_files: &'a [DownloadedFile],
+    remote: &Resource,
+) -> Option<&'a DownloadedFile> {
+    local_files.iter().find(|local| {
+        local.resource_id == remote.id && local.week == remote.week() && !local.is_superseded
+    })
+}
+
+/// Per-resource [`detect_errata_changes`]: whether the local copy of
+/// `remote` was downloaded before the server's version was published.
+/// `false` when there's no local copy.
+pub fn is_resource_stale(local_files: &[DownloadedFile], remote: &Resource) -> bool {
+    live_download(local_files, remote).is_some_and(|local| local.downloaded_at < remote.created_at)
+}
+
 /// Find resources that are new (not yet downloaded)
 pub fn find_new_resources(
     local_files: &[DownloadedFile],
@@ -157,6 +168,39 @@ fn persist_registry(app: &AppHandle, registry: &[DownloadedFile]) {
     }
 }
 
+/// Add `entries` (files found on disk by a manifest rebuild) to the registry
+/// where it has no record for that resource and week yet — an existing
+/// record is the better source. Persists and returns how many were added.
+pub fn import_downloaded_files(app: &AppHandle, entries: Vec<DownloadedFile>) -> usize {
+    let state = app.state::<crate::commands::AppState>();
+    let (added, snapshot) = {
+        let mut registry = match state.downloaded_files.write() {
+            Ok(registry) => registry,
+            Err(e) => {
+                tracing::error!("Registry: failed to write downloaded_files: {}", e);
+                return 0;
+            }
+        };
+        let before = registry.len();
+        for entry in entries {
+            let known = registry
+                .iter()
+                .any(|f| f.resource_id == entry.resource_id && f.week == entry.week);
+            if !known {
+                registry.push(entry);
+            }
+        }
+        let added = registry.len() - before;
+        if added == 0 {
+            return 0;
+        }
+        persist_registry(app, &registry);
+        (added, registry.clone())
+    };
+    refresh_superseded_status(app, &snapshot);
+    added
+}
+
 /// Producer (adr-0007 step 2): record a successfully downloaded file into the
 /// registry and persist it. Called by the queue worker on the `Ok(...)` branch
 /// of a download. Fully synchronous (no `.await`): the `std::sync` write guard
@@ -236,9 +280,12 @@ pub async fn process_errata(app: &AppHandle, remote: &[Resource]) {
         return;
     }
 
-    let (work_dir, auto_categories) = match state.config.read() {
+    let (work_dir, archive_directory, auto_categories) = match state.config.read() {
         Ok(config) => (
             config.work_directory.clone(),
+            config
+                .effective_archive_directory()
+                .map(std::path::Path::to_path_buf),
             config.auto_download_categories.clone(),
         ),
         Err(e) => {
@@ -255,7 +302,8 @@ pub async fn process_errata(app: &AppHandle, remote: &[Resource]) {
     tracing::info!("Errata: {} change(s) detected, reconciling", changes.len());
 
     // Archive each stale file and collect the resources to re-download.
-    let service = FileRetentionService::new(work_dir);
+    let archive_root = resolve_archive_root(&work_dir, archive_directory.as_deref());
+    let service = FileRetentionService::new(work_dir, archive_root);
     let mut to_redownload: Vec<Resource> = Vec::new();
     for change in &changes {
         match service.archive_superseded(&change.old_file.local_path, &change.old_file.week) {
@@ -308,6 +356,12 @@ pub async fn process_errata(app: &AppHandle, remote: &[Resource]) {
         ) {
             tracing::error!("Errata: failed to emit errata-detected: {}", e);
         }
+        let titles: Vec<String> = changes
+            .iter()
+            .filter(|change| marked_ids.contains(&change.resource_id))
+            .map(|change| change.new_resource.title.clone())
+            .collect();
+        crate::services::notifications::notify_errata(app, &titles);
     }
 }
 
@@ -660,4 +714,19 @@ mod tests {
         assert!(marked.is_empty());
         assert!(!registry[0].is_superseded, "unrelated entry must stay live");
     }
+
+    #[test]
+    fn test_is_resource_stale_matches_batch_detection() {
+        let downloaded = Utc.with_ymd_and_hms(2026, 1, 19, 10, 0, 0).unwrap();
+        let updated = Utc.with_ymd_and_hms(2026, 1, 19, 14, 0, 0).unwrap();
+        let week = WeekIdentifier::from_datetime(downloaded);
+        let mut local = vec![create_downloaded_file(1, week, downloaded)];
+
+        assert!(is_resource_stale(&local, &create_resource(1, updated)));
+        assert!(!is_resource_stale(&local, &create_resource(1, downloaded)));
+        assert!(!is_resource_stale(&local, &create_resource(2, updated)));
+
+        local[0].is_superseded = true;
+        assert!(!is_resource_stale(&local, &create_resource(1, updated)));
+    }
 }
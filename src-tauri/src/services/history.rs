@@ -0,0 +1,150 @@
+//! Download history
+//!
+//! Append-only audit trail of completed downloads ("when did we download the
+//! March retreat video?"), persisted in its own `history.json` store so it
+//! survives a `cache.json` wipe. Recorded by the queue worker, read back by
+//! `commands::get_download_history`.
+
+use crate::models::DownloadHistoryEntry;
+use tauri::{AppHandle, Manager};
+
+/// Store file holding the history, separate from `settings.json`/`cache.json`.
+pub const HISTORY_STORE: &str = "history.json";
+
+/// Key of the entries array inside [`HISTORY_STORE`].
+const HISTORY_KEY: &str = "entries";
+
+/// Upper bound on stored entries; the oldest are dropped first. A few years
+/// of weekly material at a typical ~10 files per week.
+pub const MAX_HISTORY_ENTRIES: usize = 1000;
+
+/// Append `entry` (history is kept oldest-first) and drop the oldest entries
+/// beyond `cap`.
+pub fn push_capped(
+    history: &mut Vec<DownloadHistoryEntry>,
+    entry: DownloadHistoryEntry,
+    cap: usize,
+) {
+    history.push(entry);
+    if history.len() > cap {
+        let excess = history.len() - cap;
+        history.drain(..excess);
+    }
+}
+
+/// Newest-first view of `history`, truncated to `limit` when given.
+pub fn newest_first(
+    history: &[DownloadHistoryEntry],
+    limit: Option<usize>,
+) -> Vec<DownloadHistoryEntry> {
+    history
+        .iter()
+        .rev()
+        .take(limit.unwrap_or(usize::MAX))
+        .cloned()
+        .collect()
+}
+
+/// Read the persisted history for `AppState::download_history` at setup.
+/// Missing or malformed → empty, never a startup error.
+pub fn load_history(app: &AppHandle) -> Vec<DownloadHistoryEntry> {
+    use tauri_plugin_store::StoreExt;
+    let store = match app.store(HISTORY_STORE) {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::error!("History: failed to access store: {}", e);
+            return Vec::new();
+        }
+    };
+    match store.get(HISTORY_KEY) {
+        Some(json) => serde_json::from_value(json).unwrap_or_else(|e| {
+            tracing::warn!(
+                "History: failed to parse stored entries, starting empty: {}",
+                e
+            );
+            Vec::new()
+        }),
+        None => Vec::new(),
+    }
+}
+
+/// Append a completed download and persist the capped history. Like
+/// `errata::record_downloaded_file`, the write guard is held across the
+/// persist so parallel workers can't overwrite each other's entries.
+pub fn record_download(app: &AppHandle, entry: DownloadHistoryEntry) {
+    use tauri_plugin_store::StoreExt;
+    let state = app.state::<crate::commands::AppState>();
+    let mut history = match state.download_history.write() {
+        Ok(history) => history,
+        Err(e) => {
+            tracing::error!("History: failed to write download_history: {}", e);
+            return;
+        }
+    };
+    push_capped(&mut history, entry, MAX_HISTORY_ENTRIES);
+
+    let store = match app.store(HISTORY_STORE) {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::error!("History: failed to access store: {}", e);
+            return;
+        }
+    };
+    match serde_json::to_value(&*history) {
+        Ok(json) => {
+            store.set(HISTORY_KEY, json);
+            if let Err(e) = store.save() {
+                tracing::error!("History: failed to save entries: {}", e);
+            }
+        }
+        Err(e) => tracing::error!("History: failed to serialize entries: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::WeekIdentifier;
+    use chrono::Utc;
+
+    fn entry(resource_id: i64) -> DownloadHistoryEntry {
+        DownloadHistoryEntry {
+            resource_id,
+            title: format!("Resource {resource_id}"),
+            week: WeekIdentifier::new(2026, 4),
+            bytes: Some(1024),
+            duration_secs: 1.5,
+            completed_at: Utc::now(),
+            hash: "abc".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_push_capped_drops_oldest() {
+        let mut history = Vec::new();
+        for id in 1..=5 {
+            push_capped(&mut history, entry(id), 3);
+        }
+        let ids: Vec<i64> = history.iter().map(|e| e.resource_id).collect();
+        assert_eq!(ids, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn test_newest_first_with_and_without_limit() {
+        let history: Vec<_> = (1..=4).map(entry).collect();
+
+        let all: Vec<i64> = newest_first(&history, None)
+            .iter()
+            .map(|e| e.resource_id)
+            .collect();
+        assert_eq!(all, vec![4, 3, 2, 1]);
+
+        let limited: Vec<i64> = newest_first(&history, Some(2))
+            .iter()
+            .map(|e| e.resource_id)
+            .collect();
+        assert_eq!(limited, vec![4, 3]);
+
+        assert!(newest_first(&history, Some(0)).is_empty());
+    }
+}
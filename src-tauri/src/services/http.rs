@@ -0,0 +1,271 @@
+//! Shared HTTP client construction
+//!
+//! Polling, HEAD size probes and thumbnails go through the
+//! `reqwest::Client` held in `AppState::shared_http_client`; downloads go
+//! through `AppState::download_http_client`, built the same way minus the
+//! total timeout. Both are configured here once.
+//!
+//! Also home to the `Retry-After` handling shared by polling, downloads and
+//! size probes: [`retry_after`] reads the wait off a 429/503 and
+//! [`emit_rate_limited`] tells the UI about it.
+
+use crate::constants::{
+    HTTP_CONNECT_TIMEOUT_SECS, HTTP_MAX_REDIRECTS, HTTP_READ_TIMEOUT_SECS, MAX_RETRY_AFTER_SECS,
+    USER_AGENT,
+};
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER};
+use reqwest::StatusCode;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+/// Emitted whenever a request is throttled with a `Retry-After`.
+pub const RATE_LIMITED_EVENT: &str = "rate-limited";
+
+/// Build the app-wide client, surfacing the builder error to the caller.
+/// `proxy_url` (already validated by `AppConfig::validate`) routes every
+/// request through `reqwest::Proxy::all`; `None` connects directly.
+///
+/// `request_timeout` caps each request as a whole, body included, so a dead
+/// server can't hang a poll (`AppConfig::request_timeout_secs`). Pass `None`
+/// for the download client: a total timeout would abort multi-GB video
+/// downloads on slow links, so a stalled stream is caught by `read_timeout`
+/// alone, which resets on every chunk.
+///
+/// Redirects are followed up to [`HTTP_MAX_REDIRECTS`] hops; callers that
+/// care where a request ended up read `Response::url()`.
+///
+/// `api_headers` (`AppConfig::api_headers`) go on every request; reqwest
+/// drops `Authorization` when a redirect leaves the API host.
+pub fn try_build_http_client(
+    proxy_url: Option<&str>,
+    api_headers: &HashMap<String, String>,
+    request_timeout: Option<Duration>,
+) -> Result<reqwest::Client, reqwest::Error> {
+    let mut builder = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .default_headers(header_map(api_headers))
+        .connect_timeout(Duration::from_secs(HTTP_CONNECT_TIMEOUT_SECS))
+        .read_timeout(Duration::from_secs(HTTP_READ_TIMEOUT_SECS))
+        .redirect(reqwest::redirect::Policy::limited(HTTP_MAX_REDIRECTS));
+    if let Some(timeout) = request_timeout {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(proxy) = proxy_url {
+        builder = builder.proxy(reqwest::Proxy::all(proxy)?);
+    }
+    builder.build()
+}
+
+/// Infallible wrapper around [`try_build_http_client`] for startup: a builder
+/// failure (bad proxy, TLS backend failing to initialize) is logged and
+/// degrades to reqwest's defaults rather than aborting the launch.
+pub fn build_http_client(
+    proxy_url: Option<&str>,
+    api_headers: &HashMap<String, String>,
+    request_timeout: Option<Duration>,
+) -> reqwest::Client {
+    try_build_http_client(proxy_url, api_headers, request_timeout).unwrap_or_else(|e| {
+        tracing::error!(
+            "HTTP: failed to build configured client, using defaults: {}",
+            e
+        );
+        reqwest::Client::new()
+    })
+}
+
+/// `api_headers` as a `HeaderMap`, values marked sensitive so they never
+/// show up in reqwest's debug output. Entries `AppConfig::validate` would
+/// reject are skipped, logging the name only.
+fn header_map(api_headers: &HashMap<String, String>) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in api_headers {
+        match (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            (Ok(name), Ok(mut value)) => {
+                value.set_sensitive(true);
+                map.insert(name, value);
+            }
+            _ => tracing::warn!("HTTP: skipping invalid API header {:?}", name),
+        }
+    }
+    map
+}
+
+/// The wait a 429 or 503 asks for in its `Retry-After` header, either
+/// delay-seconds or an HTTP-date, capped at [`MAX_RETRY_AFTER_SECS`]. A date
+/// already past means no wait. `None` for any other status, or when the
+/// header is missing or unreadable: the caller's own schedule applies then.
+pub fn retry_after(
+    status: StatusCode,
+    headers: &HeaderMap,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::SERVICE_UNAVAILABLE {
+        return None;
+    }
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    let secs = match value.parse::<u64>() {
+        Ok(secs) => secs,
+        Err(_) => {
+            let at = DateTime::parse_from_rfc2822(value).ok()?;
+            u64::try_from((at.with_timezone(&Utc) - now).num_seconds()).unwrap_or(0)
+        }
+    };
+    Some(Duration::from_secs(secs.min(MAX_RETRY_AFTER_SECS)))
+}
+
+/// Which request was throttled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitedSource {
+    Poll,
+    Download,
+    FileSize,
+}
+
+/// Payload of the `rate-limited` event.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RateLimited {
+    pub source: RateLimitedSource,
+    pub url: String,
+    /// The download's resource, for `download`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_id: Option<i64>,
+    /// How long the request is held back, in seconds.
+    pub wait_secs: u64,
+}
+
+/// Log the throttling and emit [`RATE_LIMITED_EVENT`].
+pub fn emit_rate_limited(app: &AppHandle, payload: RateLimited) {
+    tracing::warn!(
+        "HTTP: {:?} request to {} rate-limited, waiting {}s",
+        payload.source,
+        payload.url,
+        payload.wait_secs
+    );
+    if let Err(e) = app.emit(RATE_LIMITED_EVENT, &payload) {
+        tracing::error!("Failed to emit {}: {:?}", RATE_LIMITED_EVENT, e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_build_http_client_succeeds_with_defaults() {
+        assert!(try_build_http_client(None, &HashMap::new(), None).is_ok());
+        assert!(
+            try_build_http_client(None, &HashMap::new(), Some(Duration::from_secs(30))).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_try_build_http_client_accepts_supported_proxies() {
+        for proxy in ["http://proxy.local:3128", "socks5://127.0.0.1:1080"] {
+            assert!(
+                try_build_http_client(Some(proxy), &HashMap::new(), None).is_ok(),
+                "{proxy}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_user_agent_carries_crate_version() {
+        assert!(USER_AGENT.starts_with("church-helper-desktop/"));
+        assert!(USER_AGENT.ends_with(env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn test_header_map_marks_values_sensitive_and_skips_invalid() {
+        let headers = HashMap::from([
+            ("Authorization".to_string(), "Bearer secret".to_string()),
+            ("bad name".to_string(), "x".to_string()),
+        ]);
+        let map = header_map(&headers);
+        assert_eq!(map.len(), 1);
+        let value = map.get("authorization").unwrap();
+        assert!(value.is_sensitive());
+        assert!(!format!("{map:?}").contains("secret"));
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_aborts_a_hung_request() {
+        // Reads the request but stalls well past the timeout before answering.
+        let base = crate::services::mock_http::serve(1, |_, _| {
+            std::thread::sleep(Duration::from_secs(5));
+            crate::services::mock_http::Response::new("200 OK")
+        });
+
+        let client =
+            try_build_http_client(None, &HashMap::new(), Some(Duration::from_millis(300))).unwrap();
+        let err = client.get(format!("{base}/")).send().await.unwrap_err();
+        assert!(err.is_timeout());
+    }
+
+    fn retry_after_headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_retry_after_reads_seconds_and_http_dates() {
+        let now = DateTime::parse_from_rfc3339("2026-01-17T08:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let throttled = StatusCode::TOO_MANY_REQUESTS;
+
+        assert_eq!(
+            retry_after(throttled, &retry_after_headers("120"), now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            retry_after(
+                StatusCode::SERVICE_UNAVAILABLE,
+                &retry_after_headers("Sat, 17 Jan 2026 08:01:30 GMT"),
+                now
+            ),
+            Some(Duration::from_secs(90))
+        );
+        // A date in the past: retry right away.
+        assert_eq!(
+            retry_after(
+                throttled,
+                &retry_after_headers("Sat, 17 Jan 2026 07:00:00 GMT"),
+                now
+            ),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            retry_after(throttled, &retry_after_headers("86400"), now),
+            Some(Duration::from_secs(MAX_RETRY_AFTER_SECS))
+        );
+    }
+
+    #[test]
+    fn test_retry_after_ignores_other_statuses_and_bad_values() {
+        let now = Utc::now();
+        assert_eq!(
+            retry_after(StatusCode::BAD_GATEWAY, &retry_after_headers("5"), now),
+            None
+        );
+        assert_eq!(
+            retry_after(StatusCode::TOO_MANY_REQUESTS, &HeaderMap::new(), now),
+            None
+        );
+        assert_eq!(
+            retry_after(
+                StatusCode::TOO_MANY_REQUESTS,
+                &retry_after_headers("soon"),
+                now
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,259 @@
+//! Integrity verification of downloaded files
+//!
+//! Every completed download records its SHA-256 in the week manifest (see
+//! `manifest::record_file_hash`). A crash mid-write, a flaky USB drive or a
+//! sync client can still corrupt a file afterwards; re-hashing it against
+//! the recorded value catches that. Opt-in at startup
+//! (`AppConfig::verify_on_startup`): hashing a week of videos takes a while.
+
+use crate::commands::AppState;
+use crate::error::FileError;
+use crate::models::{latest_week, AppConfig, Resource};
+use crate::services::download::{calculate_file_hash, resource_dest_path};
+use crate::services::manifest::WeekManifest;
+use futures_util::StreamExt;
+use serde::Serialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Files hashed at the same time. Hashing is disk-bound, so more parallel
+/// readers mostly just thrash a spinning or USB drive.
+const VERIFY_CONCURRENCY: usize = 2;
+
+/// A downloaded file with the hash its manifest recorded for it.
+#[derive(Debug, Clone)]
+pub struct HashedFile {
+    pub resource: Resource,
+    pub path: PathBuf,
+    pub expected: String,
+}
+
+/// Payload of the `integrity-issue` event: a file whose content no longer
+/// matches the hash recorded when it was downloaded.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IntegrityIssue {
+    pub resource_id: i64,
+    pub path: PathBuf,
+    pub expected: String,
+    /// Hash of the file as found; `None` when it couldn't be read.
+    pub actual: Option<String>,
+}
+
+/// The downloaded files of `resources` that have a recorded hash. Files not
+/// on disk, or downloaded before hashes were recorded, are left out.
+pub fn hashed_files(resources: &[Resource], config: &AppConfig) -> Vec<HashedFile> {
+    resources
+        .iter()
+        .filter_map(|resource| {
+            let path = resource_dest_path(config, resource)?;
+            if !path.is_file() {
+                return None;
+            }
+            let name = path.file_name()?.to_str()?.to_string();
+            let expected = WeekManifest::load(path.parent()?)
+                .hashes
+                .get(&name)?
+                .clone();
+            Some(HashedFile {
+                resource: resource.clone(),
+                path,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// Re-hash `files`, at most [`VERIFY_CONCURRENCY`] at a time on blocking
+/// threads, returning the ones that don't match with their resource.
+pub async fn verify_files(files: Vec<HashedFile>) -> Vec<(Resource, IntegrityIssue)> {
+    futures_util::stream::iter(files)
+        .map(|file| async move {
+            let hash_path = file.path.clone();
+            let actual =
+                match tokio::task::spawn_blocking(move || calculate_file_hash(&hash_path)).await {
+                    Ok(Ok(hash)) => Some(hash),
+                    Ok(Err(e)) => {
+                        tracing::warn!("Integrity: failed to read {:?}: {}", file.path, e);
+                        None
+                    }
+                    Err(e) => {
+                        tracing::error!("Integrity: hashing task failed: {}", e);
+                        return None;
+                    }
+                };
+            if actual.as_deref() == Some(file.expected.as_str()) {
+                return None;
+            }
+            let issue = IntegrityIssue {
+                resource_id: file.resource.id,
+                path: file.path,
+                expected: file.expected,
+                actual,
+            };
+            Some((file.resource, issue))
+        })
+        .buffer_unordered(VERIFY_CONCURRENCY)
+        .filter_map(|result| async move { result })
+        .collect()
+        .await
+}
+
+/// Startup pass for `verify_on_startup`: re-hash the current (latest)
+/// week's files, and for each mismatch emit `integrity-issue`, move the bad
+/// copy to the trash and queue the resource again. A file that couldn't be
+/// read is reported the same way but left alone (see [`discard_bad_copy`]).
+/// Returns how many files were bad or unreadable.
+pub async fn verify_current_week(app: &AppHandle) -> usize {
+    let state = app.state::<AppState>();
+    let (config, resources) = match (state.config.read(), state.resources.read()) {
+        (Ok(config), Ok(resources)) => (config.clone(), resources.clone()),
+        _ => {
+            tracing::error!("Integrity: state lock poisoned, skipping verification");
+            return 0;
+        }
+    };
+
+    let Some(current) = latest_week(&resources) else {
+        return 0;
+    };
+    let week_resources: Vec<Resource> = resources
+        .into_iter()
+        .filter(|resource| resource.week() == current)
+        .collect();
+    let files = hashed_files(&week_resources, &config);
+    tracing::info!(
+        "Integrity: verifying {} files of the current week",
+        files.len()
+    );
+    let issues = verify_files(files).await;
+
+    for (resource, issue) in &issues {
+        let _ = app.emit("integrity-issue", issue);
+        match discard_bad_copy(issue) {
+            Ok(true) => {
+                state
+                    .download_queue
+                    .add_task(app.clone(), resource.clone())
+                    .await;
+            }
+            Ok(false) => {}
+            Err(e) => tracing::error!("Integrity: {}", e),
+        }
+    }
+    issues.len()
+}
+
+/// Move the file behind `issue` to the trash so it can be downloaded again;
+/// `Ok(true)` once it's gone. A file whose hash couldn't be computed
+/// (`actual` is `None`) is only logged and kept (`Ok(false)`): a transient
+/// read error must not cost a good copy.
+fn discard_bad_copy(issue: &IntegrityIssue) -> Result<bool, FileError> {
+    if issue.actual.is_none() {
+        tracing::error!(
+            "Integrity: couldn't read {:?} to verify it, leaving it in place",
+            issue.path
+        );
+        return Ok(false);
+    }
+    tracing::warn!(
+        "Integrity: {:?} doesn't match its recorded hash, downloading it again",
+        issue.path
+    );
+    trash::delete(&issue.path).map_err(|source| FileError::TrashFailed {
+        path: issue.path.clone(),
+        source,
+    })?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::manifest::record_file_hash;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    fn resource(id: i64, url: &str) -> Resource {
+        Resource {
+            id,
+            category: "video".to_string(),
+            title: format!("Resource {id}"),
+            description: None,
+            download_url: url.to_string(),
+            thumbnail_url: None,
+            file_type: None,
+            checksum: None,
+            is_active: true,
+            created_at: chrono::Utc.with_ymd_and_hms(2026, 1, 19, 12, 0, 0).unwrap(),
+            week_date: None,
+            optimized_video_url: None,
+            optimized_videos: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_files_reports_only_mismatches() {
+        let tmp = TempDir::new().unwrap();
+        let config = AppConfig {
+            work_directory: Some(tmp.path().to_path_buf()),
+            ..Default::default()
+        };
+        let good = resource(1, "https://example.com/good.pdf");
+        let bad = resource(2, "https://example.com/bad.pdf");
+        let unhashed = resource(3, "https://example.com/old.pdf");
+        let missing = resource(4, "https://example.com/missing.pdf");
+
+        for (r, content) in [(&good, "good"), (&bad, "bad"), (&unhashed, "old")] {
+            let path = resource_dest_path(&config, r).unwrap();
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, content).unwrap();
+        }
+        for r in [&good, &bad] {
+            let path = resource_dest_path(&config, r).unwrap();
+            record_file_hash(&path, &calculate_file_hash(&path).unwrap());
+        }
+        let bad_path = resource_dest_path(&config, &bad).unwrap();
+        std::fs::write(&bad_path, "corrupted").unwrap();
+
+        let files = hashed_files(&[good, bad, unhashed, missing], &config);
+        assert_eq!(files.len(), 2);
+
+        let issues = verify_files(files).await;
+        assert_eq!(issues.len(), 1);
+        let (resource, issue) = &issues[0];
+        assert_eq!(resource.id, 2);
+        assert_eq!(issue.path, bad_path);
+        assert_eq!(
+            issue.actual.as_deref(),
+            Some(calculate_file_hash(&bad_path).unwrap().as_str())
+        );
+    }
+
+    /// A file that can't be read is reported with no actual hash and is not
+    /// discarded.
+    #[tokio::test]
+    async fn test_unreadable_file_is_reported_and_kept() {
+        let tmp = TempDir::new().unwrap();
+        let config = AppConfig {
+            work_directory: Some(tmp.path().to_path_buf()),
+            ..Default::default()
+        };
+        let r = resource(1, "https://example.com/video.mp4");
+        let path = resource_dest_path(&config, &r).unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "video").unwrap();
+        record_file_hash(&path, &calculate_file_hash(&path).unwrap());
+        let files = hashed_files(&[r], &config);
+
+        // A directory in place of the file: opening or reading it fails.
+        std::fs::remove_file(&path).unwrap();
+        std::fs::create_dir(&path).unwrap();
+
+        let issues = verify_files(files).await;
+        assert_eq!(issues.len(), 1);
+        let (_, issue) = &issues[0];
+        assert_eq!(issue.actual, None);
+        assert!(!discard_bad_copy(issue).unwrap());
+        assert!(path.exists());
+    }
+}
@@ -0,0 +1,164 @@
+//! Logging setup
+//!
+//! Console output for development plus a daily-rotated file in the app log
+//! directory, which is the only place a packaged build's logs can be found.
+//! The level follows `AppConfig::log_level` and can change at runtime;
+//! `RUST_LOG`, when set, overrides it.
+//!
+//! The subscriber is installed at the very start of `run()`, before the
+//! log directory is known; the file layer writes nowhere until
+//! [`attach_log_file`] is called from `setup`.
+
+use crate::models::LogLevel;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tracing_appender::non_blocking::{NonBlocking, WorkerGuard};
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::fmt::writer::{MakeWriter, OptionalWriter};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+/// Log files are named `church-helper.YYYY-MM-DD.log`.
+const LOG_FILE_PREFIX: &str = "church-helper";
+const LOG_FILE_SUFFIX: &str = "log";
+
+/// Rotated files kept before the oldest is deleted: about a week.
+const MAX_LOG_FILES: usize = 7;
+
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+static FILE_WRITER: OnceLock<NonBlocking> = OnceLock::new();
+/// Keeps the background writer thread alive for the whole process.
+static FILE_WRITER_GUARD: OnceLock<WorkerGuard> = OnceLock::new();
+static LOG_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Filter directive for `level`. Debug and trace are scoped to this crate:
+/// reqwest/hyper at debug would bury the lines that matter in a report.
+pub fn filter_directive(level: &LogLevel) -> String {
+    match level {
+        LogLevel::Error => "error".to_string(),
+        LogLevel::Warn => "warn".to_string(),
+        LogLevel::Info => "info".to_string(),
+        LogLevel::Debug => "info,church_helper_desktop_lib=debug".to_string(),
+        LogLevel::Trace => "info,church_helper_desktop_lib=trace".to_string(),
+    }
+}
+
+fn build_filter(level: &LogLevel) -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(filter_directive(level)))
+}
+
+/// `MakeWriter` for the file layer: a no-op until [`attach_log_file`] runs.
+struct FileLogWriter;
+
+impl<'a> MakeWriter<'a> for FileLogWriter {
+    type Writer = OptionalWriter<NonBlocking>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        match FILE_WRITER.get() {
+            Some(writer) => OptionalWriter::some(writer.clone()),
+            None => OptionalWriter::none(),
+        }
+    }
+}
+
+/// Install the global subscriber at the default level. Called once, first
+/// thing in `run()`.
+pub fn init_logging() {
+    let (filter, handle) = reload::Layer::new(build_filter(&LogLevel::default()));
+    let _ = FILTER_HANDLE.set(handle);
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(
+            tracing_subscriber::fmt::layer()
+                .with_ansi(false)
+                .with_writer(FileLogWriter),
+        )
+        .init();
+}
+
+/// Start writing to a daily-rotated file in `log_dir`. Called from `setup`
+/// once the app's log directory is resolved; a failure leaves console-only
+/// logging in place.
+pub fn attach_log_file(log_dir: &Path) {
+    let appender = std::fs::create_dir_all(log_dir)
+        .map_err(|e| e.to_string())
+        .and_then(|()| {
+            RollingFileAppender::builder()
+                .rotation(Rotation::DAILY)
+                .filename_prefix(LOG_FILE_PREFIX)
+                .filename_suffix(LOG_FILE_SUFFIX)
+                .max_log_files(MAX_LOG_FILES)
+                .build(log_dir)
+                .map_err(|e| e.to_string())
+        });
+    match appender {
+        Ok(appender) => {
+            let (writer, guard) = tracing_appender::non_blocking(appender);
+            if FILE_WRITER.set(writer).is_ok() {
+                let _ = FILE_WRITER_GUARD.set(guard);
+                let _ = LOG_DIR.set(log_dir.to_path_buf());
+                tracing::info!("Logging to {:?}", current_log_file());
+            }
+        }
+        Err(e) => tracing::error!("Failed to set up file logging in {:?}: {}", log_dir, e),
+    }
+}
+
+/// Switch to `level` (from `AppConfig::log_level`) without restarting.
+pub fn apply_log_level(level: &LogLevel) {
+    let Some(handle) = FILTER_HANDLE.get() else {
+        return;
+    };
+    if let Err(e) = handle.reload(build_filter(level)) {
+        tracing::error!("Failed to change log level: {}", e);
+    }
+}
+
+/// Name of the file written on `date` (rotation follows UTC days).
+pub fn log_file_name(date: chrono::NaiveDate) -> String {
+    format!(
+        "{}.{}.{}",
+        LOG_FILE_PREFIX,
+        date.format("%Y-%m-%d"),
+        LOG_FILE_SUFFIX
+    )
+}
+
+/// File currently being written, once file logging is attached.
+pub fn current_log_file() -> Option<PathBuf> {
+    LOG_DIR
+        .get()
+        .map(|dir| dir.join(log_file_name(chrono::Utc::now().date_naive())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_directive_scopes_verbose_levels_to_the_app() {
+        assert_eq!(filter_directive(&LogLevel::Info), "info");
+        assert_eq!(filter_directive(&LogLevel::Warn), "warn");
+        assert_eq!(
+            filter_directive(&LogLevel::Debug),
+            "info,church_helper_desktop_lib=debug"
+        );
+        for level in [
+            LogLevel::Error,
+            LogLevel::Warn,
+            LogLevel::Info,
+            LogLevel::Debug,
+            LogLevel::Trace,
+        ] {
+            assert!(EnvFilter::try_new(filter_directive(&level)).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_log_file_name() {
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 7).unwrap();
+        assert_eq!(log_file_name(date), "church-helper.2026-03-07.log");
+    }
+}
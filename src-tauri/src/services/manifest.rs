@@ -0,0 +1,1052 @@
+//! Per-week download manifest
+//!
+//! Every week folder carries a small `.manifest.json` recording which resource
+//! owns which filename. Two resources of the same week whose URLs end in the
+//! same name (`video.mp4` from two different hosts) would otherwise resolve
+//! to one path: the second download would overwrite the first, and the
+//! status checks would report the second resource as already downloaded.
+//!
+//! It also remembers which download URL each file was saved from: the name
+//! can come from the URL a redirect ended on (a signed CDN link), which a
+//! later lookup starting from the API's URL couldn't re-derive.
+
+use crate::models::{DownloadedFile, Resource, WeekIdentifier};
+use crate::services::download::is_partial_download_name;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Manifest file name inside each week folder. Dot-prefixed so it stays out
+/// of the way in file managers.
+pub const MANIFEST_FILE_NAME: &str = ".manifest.json";
+
+/// Highest ` (n)` suffix tried before giving up on finding a free name.
+const MAX_COLLISION_SUFFIX: u32 = 99;
+
+/// Serializes the load → claim → save cycle across parallel queue workers, so
+/// two downloads starting together can't both claim the same free name.
+static MANIFEST_LOCK: Mutex<()> = Mutex::new(());
+
+/// Filename → resource id for one week folder, plus download URL →
+/// filename for the names that were actually used, filename → SHA-256 of
+/// the completed file, filename → how its download went, and filename →
+/// whether its download is still in progress.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct WeekManifest {
+    pub files: BTreeMap<String, i64>,
+    pub sources: BTreeMap<String, String>,
+    pub hashes: BTreeMap<String, String>,
+    pub transfers: BTreeMap<String, TransferRecord>,
+    pub downloads: BTreeMap<String, DownloadRecord>,
+}
+
+/// State of one file's download, so an interrupted download can be found
+/// and resumed after a restart (see [`reconcile_downloads`]).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct DownloadRecord {
+    pub status: DownloadStatus,
+    /// Full size announced by the server, when it sent one.
+    #[serde(default)]
+    pub expected_bytes: Option<u64>,
+    /// `ETag`/`Last-Modified` the `.part` is being filled against.
+    #[serde(default)]
+    pub validator: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadStatus {
+    #[default]
+    InProgress,
+    Done,
+}
+
+/// Bytes fetched and time spent by the download that completed a file. A
+/// resumed download only counts its last session.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+pub struct TransferRecord {
+    pub bytes: u64,
+    pub duration_secs: f64,
+}
+
+impl WeekManifest {
+    /// Read the manifest of `week_dir`. Missing or unreadable → empty: the
+    /// manifest only disambiguates collisions, it must never block a download.
+    pub fn load(week_dir: &Path) -> Self {
+        let path = week_dir.join(MANIFEST_FILE_NAME);
+        match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+                tracing::warn!("Manifest: ignoring unparseable {:?}: {}", path, e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, week_dir: &Path) -> std::io::Result<()> {
+        let json = serde_json::to_vec_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(week_dir.join(MANIFEST_FILE_NAME), json)
+    }
+
+    /// Resource id that claimed `filename`, if any.
+    pub fn owner(&self, filename: &str) -> Option<i64> {
+        self.files.get(filename).copied()
+    }
+}
+
+/// `filename` with a ` (n)` suffix before its extension:
+/// `video.mp4` → `video (2).mp4`, `README` → `README (2)`.
+pub fn suffixed_filename(filename: &str, n: u32) -> String {
+    match filename.rfind('.') {
+        Some(dot) if dot > 0 => format!("{} ({}){}", &filename[..dot], n, &filename[dot..]),
+        _ => format!("{filename} ({n})"),
+    }
+}
+
+/// `filename` followed by its ` (2)`, ` (3)`, … variants.
+fn candidate_names(filename: &str) -> impl Iterator<Item = String> + '_ {
+    (1..=MAX_COLLISION_SUFFIX).map(move |n| {
+        if n == 1 {
+            filename.to_string()
+        } else {
+            suffixed_filename(filename, n)
+        }
+    })
+}
+
+/// Pure resolution against an already loaded manifest: the name recorded for
+/// `source_url`, else the name this resource already claimed, else the first
+/// name nobody claimed. An unclaimed name that exists on disk (a file saved
+/// before manifests existed) is taken as this resource's own, matching the
+/// pre-manifest behaviour.
+fn pick_filename(
+    manifest: &WeekManifest,
+    filename: &str,
+    resource_id: i64,
+    source_url: &str,
+) -> String {
+    if let Some(recorded) = manifest.sources.get(source_url) {
+        match manifest.owner(recorded) {
+            Some(owner) if owner != resource_id => {}
+            _ => return recorded.clone(),
+        }
+    }
+    if let Some(own) =
+        candidate_names(filename).find(|name| manifest.owner(name) == Some(resource_id))
+    {
+        return own;
+    }
+    candidate_names(filename)
+        .find(|name| manifest.owner(name).is_none())
+        .unwrap_or_else(|| {
+            tracing::warn!(
+                "Manifest: no free name for {} after {} attempts, reusing it",
+                filename,
+                MAX_COLLISION_SUFFIX
+            );
+            filename.to_string()
+        })
+}
+
+/// Path `resource_id`'s file downloaded from `source_url` has (or would
+/// have) inside `dir`: the name recorded for that URL, else `filename`,
+/// stepping to ` (2)`-style names when the plain one belongs to a different
+/// resource per the week manifest. Read-only: see [`claim_dest_path`] for
+/// the variant that records the choice.
+pub fn unique_dest_path(dir: &Path, filename: &str, resource_id: i64, source_url: &str) -> PathBuf {
+    let manifest = WeekManifest::load(dir);
+    dir.join(pick_filename(&manifest, filename, resource_id, source_url))
+}
+
+/// Like [`unique_dest_path`], but also records the chosen name in the
+/// manifest so later resolutions (and parallel downloads) see it as taken.
+/// Called when a download starts; `dir` must already exist. A failed manifest
+/// write is logged and the path still returned.
+pub fn claim_dest_path(dir: &Path, filename: &str, resource_id: i64, source_url: &str) -> PathBuf {
+    let _guard = MANIFEST_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut manifest = WeekManifest::load(dir);
+    let name = pick_filename(&manifest, filename, resource_id, source_url);
+    let newly_owned = manifest.owner(&name) != Some(resource_id);
+    let newly_sourced = manifest.sources.get(source_url) != Some(&name);
+    if newly_owned || newly_sourced {
+        manifest.files.insert(name.clone(), resource_id);
+        manifest
+            .sources
+            .insert(source_url.to_string(), name.clone());
+        if let Err(e) = manifest.save(dir) {
+            tracing::warn!("Manifest: failed to save in {:?}: {}", dir, e);
+        }
+    }
+    dir.join(name)
+}
+
+/// One file in a week folder, as listed by [`list_week_files`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeekFile {
+    pub filename: String,
+    pub size: u64,
+    pub modified: Option<chrono::DateTime<chrono::Utc>>,
+    /// Resource that claimed this name in the manifest; `None` for a file
+    /// the app didn't download (dropped in by the user, or saved before
+    /// manifests existed).
+    pub resource_id: Option<i64>,
+}
+
+/// Regular files in `week_dir`, by name, with their manifest owner. Hidden
+/// files (the manifest itself) and in-progress `.part`/`.part.meta` files
+/// are skipped. A missing or unreadable folder lists as empty.
+pub fn list_week_files(week_dir: &Path) -> Vec<WeekFile> {
+    let Ok(entries) = std::fs::read_dir(week_dir) else {
+        return Vec::new();
+    };
+    let manifest = WeekManifest::load(week_dir);
+    let mut files: Vec<WeekFile> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let filename = entry.file_name().to_str()?.to_string();
+            if filename.starts_with('.') || is_partial_download_name(&filename) {
+                return None;
+            }
+            let metadata = entry.metadata().ok().filter(|m| m.is_file())?;
+            Some(WeekFile {
+                resource_id: manifest.owner(&filename),
+                size: metadata.len(),
+                modified: metadata.modified().ok().map(chrono::DateTime::from),
+                filename,
+            })
+        })
+        .collect();
+    files.sort_by(|a, b| a.filename.cmp(&b.filename));
+    files
+}
+
+/// Record the SHA-256 of the completed file at `path` in its folder's
+/// manifest. Best-effort, like the claims: a failed write is logged.
+pub fn record_file_hash(path: &Path, hash: &str) {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return;
+    };
+    let _guard = MANIFEST_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut manifest = WeekManifest::load(dir);
+    if manifest.hashes.get(name).map(String::as_str) == Some(hash) {
+        return;
+    }
+    manifest.hashes.insert(name.to_string(), hash.to_string());
+    if let Err(e) = manifest.save(dir) {
+        tracing::warn!("Manifest: failed to save in {:?}: {}", dir, e);
+    }
+}
+
+/// Record how the download of the completed file at `path` went in its
+/// folder's manifest. Best-effort, like [`record_file_hash`].
+pub fn record_transfer(path: &Path, transfer: TransferRecord) {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return;
+    };
+    let _guard = MANIFEST_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut manifest = WeekManifest::load(dir);
+    manifest.transfers.insert(name.to_string(), transfer);
+    if let Err(e) = manifest.save(dir) {
+        tracing::warn!("Manifest: failed to save in {:?}: {}", dir, e);
+    }
+}
+
+/// Load, update and save the manifest of `path`'s folder for the file at
+/// `path`, under the manifest lock. Best-effort: a failed write is logged.
+fn update_download_record(path: &Path, update: impl FnOnce(&mut WeekManifest, &str)) {
+    let (Some(dir), Some(name)) = (path.parent(), path.file_name().and_then(|n| n.to_str())) else {
+        return;
+    };
+    let _guard = MANIFEST_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut manifest = WeekManifest::load(dir);
+    let before = manifest.downloads.clone();
+    update(&mut manifest, name);
+    if manifest.downloads != before {
+        if let Err(e) = manifest.save(dir) {
+            tracing::warn!("Manifest: failed to save in {:?}: {}", dir, e);
+        }
+    }
+}
+
+/// Record that the file at `path` is being downloaded (its `.part` is being
+/// written), with the expected size and validator when known.
+pub fn record_download_started(
+    path: &Path,
+    expected_bytes: Option<u64>,
+    validator: Option<String>,
+) {
+    update_download_record(path, |manifest, name| {
+        manifest.downloads.insert(
+            name.to_string(),
+            DownloadRecord {
+                status: DownloadStatus::InProgress,
+                expected_bytes,
+                validator,
+            },
+        );
+    });
+}
+
+/// Record that the download of the file at `path` completed.
+pub fn record_download_done(path: &Path) {
+    update_download_record(path, |manifest, name| {
+        let record = manifest.downloads.entry(name.to_string()).or_default();
+        record.status = DownloadStatus::Done;
+    });
+}
+
+/// Forget the download state of the file at `path` (cancelled: its `.part`
+/// is gone).
+pub fn clear_download_record(path: &Path) {
+    update_download_record(path, |manifest, name| {
+        manifest.downloads.remove(name);
+    });
+}
+
+/// What [`reconcile_downloads`] found in one folder.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReconciledDownloads {
+    /// Resources whose interrupted download left a `.part` to resume.
+    pub resumable: Vec<i64>,
+    /// Completed files given the `done` record they were missing.
+    pub backfilled: usize,
+    /// `in_progress` records with neither a `.part` nor a file: dropped.
+    pub dropped: usize,
+}
+
+/// Bring `dir`'s download records in line with the disk at startup: an
+/// `in_progress` record whose `.part` (in place or staged) is still there
+/// is resumable; one whose file is complete, or a claimed file with no
+/// record at all, is marked `done`; anything else is dropped.
+pub fn reconcile_downloads(dir: &Path) -> ReconciledDownloads {
+    use crate::services::download::existing_part_path;
+
+    let _guard = MANIFEST_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let mut manifest = WeekManifest::load(dir);
+    let mut report = ReconciledDownloads::default();
+    let mut changed = false;
+
+    let in_progress: Vec<String> = manifest
+        .downloads
+        .iter()
+        .filter(|(_, record)| record.status == DownloadStatus::InProgress)
+        .map(|(name, _)| name.clone())
+        .collect();
+    for name in in_progress {
+        let path = dir.join(&name);
+        if existing_part_path(&path).is_some() {
+            if let Some(id) = manifest.owner(&name) {
+                report.resumable.push(id);
+            }
+        } else if path.is_file() {
+            if let Some(record) = manifest.downloads.get_mut(&name) {
+                record.status = DownloadStatus::Done;
+            }
+            report.backfilled += 1;
+            changed = true;
+        } else {
+            manifest.downloads.remove(&name);
+            report.dropped += 1;
+            changed = true;
+        }
+    }
+
+    let unrecorded: Vec<String> = manifest
+        .files
+        .keys()
+        .filter(|name| !manifest.downloads.contains_key(*name))
+        .filter(|name| {
+            let path = dir.join(name);
+            path.is_file() && existing_part_path(&path).is_none()
+        })
+        .cloned()
+        .collect();
+    for name in unrecorded {
+        manifest.downloads.insert(
+            name,
+            DownloadRecord {
+                status: DownloadStatus::Done,
+                ..Default::default()
+            },
+        );
+        report.backfilled += 1;
+        changed = true;
+    }
+
+    if changed {
+        if let Err(e) = manifest.save(dir) {
+            tracing::warn!("Manifest: failed to save in {:?}: {}", dir, e);
+        }
+    }
+    report
+}
+
+/// Download totals of one week folder, from its manifest's transfer records.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WeekStats {
+    /// Files with a recorded transfer (downloads that predate the records,
+    /// or files dropped in by hand, aren't counted).
+    pub file_count: usize,
+    pub total_bytes: u64,
+    pub total_download_secs: f64,
+    /// `total_bytes` over `total_download_secs`, in megabits per second;
+    /// 0 when no time was recorded.
+    pub avg_mbps: f64,
+}
+
+/// [`WeekStats`] of `week_dir`. Reads the manifest only: no network, no
+/// file walk. A missing folder or manifest gives all zeros.
+pub fn week_stats(week_dir: &Path) -> WeekStats {
+    stats_of(WeekManifest::load(week_dir).transfers.values())
+}
+
+/// [`WeekStats`] of just `files`, each looked up in the manifest of its own
+/// folder. For layouts where a week's files don't share a folder of their
+/// own (flat, by category, category destinations), and folders that hold
+/// other weeks too. Each manifest is read once; duplicates count once.
+pub fn week_stats_of_files(files: &[PathBuf]) -> WeekStats {
+    let mut by_dir: BTreeMap<&Path, std::collections::BTreeSet<&str>> = BTreeMap::new();
+    for file in files {
+        if let (Some(dir), Some(name)) = (file.parent(), file.file_name().and_then(|n| n.to_str()))
+        {
+            by_dir.entry(dir).or_default().insert(name);
+        }
+    }
+    let mut transfers = Vec::new();
+    for (dir, names) in by_dir {
+        let manifest = WeekManifest::load(dir);
+        transfers.extend(
+            names
+                .into_iter()
+                .filter_map(|name| manifest.transfers.get(name).cloned()),
+        );
+    }
+    stats_of(transfers.iter())
+}
+
+fn stats_of<'a>(transfers: impl Iterator<Item = &'a TransferRecord> + Clone) -> WeekStats {
+    let total_bytes: u64 = transfers.clone().map(|t| t.bytes).sum();
+    let total_download_secs: f64 = transfers.clone().map(|t| t.duration_secs).sum();
+    let avg_mbps = if total_download_secs > 0.0 {
+        total_bytes as f64 * 8.0 / 1_000_000.0 / total_download_secs
+    } else {
+        0.0
+    };
+    WeekStats {
+        file_count: transfers.count(),
+        total_bytes,
+        total_download_secs,
+        avg_mbps,
+    }
+}
+
+/// A file the app would save for `resource_id` from `source_url`, named
+/// `filename` — what [`rebuild_week_manifest`] matches files on disk against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpectedFile {
+    pub resource_id: i64,
+    pub source_url: String,
+    pub filename: String,
+}
+
+/// Every file name the app could have saved, per week: each URL a resource
+/// offers (original and optimized variants, since the preference may have
+/// changed) plus what the registry remembers downloading.
+pub fn expected_files(
+    resources: &[Resource],
+    registry: &[DownloadedFile],
+) -> HashMap<WeekIdentifier, Vec<ExpectedFile>> {
+    use crate::services::download::{extract_filename_from_url, sanitize_filename};
+
+    let mut expected: HashMap<WeekIdentifier, Vec<ExpectedFile>> = HashMap::new();
+    for entry in registry {
+        if let Some(filename) = entry.local_path.file_name().and_then(|n| n.to_str()) {
+            expected
+                .entry(entry.week.clone())
+                .or_default()
+                .push(ExpectedFile {
+                    resource_id: entry.resource_id,
+                    source_url: entry.source_url.clone(),
+                    filename: filename.to_string(),
+                });
+        }
+    }
+    for resource in resources {
+        let optimized = resource
+            .optimized_videos
+            .iter()
+            .flatten()
+            .map(|video| video.url.as_str());
+        let urls = std::iter::once(resource.download_url.as_str())
+            .chain(resource.optimized_video_url.as_deref())
+            .chain(optimized);
+        let week_files = expected.entry(resource.week()).or_default();
+        for url in urls {
+            week_files.push(ExpectedFile {
+                resource_id: resource.id,
+                source_url: url.to_string(),
+                filename: extract_filename_from_url(url)
+                    .unwrap_or_else(|| sanitize_filename(&resource.title)),
+            });
+        }
+    }
+    expected
+}
+
+/// A file on disk [`rebuild_week_manifest`] attributed to a resource.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RebuiltFile {
+    pub resource_id: i64,
+    pub path: PathBuf,
+    pub source_url: String,
+    pub modified: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// [`rebuild_week_manifest`] for every week folder directly under each of
+/// `roots` (the work directory and the archive). Returns how many week
+/// folders were rebuilt and the files attributed in each; a folder that
+/// fails is logged and skipped.
+pub fn rebuild_manifests(
+    roots: &[PathBuf],
+    expected: &HashMap<WeekIdentifier, Vec<ExpectedFile>>,
+) -> (usize, Vec<(WeekIdentifier, RebuiltFile)>) {
+    let mut weeks = 0;
+    let mut attributed = Vec::new();
+    for (week, path) in week_dirs(roots) {
+        let week_expected = expected.get(&week).map(Vec::as_slice).unwrap_or_default();
+        match rebuild_week_manifest(&path, week_expected) {
+            Ok(files) => {
+                weeks += 1;
+                attributed.extend(files.into_iter().map(|file| (week.clone(), file)));
+            }
+            Err(e) => tracing::warn!("Manifest rebuild: skipping {:?}: {}", path, e),
+        }
+    }
+    (weeks, attributed)
+}
+
+/// Week folders directly under each of `roots`; unreadable roots are
+/// skipped.
+pub(crate) fn week_dirs(roots: &[PathBuf]) -> Vec<(WeekIdentifier, PathBuf)> {
+    let mut dirs = Vec::new();
+    for root in roots {
+        let Ok(entries) = std::fs::read_dir(root) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let path = entry.path();
+            let Some(week) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse::<WeekIdentifier>().ok())
+            else {
+                continue;
+            };
+            if path.is_dir() {
+                dirs.push((week, path));
+            }
+        }
+    }
+    dirs
+}
+
+/// Files with identical content, as found by [`find_duplicate_files`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateGroup {
+    pub hash: String,
+    /// Size of one copy.
+    pub bytes: u64,
+    /// Every copy, sorted; always more than one.
+    pub paths: Vec<PathBuf>,
+}
+
+/// Group the files of every week folder under `roots` by the SHA-256 their
+/// manifest recorded, keeping hashes shared by more than one file still on
+/// disk. Reads manifests only, nothing is hashed. Groups wasting the most
+/// space (size × extra copies) come first.
+pub fn find_duplicate_files(roots: &[PathBuf]) -> Vec<DuplicateGroup> {
+    let mut by_hash: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+    for (_, dir) in week_dirs(roots) {
+        for (name, hash) in WeekManifest::load(&dir).hashes {
+            let path = dir.join(name);
+            if path.is_file() {
+                by_hash.entry(hash).or_default().push(path);
+            }
+        }
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_hash
+        .into_iter()
+        .filter(|(_, paths)| paths.len() > 1)
+        .map(|(hash, mut paths)| {
+            paths.sort();
+            let bytes = std::fs::metadata(&paths[0]).map(|m| m.len()).unwrap_or(0);
+            DuplicateGroup { hash, bytes, paths }
+        })
+        .collect();
+    let wasted = |group: &DuplicateGroup| group.bytes * (group.paths.len() as u64 - 1);
+    groups.sort_by(|a, b| wasted(b).cmp(&wasted(a)).then_with(|| a.hash.cmp(&b.hash)));
+    groups
+}
+
+/// Rewrite `week_dir`'s manifest from the files actually on disk, for
+/// installs that predate manifests or whose files were moved by hand. Each
+/// file keeps the owner and source the old manifest recorded, else is matched
+/// by name against `expected` (best-effort: a file nothing matches stays
+/// unowned), and is hashed. Claims for downloads still in progress (a `.part`
+/// on disk) survive; claims for files that are gone don't. Blocking: hashes
+/// every file. Returns the attributed files.
+pub fn rebuild_week_manifest(
+    week_dir: &Path,
+    expected: &[ExpectedFile],
+) -> std::io::Result<Vec<RebuiltFile>> {
+    let old = WeekManifest::load(week_dir);
+    let mut rebuilt = WeekManifest::default();
+    let mut attributed = Vec::new();
+
+    for file in list_week_files(week_dir) {
+        let path = week_dir.join(&file.filename);
+        let recorded_source = || {
+            old.sources
+                .iter()
+                .find(|(_, name)| **name == file.filename)
+                .map(|(url, _)| url.clone())
+        };
+        let owner = match old.owner(&file.filename) {
+            Some(id) => recorded_source().map(|url| (id, url)).or_else(|| {
+                expected
+                    .iter()
+                    .find(|e| e.resource_id == id && e.filename == file.filename)
+                    .map(|e| (id, e.source_url.clone()))
+            }),
+            None => expected
+                .iter()
+                .find(|e| {
+                    e.filename == file.filename
+                        && !rebuilt.files.values().any(|&id| id == e.resource_id)
+                })
+                .map(|e| (e.resource_id, e.source_url.clone())),
+        };
+
+        match crate::services::download::hash_file(&path) {
+            Ok(hash) => {
+                rebuilt.hashes.insert(file.filename.clone(), hash);
+            }
+            Err(e) => tracing::warn!("Manifest rebuild: could not hash {:?}: {}", path, e),
+        }
+
+        match owner {
+            Some((resource_id, source_url)) => {
+                rebuilt.files.insert(file.filename.clone(), resource_id);
+                rebuilt
+                    .sources
+                    .insert(source_url.clone(), file.filename.clone());
+                attributed.push(RebuiltFile {
+                    resource_id,
+                    path,
+                    source_url,
+                    modified: file.modified,
+                });
+            }
+            None => {
+                if let Some(id) = old.owner(&file.filename) {
+                    rebuilt.files.insert(file.filename.clone(), id);
+                }
+            }
+        }
+    }
+
+    // Hashing took a while: merge with the manifest as it is now, so a
+    // download that claimed a name meanwhile keeps its claim.
+    let _guard = MANIFEST_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    let current = WeekManifest::load(week_dir);
+    for (name, id) in &current.files {
+        let in_progress =
+            crate::services::download::existing_part_path(&week_dir.join(name)).is_some();
+        if in_progress && !rebuilt.files.contains_key(name) {
+            rebuilt.files.insert(name.clone(), *id);
+            for (url, _) in current.sources.iter().filter(|(_, n)| *n == name) {
+                rebuilt.sources.insert(url.clone(), name.clone());
+            }
+            if let Some(record) = current.downloads.get(name) {
+                rebuilt.downloads.insert(name.clone(), record.clone());
+            }
+        }
+    }
+    if rebuilt != current {
+        rebuilt.save(week_dir)?;
+    }
+    Ok(attributed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const URL_1: &str = "https://a.example.com/video.mp4";
+    const URL_2: &str = "https://b.example.com/video.mp4";
+
+    #[test]
+    fn test_suffixed_filename() {
+        assert_eq!(suffixed_filename("video.mp4", 2), "video (2).mp4");
+        assert_eq!(suffixed_filename("archive.tar.gz", 3), "archive.tar (3).gz");
+        assert_eq!(suffixed_filename("README", 2), "README (2)");
+        assert_eq!(suffixed_filename(".hidden", 2), ".hidden (2)");
+    }
+
+    #[test]
+    fn test_unique_dest_path_without_manifest_keeps_plain_name() {
+        let tmp = TempDir::new().unwrap();
+        assert_eq!(
+            unique_dest_path(tmp.path(), "video.mp4", 1, URL_1),
+            tmp.path().join("video.mp4")
+        );
+    }
+
+    #[test]
+    fn test_claim_dest_path_suffixes_collisions_between_resources() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+
+        assert_eq!(
+            claim_dest_path(dir, "video.mp4", 1, URL_1),
+            dir.join("video.mp4")
+        );
+        assert_eq!(
+            claim_dest_path(dir, "video.mp4", 2, URL_2),
+            dir.join("video (2).mp4")
+        );
+
+        // Each resource keeps resolving to its own claim.
+        assert_eq!(
+            unique_dest_path(dir, "video.mp4", 1, URL_1),
+            dir.join("video.mp4")
+        );
+        assert_eq!(
+            unique_dest_path(dir, "video.mp4", 2, URL_2),
+            dir.join("video (2).mp4")
+        );
+        // Re-claiming is idempotent.
+        assert_eq!(
+            claim_dest_path(dir, "video.mp4", 2, URL_2),
+            dir.join("video (2).mp4")
+        );
+
+        let manifest = WeekManifest::load(dir);
+        assert_eq!(manifest.owner("video.mp4"), Some(1));
+        assert_eq!(manifest.owner("video (2).mp4"), Some(2));
+        assert_eq!(manifest.files.len(), 2);
+    }
+
+    #[test]
+    fn test_pick_filename_prefers_own_claim_over_earlier_free_slot() {
+        let mut manifest = WeekManifest::default();
+        manifest.files.insert("video.mp4".to_string(), 1);
+        manifest.files.insert("video (3).mp4".to_string(), 7);
+        assert_eq!(
+            pick_filename(&manifest, "video.mp4", 7, URL_1),
+            "video (3).mp4"
+        );
+        assert_eq!(
+            pick_filename(&manifest, "video.mp4", 8, URL_2),
+            "video (2).mp4"
+        );
+    }
+
+    #[test]
+    fn test_load_ignores_corrupt_manifest() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(tmp.path().join(MANIFEST_FILE_NAME), b"{not json").unwrap();
+        assert_eq!(WeekManifest::load(tmp.path()), WeekManifest::default());
+    }
+
+    #[test]
+    fn test_recorded_source_name_wins_over_url_name() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let api_url = "https://api.example.com/download?id=5";
+
+        // First download: named after the post-redirect URL.
+        let claimed = claim_dest_path(dir, "sermon.mp4", 5, api_url);
+        assert_eq!(claimed, dir.join("sermon.mp4"));
+
+        // Later lookups only know the API URL (and its poor name hint).
+        assert_eq!(
+            unique_dest_path(dir, "Resource 5", 5, api_url),
+            dir.join("sermon.mp4")
+        );
+        let manifest = WeekManifest::load(dir);
+        assert_eq!(
+            manifest.sources.get(api_url).map(String::as_str),
+            Some("sermon.mp4")
+        );
+    }
+
+    #[test]
+    fn test_manifest_without_sources_still_loads() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join(MANIFEST_FILE_NAME),
+            br#"{"files":{"video.mp4":1}}"#,
+        )
+        .unwrap();
+        let manifest = WeekManifest::load(tmp.path());
+        assert_eq!(manifest.owner("video.mp4"), Some(1));
+        assert!(manifest.sources.is_empty());
+    }
+
+    #[test]
+    fn test_list_week_files_marks_owned_and_extra_files() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        let owned = claim_dest_path(dir, "video.mp4", 1, URL_1);
+        std::fs::write(&owned, b"12345").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"x").unwrap();
+        std::fs::write(dir.join("other.mp4.part"), b"partial").unwrap();
+        std::fs::write(dir.join("other.mp4.part.meta"), b"{}").unwrap();
+        std::fs::write(dir.join(".DS_Store"), b"").unwrap();
+        std::fs::create_dir(dir.join("nested")).unwrap();
+
+        let files = list_week_files(dir);
+        let names: Vec<&str> = files.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(names, vec!["notes.txt", "video.mp4"]);
+        assert_eq!(files[0].resource_id, None);
+        assert_eq!(files[1].resource_id, Some(1));
+        assert_eq!(files[1].size, 5);
+        assert!(files[1].modified.is_some());
+    }
+
+    #[test]
+    fn test_list_week_files_missing_dir_is_empty() {
+        let tmp = TempDir::new().unwrap();
+        assert!(list_week_files(&tmp.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_week_stats_sums_recorded_transfers() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        assert_eq!(week_stats(dir), WeekStats::default());
+
+        record_transfer(
+            &dir.join("a.mp4"),
+            TransferRecord {
+                bytes: 3_000_000,
+                duration_secs: 2.0,
+            },
+        );
+        record_transfer(
+            &dir.join("b.pdf"),
+            TransferRecord {
+                bytes: 2_000_000,
+                duration_secs: 2.0,
+            },
+        );
+
+        let stats = week_stats(dir);
+        assert_eq!(stats.file_count, 2);
+        assert_eq!(stats.total_bytes, 5_000_000);
+        assert_eq!(stats.total_download_secs, 4.0);
+        assert_eq!(stats.avg_mbps, 10.0);
+    }
+
+    #[test]
+    fn test_week_stats_of_files_reads_each_files_folder() {
+        let tmp = TempDir::new().unwrap();
+        let video = tmp.path().join("Video");
+        let docs = tmp.path().join("Documenti");
+        std::fs::create_dir_all(&video).unwrap();
+        std::fs::create_dir_all(&docs).unwrap();
+        let transfer = |bytes| TransferRecord {
+            bytes,
+            duration_secs: 1.0,
+        };
+        record_transfer(&video.join("this-week.mp4"), transfer(3_000_000));
+        record_transfer(&docs.join("this-week.pdf"), transfer(1_000_000));
+        // Another week's file in the same folder.
+        record_transfer(&video.join("last-week.mp4"), transfer(9_000_000));
+
+        let files = vec![
+            video.join("this-week.mp4"),
+            docs.join("this-week.pdf"),
+            video.join("this-week.mp4"),
+            docs.join("never-downloaded.pdf"),
+        ];
+        let stats = week_stats_of_files(&files);
+        assert_eq!(stats.file_count, 2);
+        assert_eq!(stats.total_bytes, 4_000_000);
+        assert_eq!(stats.total_download_secs, 2.0);
+        assert_eq!(week_stats_of_files(&[]), WeekStats::default());
+    }
+
+    #[test]
+    fn test_rebuild_week_manifest_matches_files_by_name_and_hashes_them() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        std::fs::write(dir.join("video.mp4"), b"video").unwrap();
+        std::fs::write(dir.join("notes.pdf"), b"notes").unwrap();
+        std::fs::write(dir.join("stray.txt"), b"mine").unwrap();
+        // Another resource's download still in progress.
+        claim_dest_path(dir, "slides.pptx", 3, "https://a.example.com/slides.pptx");
+        std::fs::write(dir.join("slides.pptx.part"), b"sli").unwrap();
+        // A claim whose file has since been deleted.
+        claim_dest_path(dir, "gone.mp4", 4, "https://a.example.com/gone.mp4");
+
+        let expected = [
+            ExpectedFile {
+                resource_id: 1,
+                source_url: URL_1.to_string(),
+                filename: "video.mp4".to_string(),
+            },
+            ExpectedFile {
+                resource_id: 2,
+                source_url: "https://a.example.com/notes.pdf".to_string(),
+                filename: "notes.pdf".to_string(),
+            },
+        ];
+        let attributed = rebuild_week_manifest(dir, &expected).unwrap();
+
+        let ids: Vec<i64> = attributed.iter().map(|f| f.resource_id).collect();
+        assert_eq!(ids, vec![2, 1]);
+        assert_eq!(attributed[1].path, dir.join("video.mp4"));
+        assert_eq!(attributed[1].source_url, URL_1);
+        assert!(attributed[1].modified.is_some());
+
+        let manifest = WeekManifest::load(dir);
+        assert_eq!(manifest.owner("video.mp4"), Some(1));
+        assert_eq!(manifest.owner("notes.pdf"), Some(2));
+        assert_eq!(manifest.owner("stray.txt"), None);
+        assert_eq!(manifest.owner("slides.pptx"), Some(3));
+        assert_eq!(manifest.owner("gone.mp4"), None);
+        assert_eq!(
+            manifest.sources.get(URL_1).map(String::as_str),
+            Some("video.mp4")
+        );
+        assert_eq!(
+            manifest.hashes.get("video.mp4"),
+            Some(&crate::services::download::hash_file(&dir.join("video.mp4")).unwrap())
+        );
+        assert!(manifest.hashes.contains_key("stray.txt"));
+    }
+
+    /// A file the manifest already attributes (e.g. a ` (2)` name no
+    /// filename match could recover) keeps its owner and source.
+    #[test]
+    fn test_rebuild_week_manifest_keeps_recorded_owner() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path();
+        claim_dest_path(dir, "video.mp4", 1, URL_1);
+        claim_dest_path(dir, "video.mp4", 2, URL_2);
+        std::fs::write(dir.join("video.mp4"), b"one").unwrap();
+        std::fs::write(dir.join("video (2).mp4"), b"two").unwrap();
+
+        let attributed = rebuild_week_manifest(dir, &[]).unwrap();
+        assert_eq!(attributed.len(), 2);
+        let manifest = WeekManifest::load(dir);
+        assert_eq!(manifest.owner("video (2).mp4"), Some(2));
+        assert_eq!(
+            manifest.sources.get(URL_2).map(String::as_str),
+            Some("video (2).mp4")
+        );
+    }
+
+    #[test]
+    fn test_reconcile_downloads_resumes_backfills_and_drops() {
+        let dir = TempDir::new().unwrap();
+        let resumable = claim_dest_path(dir.path(), "partial.mp4", 1, URL_1);
+        let finished = claim_dest_path(dir.path(), "finished.mp4", 2, URL_2);
+        let vanished =
+            claim_dest_path(dir.path(), "vanished.mp4", 3, "https://c.example.com/v.mp4");
+        let unrecorded = claim_dest_path(dir.path(), "old.mp4", 4, "https://d.example.com/o.mp4");
+
+        record_download_started(&resumable, Some(100), Some("\"v1\"".to_string()));
+        std::fs::write(dir.path().join("partial.mp4.part"), b"half").unwrap();
+        record_download_started(&finished, Some(4), None);
+        std::fs::write(&finished, b"done").unwrap();
+        record_download_started(&vanished, None, None);
+        std::fs::write(&unrecorded, b"old").unwrap();
+
+        let report = reconcile_downloads(dir.path());
+        assert_eq!(report.resumable, vec![1]);
+        assert_eq!(report.backfilled, 2);
+        assert_eq!(report.dropped, 1);
+
+        let manifest = WeekManifest::load(dir.path());
+        assert_eq!(
+            manifest.downloads["partial.mp4"].status,
+            DownloadStatus::InProgress
+        );
+        assert_eq!(manifest.downloads["partial.mp4"].expected_bytes, Some(100));
+        assert_eq!(
+            manifest.downloads["finished.mp4"].status,
+            DownloadStatus::Done
+        );
+        assert_eq!(manifest.downloads["old.mp4"].status, DownloadStatus::Done);
+        assert!(!manifest.downloads.contains_key("vanished.mp4"));
+    }
+
+    #[test]
+    fn test_download_record_lifecycle() {
+        let dir = TempDir::new().unwrap();
+        let path = claim_dest_path(dir.path(), "video.mp4", 1, URL_1);
+
+        record_download_started(&path, Some(10), None);
+        assert_eq!(
+            WeekManifest::load(dir.path()).downloads["video.mp4"].status,
+            DownloadStatus::InProgress
+        );
+        record_download_done(&path);
+        assert_eq!(
+            WeekManifest::load(dir.path()).downloads["video.mp4"].status,
+            DownloadStatus::Done
+        );
+        clear_download_record(&path);
+        assert!(WeekManifest::load(dir.path()).downloads.is_empty());
+    }
+
+    #[test]
+    fn test_find_duplicate_files_groups_shared_hashes_across_weeks() {
+        let root = TempDir::new().unwrap();
+        let week_a = root.path().join("W03-2026-01-17");
+        let week_b = root.path().join("W04-2026-01-24");
+        std::fs::create_dir_all(&week_a).unwrap();
+        std::fs::create_dir_all(&week_b).unwrap();
+
+        let a = claim_dest_path(&week_a, "bulletin.pdf", 1, URL_1);
+        let b = claim_dest_path(&week_b, "bulletin.pdf", 2, URL_2);
+        let unique = claim_dest_path(&week_b, "video.mp4", 3, "https://c.example.com/v.mp4");
+        let gone = claim_dest_path(&week_b, "gone.pdf", 4, "https://d.example.com/g.pdf");
+        for path in [&a, &b, &unique] {
+            std::fs::write(path, b"same").unwrap();
+        }
+        record_file_hash(&a, "aaa");
+        record_file_hash(&b, "aaa");
+        record_file_hash(&unique, "bbb");
+        record_file_hash(&gone, "bbb");
+
+        let groups = find_duplicate_files(&[root.path().to_path_buf()]);
+        assert_eq!(
+            groups,
+            vec![DuplicateGroup {
+                hash: "aaa".to_string(),
+                bytes: 4,
+                paths: vec![a, b],
+            }]
+        );
+    }
+}
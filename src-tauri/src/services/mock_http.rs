@@ -0,0 +1,125 @@
+//! Minimal HTTP/1.1 server for tests
+//!
+//! Accepts connections on a local port and answers each one's single
+//! request with whatever the test's closure builds, then closes it. Enough
+//! for reqwest-driven code paths (redirects, ranges, throttling, status
+//! codes) without a mocking crate.
+
+use std::io::{BufRead, BufReader, Write};
+
+/// A request as the server read it. Header names are lowercased.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    /// Path and query, e.g. `/api/resources/latest-week?page=2`.
+    pub target: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Value of header `name` (case-insensitive), if sent.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_ascii_lowercase();
+        self.headers
+            .iter()
+            .find(|(header, _)| *header == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// The answer to one request. `Content-Length` and `Connection: close` are
+/// added when it's written.
+#[derive(Debug, Clone)]
+pub struct Response {
+    status: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl Response {
+    /// Empty response with `status`, e.g. `"404 Not Found"`.
+    pub fn new(status: &str) -> Self {
+        Self {
+            status: status.to_string(),
+            headers: Vec::new(),
+            body: Vec::new(),
+        }
+    }
+
+    /// `200 OK` carrying `body`.
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::new("200 OK").body(body)
+    }
+
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    pub fn body(mut self, body: impl Into<Vec<u8>>) -> Self {
+        self.body = body.into();
+        self
+    }
+}
+
+/// Serve up to `n` connections, one request each, answering the `i`-th
+/// (0-based) with `respond(i, &request)`. Returns the base URL
+/// (`http://127.0.0.1:port`). A client that hung up before the answer is
+/// ignored, so `respond` may also stall to simulate a hung server.
+pub fn serve<F>(n: usize, mut respond: F) -> String
+where
+    F: FnMut(usize, &Request) -> Response + Send + 'static,
+{
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    std::thread::spawn(move || {
+        for (i, stream) in listener.incoming().take(n).enumerate() {
+            let Ok(mut stream) = stream else {
+                continue;
+            };
+            let Some(request) = read_request(&stream) else {
+                continue;
+            };
+            let response = respond(i, &request);
+            let _ = write_response(&mut stream, &response);
+        }
+    });
+    base
+}
+
+/// Request line and headers; the tests' requests carry no body.
+fn read_request(stream: &std::net::TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream.try_clone().ok()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).ok()?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?.to_string();
+
+    let mut headers = Vec::new();
+    let mut line = String::new();
+    while reader.read_line(&mut line).ok()? > 2 {
+        if let Some((name, value)) = line.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+        line.clear();
+    }
+    Some(Request {
+        method,
+        target,
+        headers,
+    })
+}
+
+fn write_response(stream: &mut std::net::TcpStream, response: &Response) -> std::io::Result<()> {
+    write!(stream, "HTTP/1.1 {}\r\n", response.status)?;
+    for (name, value) in &response.headers {
+        write!(stream, "{name}: {value}\r\n")?;
+    }
+    write!(
+        stream,
+        "Content-Length: {}\r\nConnection: close\r\n\r\n",
+        response.body.len()
+    )?;
+    stream.write_all(&response.body)
+}
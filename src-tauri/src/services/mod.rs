@@ -2,14 +2,33 @@
 //!
 //! This module contains all business logic services.
 
+pub mod category_labels;
+pub mod diagnostics;
 pub mod download;
+pub mod download_events;
 pub mod errata;
+pub mod history;
+pub mod http;
+pub mod integrity;
+pub mod logging;
+pub mod manifest;
+#[cfg(test)]
+pub mod mock_http;
+pub mod notifications;
 pub mod polling;
 pub mod queue;
 pub mod retention;
+pub mod size_cache;
+pub mod state_bundle;
+pub mod thumbnails;
+pub mod workdir;
+pub mod ytdlp;
 
 pub use download::DownloadService;
 pub use errata::{detect_errata_changes, process_errata, record_downloaded_file};
 pub use polling::{poll_once, refresh_categories, PollingService};
 pub use queue::DownloadQueue;
-pub use retention::{archive_previous_weeks_once, FileRetentionService, RetentionScheduler};
+pub use retention::{
+    archive_previous_weeks_once, resolve_archive_root, run_retention_if_due, FileRetentionService,
+    RetentionScheduler,
+};
@@ -0,0 +1,292 @@
+//! Native desktop notifications
+//!
+//! Downloads usually finish while the window is hidden in the tray, so
+//! completions and errata corrige are surfaced through the OS notification
+//! center (`tauri-plugin-notification`), gated by
+//! `AppConfig::notifications_enabled`.
+
+use crate::commands::AppState;
+use crate::models::{Resource, WeekIdentifier};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// `settings.json` key remembering the newest week already announced via
+/// `new-week-available`, so a restart never re-announces the same week.
+const LAST_NOTIFIED_WEEK_KEY: &str = "last_notified_week";
+
+/// How long completions are collected before anything is shown. Long enough
+/// to catch a parallel burst (up to 4 workers finishing together), short
+/// enough that a single download still feels immediate.
+pub const COMPLETION_BATCH_WINDOW: Duration = Duration::from_secs(4);
+
+/// More completions than this inside one window collapse into a single
+/// summary notification instead of one per file.
+pub const COMPLETION_BATCH_THRESHOLD: usize = 3;
+
+#[derive(Debug, Default)]
+struct CompletionBatch {
+    /// One line per completed download, in completion order.
+    pending: Vec<String>,
+    /// Whether a flush task is already sleeping on the current window.
+    flush_scheduled: bool,
+}
+
+/// Collects `download-complete` events into time windows so a burst of
+/// parallel downloads produces one summary rather than a stack of toasts.
+/// Held in `AppState::completion_notifier`.
+#[derive(Debug, Default)]
+pub struct CompletionNotifier {
+    batch: Mutex<CompletionBatch>,
+}
+
+impl CompletionNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a completed download; the first push of a window schedules the
+    /// flush that shows whatever has accumulated by then.
+    fn push(&self, app: &AppHandle, line: String) {
+        let schedule_flush = {
+            let mut batch = match self.batch.lock() {
+                Ok(batch) => batch,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            batch.pending.push(line);
+            !std::mem::replace(&mut batch.flush_scheduled, true)
+        };
+        if !schedule_flush {
+            return;
+        }
+
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(COMPLETION_BATCH_WINDOW).await;
+            let pending = app.state::<AppState>().completion_notifier.take_pending();
+            for (title, body) in completion_notifications(&pending) {
+                show(&app, &title, &body);
+            }
+        });
+    }
+
+    /// Drain the window and re-arm scheduling under the same lock, so a push
+    /// racing the flush either lands in this batch or starts the next one.
+    fn take_pending(&self) -> Vec<String> {
+        let mut batch = match self.batch.lock() {
+            Ok(batch) => batch,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        batch.flush_scheduled = false;
+        std::mem::take(&mut batch.pending)
+    }
+}
+
+/// Body line for one completed download, e.g.
+/// "Sermon Video — Settimana 2026-W04".
+pub fn download_line(resource: &Resource) -> String {
+    format!("{} — Settimana {}", resource.title, resource.week())
+}
+
+/// Turn one window of completion lines into the `(title, body)` pairs to
+/// show: one per download up to [`COMPLETION_BATCH_THRESHOLD`], a single
+/// summary above it.
+pub fn completion_notifications(lines: &[String]) -> Vec<(String, String)> {
+    if lines.len() > COMPLETION_BATCH_THRESHOLD {
+        return vec![(
+            "Download completati".to_string(),
+            format!("{} materiali scaricati", lines.len()),
+        )];
+    }
+    lines
+        .iter()
+        .map(|line| {
+            (
+                "Download completato".to_string(),
+                format!("Scaricato: {line}"),
+            )
+        })
+        .collect()
+}
+
+/// `(title, body)` for the errata corrige detected by one poll; `None` when
+/// nothing was superseded.
+pub fn errata_notification(titles: &[String]) -> Option<(String, String)> {
+    match titles {
+        [] => None,
+        [title] => Some((
+            "Errata corrige".to_string(),
+            format!("Materiale aggiornato: {title}"),
+        )),
+        _ => Some((
+            "Errata corrige".to_string(),
+            format!("{} materiali aggiornati", titles.len()),
+        )),
+    }
+}
+
+fn notifications_enabled(app: &AppHandle) -> bool {
+    match app.state::<AppState>().config.read() {
+        Ok(config) => config.notifications_enabled,
+        Err(e) => {
+            tracing::error!("Notifications: failed to read config: {}", e);
+            false
+        }
+    }
+}
+
+fn show(app: &AppHandle, title: &str, body: &str) {
+    use tauri_plugin_notification::NotificationExt;
+    if let Err(e) = app.notification().builder().title(title).body(body).show() {
+        tracing::warn!("Notifications: failed to show \"{}\": {}", title, e);
+    }
+}
+
+/// Called by the queue worker right after `download-complete`.
+pub fn notify_download_complete(app: &AppHandle, resource: &Resource) {
+    if !notifications_enabled(app) {
+        return;
+    }
+    app.state::<AppState>()
+        .completion_notifier
+        .push(app, download_line(resource));
+}
+
+/// Called by `process_errata` right after `errata-detected`, with the titles
+/// of the resources whose local copy was just superseded.
+pub fn notify_errata(app: &AppHandle, titles: &[String]) {
+    if !notifications_enabled(app) {
+        return;
+    }
+    if let Some((title, body)) = errata_notification(titles) {
+        show(app, &title, &body);
+    }
+}
+
+/// Whether `latest` deserves a `new-week-available` announcement given the
+/// last announced week. Nothing announced yet (fresh install, or upgrading
+/// from a build without this key) seeds the marker silently: the user is
+/// looking at that week already, it isn't news.
+pub fn is_newer_week(last_notified: Option<&WeekIdentifier>, latest: &WeekIdentifier) -> bool {
+    matches!(last_notified, Some(previous) if latest > previous)
+}
+
+/// Called by `poll_once` with the latest week of each fresh snapshot: when it
+/// is newer than the last announced one, emits `new-week-available` (always)
+/// and shows a notification (if enabled), then records the week in
+/// `settings.json`. Store failures are logged, never propagated — the worst
+/// case is announcing the same week again on the next launch.
+pub fn announce_new_week(app: &AppHandle, latest: &WeekIdentifier) {
+    use tauri_plugin_store::StoreExt;
+    let store = match app.store("settings.json") {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::error!("New week: failed to access store: {}", e);
+            return;
+        }
+    };
+    let last_notified = store
+        .get(LAST_NOTIFIED_WEEK_KEY)
+        .and_then(|json| serde_json::from_value::<WeekIdentifier>(json).ok());
+    if last_notified.as_ref() == Some(latest) {
+        return;
+    }
+    if last_notified
+        .as_ref()
+        .is_some_and(|previous| previous > latest)
+    {
+        // An older snapshot (e.g. a backend rollback) never moves the marker
+        // backwards, or the real new week would be announced twice.
+        return;
+    }
+
+    if is_newer_week(last_notified.as_ref(), latest) {
+        tracing::info!("New week available: {}", latest);
+        if let Err(e) = app.emit("new-week-available", latest) {
+            tracing::error!("New week: failed to emit new-week-available: {}", e);
+        }
+        if notifications_enabled(app) {
+            show(
+                app,
+                "Nuovi materiali disponibili",
+                &format!("Sono disponibili i materiali della settimana {latest}"),
+            );
+        }
+    }
+
+    match serde_json::to_value(latest) {
+        Ok(json) => {
+            store.set(LAST_NOTIFIED_WEEK_KEY, json);
+            if let Err(e) = store.save() {
+                tracing::error!("New week: failed to persist last notified week: {}", e);
+            }
+        }
+        Err(e) => tracing::error!("New week: failed to serialize week: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(n: usize) -> Vec<String> {
+        (1..=n)
+            .map(|i| format!("File {i} — Settimana 2026-W04"))
+            .collect()
+    }
+
+    #[test]
+    fn test_completion_notifications_individual_up_to_threshold() {
+        let shown = completion_notifications(&lines(COMPLETION_BATCH_THRESHOLD));
+        assert_eq!(shown.len(), COMPLETION_BATCH_THRESHOLD);
+        assert_eq!(shown[0].1, "Scaricato: File 1 — Settimana 2026-W04");
+    }
+
+    #[test]
+    fn test_completion_notifications_summarizes_bursts() {
+        let shown = completion_notifications(&lines(COMPLETION_BATCH_THRESHOLD + 2));
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].1, "5 materiali scaricati");
+    }
+
+    #[test]
+    fn test_completion_notifications_empty_window() {
+        assert!(completion_notifications(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_errata_notification() {
+        assert_eq!(errata_notification(&[]), None);
+        let single = errata_notification(&["Sermone".to_string()]).unwrap();
+        assert_eq!(single.1, "Materiale aggiornato: Sermone");
+        let many = errata_notification(&["A".to_string(), "B".to_string()]).unwrap();
+        assert_eq!(many.1, "2 materiali aggiornati");
+    }
+
+    #[test]
+    fn test_is_newer_week() {
+        let w4 = WeekIdentifier::new(2026, 4);
+        let w5 = WeekIdentifier::new(2026, 5);
+        let next_year = WeekIdentifier::new(2027, 1);
+        assert!(is_newer_week(Some(&w4), &w5));
+        assert!(is_newer_week(Some(&w5), &next_year));
+        assert!(!is_newer_week(Some(&w5), &w5));
+        assert!(!is_newer_week(Some(&w5), &w4));
+        // First ever poll only seeds the marker.
+        assert!(!is_newer_week(None, &w5));
+    }
+
+    #[test]
+    fn test_take_pending_rearms_the_window() {
+        let notifier = CompletionNotifier::new();
+        {
+            let mut batch = notifier.batch.lock().unwrap();
+            batch.pending = lines(2);
+            batch.flush_scheduled = true;
+        }
+        assert_eq!(notifier.take_pending().len(), 2);
+        let batch = notifier.batch.lock().unwrap();
+        assert!(batch.pending.is_empty());
+        assert!(!batch.flush_scheduled);
+    }
+}
@@ -4,12 +4,12 @@
 
 use crate::commands::AppState;
 use crate::constants::api_base_url;
-use crate::models::{CategoriesCountResponse, ResourceListResponse, WeekIdentifier};
+use crate::models::{CategoriesCountResponse, Resource, ResourceListResponse, WeekIdentifier};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::watch;
-use tokio::time::{interval, sleep, Duration};
+use tokio::time::{sleep, Duration};
 
 /// Polling service that runs in the background
 pub struct PollingService {
@@ -82,17 +82,25 @@ impl PollingService {
             }
 
             let duration = Duration::from_secs(interval_mins as u64 * 60);
-            let mut ticker = interval(duration);
-
-            // `interval` fires its first tick immediately upon creation; consume
-            // it here so the periodic ticks below stay spaced by `duration`
-            // starting after the initial poll above, instead of firing a second
-            // poll back-to-back with it.
-            ticker.tick().await;
 
             loop {
+                // Each wait is drawn afresh around `duration` so installs
+                // polling on the same cadence drift apart instead of hitting
+                // the server together.
+                let jitter_percent = app
+                    .state::<AppState>()
+                    .config
+                    .read()
+                    .map(|config| config.poll_jitter_percent)
+                    .unwrap_or(0);
+                let mut wait = jittered_interval(duration, jitter_percent, fastrand::f64());
+                // The last attempt of a cycle (or a `force_poll`) was
+                // throttled: the server's wait wins when it's the longer one.
+                if let Some(retry_after) = app.state::<AppState>().take_poll_retry_after() {
+                    wait = wait.max(retry_after);
+                }
                 tokio::select! {
-                    _ = ticker.tick() => {
+                    _ = sleep(wait) => {
                         tracing::debug!("Polling tick (interval: {} minutes)", interval_mins);
 
                         // The retry backoffs live here (not in `poll_once`) so
@@ -152,6 +160,14 @@ impl PollingService {
     }
 }
 
+/// `base` moved by up to ±`percent`% of itself: `sample` (uniform in
+/// `[0, 1)`) maps linearly onto `[-percent, +percent)`.
+fn jittered_interval(base: Duration, percent: u8, sample: f64) -> Duration {
+    let spread = f64::from(percent.min(100)) / 100.0;
+    let factor = 1.0 + spread * (2.0 * sample.clamp(0.0, 1.0) - 1.0);
+    base.mul_f64(factor)
+}
+
 impl Default for PollingService {
     fn default() -> Self {
         Self::new()
@@ -187,16 +203,21 @@ enum RetryOutcome<E> {
 
 /// Run `attempt`, retrying on error along `backoffs`. The backoff sleeps sit
 /// inside a `tokio::select!` on `cancel_rx`, so cancellation during a wait is
-/// observed *immediately* rather than after the sleep elapses. Deliberately
-/// free of any `AppHandle` so the retry/cancel control flow is unit-testable.
-async fn run_with_backoff<F, Fut, T, E>(
+/// observed *immediately* rather than after the sleep elapses. Before each
+/// sleep `retry_after` is asked for a server-requested wait (a throttled
+/// attempt's `Retry-After`), which stretches the backoff when longer.
+/// Deliberately free of any `AppHandle` so the retry/cancel control flow is
+/// unit-testable.
+async fn run_with_backoff<F, Fut, T, E, R>(
     cancel_rx: &mut watch::Receiver<bool>,
     backoffs: &[Duration],
+    mut retry_after: R,
     mut attempt: F,
 ) -> RetryOutcome<E>
 where
     F: FnMut() -> Fut,
     Fut: std::future::Future<Output = Result<T, E>>,
+    R: FnMut() -> Option<Duration>,
 {
     let mut last_err = match attempt().await {
         Ok(_) => return RetryOutcome::Succeeded,
@@ -204,7 +225,7 @@ where
     };
     for &backoff in backoffs {
         tokio::select! {
-            _ = sleep(backoff) => {}
+            _ = sleep(backoff.max(retry_after().unwrap_or_default())) => {}
             _ = cancel_rx.changed() => return RetryOutcome::Cancelled,
         }
         match attempt().await {
@@ -223,7 +244,17 @@ async fn poll_once_with_cancellable_retry(
     app: &AppHandle,
     cancel_rx: &mut watch::Receiver<bool>,
 ) -> PollCycle {
-    match run_with_backoff(cancel_rx, &POLL_RETRY_BACKOFFS, || poll_once(app)).await {
+    // Offline mode: skip the cycle quietly instead of failing and retrying.
+    if app.state::<AppState>().is_offline() {
+        tracing::debug!("Offline mode: skipping scheduled poll");
+        return PollCycle::Finished;
+    }
+    let take_retry_after = || app.state::<AppState>().take_poll_retry_after();
+    match run_with_backoff(cancel_rx, &POLL_RETRY_BACKOFFS, take_retry_after, || {
+        poll_once(app)
+    })
+    .await
+    {
         RetryOutcome::Succeeded => PollCycle::Finished,
         RetryOutcome::GaveUp(e) => {
             tracing::error!(
@@ -249,42 +280,320 @@ fn check_poll_status(status: reqwest::StatusCode) -> Result<(), String> {
     }
 }
 
+/// A resource of the latest-week response that failed to deserialize and
+/// was left out; payload of the `resource-parse-errors` event.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ResourceParseError {
+    /// Position of the entry in the response's `resources` array.
+    pub index: usize,
+    /// The entry's `id`, when it has a readable one.
+    pub id: Option<i64>,
+    pub error: String,
+}
+
+/// Latest-week response with `resources` kept as raw JSON, so each entry
+/// can be decoded on its own.
+#[derive(serde::Deserialize)]
+struct RawResourceList {
+    count: u32,
+    resources: Vec<serde_json::Value>,
+    #[serde(default)]
+    next: Option<String>,
+}
+
 /// Decode a latest-week body, logging the first ~300 chars on failure. The
 /// caller passes the already-read body (via `response.text()`) precisely so a
 /// malformed 2xx payload is available to diagnose here instead of being lost
-/// inside `response.json()`.
-fn parse_latest_week_body(body: &str) -> Result<ResourceListResponse, String> {
-    serde_json::from_str(body).map_err(|e| {
+/// inside `response.json()`. Only the envelope is fatal: a resource that
+/// fails to decode is skipped and reported, so one bad entry can't hide the
+/// valid ones.
+fn parse_latest_week_body(
+    body: &str,
+) -> Result<(ResourceListResponse, Vec<ResourceParseError>), String> {
+    let raw: RawResourceList = serde_json::from_str(body).map_err(|e| {
         let preview: String = body.chars().take(300).collect();
         tracing::warn!("Poll decode failed: {}; body starts: {}", e, preview);
         format!("Failed to parse response: {}", e)
-    })
+    })?;
+
+    let mut resources = Vec::with_capacity(raw.resources.len());
+    let mut errors = Vec::new();
+    for (index, value) in raw.resources.into_iter().enumerate() {
+        let id = value.get("id").and_then(serde_json::Value::as_i64);
+        match serde_json::from_value::<Resource>(value) {
+            Ok(resource) => resources.push(resource),
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping unparseable resource #{} (id {:?}): {}",
+                    index,
+                    id,
+                    e
+                );
+                errors.push(ResourceParseError {
+                    index,
+                    id,
+                    error: e.to_string(),
+                });
+            }
+        }
+    }
+
+    Ok((
+        ResourceListResponse {
+            count: raw.count,
+            resources,
+            next: raw.next.filter(|next| !next.trim().is_empty()),
+        },
+        errors,
+    ))
 }
 
-/// Single latest-week fetch attempt: status is checked *before* decoding, and
-/// the body is read as text first so a bad payload can be logged.
+/// Why a latest-week fetch failed. Only `Unreachable` (no connection, or no
+/// response in time) is worth repeating against the fallback endpoint: an
+/// HTTP error or a bad body would most likely be the same there.
+#[derive(Debug)]
+enum FetchError {
+    Unreachable(String),
+    /// 404: the endpoint doesn't exist on this server (an older API without
+    /// `recent`).
+    NotFound(String),
+    /// 429/503 with a `Retry-After`: the server wants `wait` before the
+    /// next attempt.
+    RateLimited {
+        message: String,
+        url: String,
+        wait: Duration,
+    },
+    Failed(String),
+}
+
+impl FetchError {
+    fn into_message(self) -> String {
+        match self {
+            FetchError::Unreachable(message)
+            | FetchError::NotFound(message)
+            | FetchError::RateLimited { message, .. }
+            | FetchError::Failed(message) => message,
+        }
+    }
+}
+
+/// Latest-week fetch, following `next` for up to `max_pages` pages and
+/// accumulating them into one response: `count` is the sum of the pages'
+/// counts and parse-error indexes run on across pages. A failed page fails
+/// the whole fetch, so a partial week never replaces the resources. When
+/// the cap stops it early, `next` keeps the first page not fetched.
 async fn fetch_latest_week(
     client: &reqwest::Client,
     url: &str,
-) -> Result<ResourceListResponse, String> {
-    let response = client
-        .get(url)
-        .send()
-        .await
-        .map_err(|e| format!("API request failed: {}", e))?;
+    max_pages: u32,
+) -> Result<(ResourceListResponse, Vec<ResourceParseError>), FetchError> {
+    let (mut response, mut errors) = fetch_page(client, url).await?;
+    let mut seen = vec![url.to_string()];
+    let mut received = response.resources.len() + errors.len();
+    while let Some(next) = response.next.take() {
+        let current = seen.last().map(String::as_str).unwrap_or(url);
+        let next_url = next_page_url(current, &next).map_err(FetchError::Failed)?;
+        if seen.contains(&next_url) {
+            tracing::warn!(
+                "Poll: page {} links back to an earlier page; stopping",
+                next_url
+            );
+            break;
+        }
+        if seen.len() >= max_pages as usize {
+            tracing::warn!(
+                "Poll: stopped after {} pages; {} not fetched",
+                seen.len(),
+                next_url
+            );
+            response.next = Some(next_url);
+            break;
+        }
+        let (page, page_errors) = fetch_page(client, &next_url).await?;
+        let offset = received;
+        received += page.resources.len() + page_errors.len();
+        response.count = response.count.saturating_add(page.count);
+        response.next = page.next;
+        response.resources.extend(page.resources);
+        errors.extend(page_errors.into_iter().map(|mut error| {
+            error.index += offset;
+            error
+        }));
+        seen.push(next_url);
+    }
+    Ok((response, errors))
+}
+
+/// `next` resolved against the URL of the page that carried it.
+fn next_page_url(current: &str, next: &str) -> Result<String, String> {
+    reqwest::Url::parse(current)
+        .and_then(|base| base.join(next.trim()))
+        .map(String::from)
+        .map_err(|e| format!("Invalid next page URL {:?}: {}", next, e))
+}
+
+/// Single latest-week page fetch: status is checked *before* decoding, and
+/// the body is read as text first so a bad payload can be logged.
+async fn fetch_page(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(ResourceListResponse, Vec<ResourceParseError>), FetchError> {
+    let response = client.get(url).send().await.map_err(|e| {
+        let message = format!("API request failed: {}", e);
+        if e.is_connect() || e.is_timeout() {
+            FetchError::Unreachable(message)
+        } else {
+            FetchError::Failed(message)
+        }
+    })?;
 
     let status = response.status();
+    if let Some(wait) =
+        crate::services::http::retry_after(status, response.headers(), chrono::Utc::now())
+    {
+        return Err(FetchError::RateLimited {
+            message: format!("API {} (retry after {}s)", status, wait.as_secs()),
+            url: url.to_string(),
+            wait,
+        });
+    }
     if let Err(e) = check_poll_status(status) {
         tracing::warn!("Poll fetch returned non-success status: {}", status);
-        return Err(e);
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Err(FetchError::NotFound(e));
+        }
+        return Err(FetchError::Failed(e));
     }
 
     let body = response
         .text()
         .await
-        .map_err(|e| format!("Failed to read response body: {}", e))?;
+        .map_err(|e| FetchError::Failed(format!("Failed to read response body: {}", e)))?;
 
-    parse_latest_week_body(&body)
+    parse_latest_week_body(&body).map_err(FetchError::Failed)
+}
+
+/// The resources of the latest `fetch_weeks` weeks from `base`: the
+/// `recent?weeks=N` endpoint when more than one week is wanted, else (or
+/// when the server has no such endpoint and answers 404) `latest-week`.
+async fn fetch_resources(
+    client: &reqwest::Client,
+    base: &str,
+    fetch_weeks: u32,
+    max_pages: u32,
+) -> Result<(ResourceListResponse, Vec<ResourceParseError>), FetchError> {
+    if fetch_weeks > 1 {
+        let recent_url = format!("{}/api/resources/recent?weeks={}", base, fetch_weeks);
+        match fetch_latest_week(client, &recent_url, max_pages).await {
+            Err(FetchError::NotFound(_)) => {
+                tracing::warn!(
+                    "Poll: {} has no recent-weeks endpoint; fetching the latest week only",
+                    base
+                );
+            }
+            result => return result,
+        }
+    }
+    let latest_url = format!("{}/api/resources/latest-week", base);
+    fetch_latest_week(client, &latest_url, max_pages).await
+}
+
+/// Fetch the latest week(s) from `primary_base`, and from `fallback_base`
+/// when the primary is unreachable. The third value is true when the
+/// fallback served the data. Both go through the caller's retry schedule
+/// together (`POLL_RETRY_BACKOFFS` for the polling loop), so the mirror is
+/// paced exactly like the primary.
+async fn fetch_latest_week_with_fallback(
+    client: &reqwest::Client,
+    primary_base: &str,
+    fallback_base: Option<&str>,
+    fetch_weeks: u32,
+    max_pages: u32,
+) -> Result<(ResourceListResponse, Vec<ResourceParseError>, bool), FetchError> {
+    match fetch_resources(client, primary_base, fetch_weeks, max_pages).await {
+        Ok((response, errors)) => Ok((response, errors, false)),
+        Err(FetchError::Unreachable(primary_err)) => {
+            let Some(fallback_base) = fallback_base else {
+                return Err(FetchError::Unreachable(primary_err));
+            };
+            tracing::warn!(
+                "Primary API unreachable ({}), trying the fallback endpoint",
+                primary_err
+            );
+            fetch_resources(client, fallback_base, fetch_weeks, max_pages)
+                .await
+                .map(|(response, errors)| (response, errors, true))
+                .map_err(|e| match e {
+                    FetchError::RateLimited { message, url, wait } => FetchError::RateLimited {
+                        message: format!("{}; fallback: {}", primary_err, message),
+                        url,
+                        wait,
+                    },
+                    e => FetchError::Failed(format!(
+                        "{}; fallback: {}",
+                        primary_err,
+                        e.into_message()
+                    )),
+                })
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// The latest-week response's `count` disagreeing with the number of
+/// resources it actually carried (a truncated body or a pagination bug on
+/// the server); payload of the `resource-count-mismatch` event.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ResourceCountMismatch {
+    /// `count` as reported by the server.
+    pub reported: u32,
+    /// Entries in `resources`, including any that failed to parse.
+    pub received: usize,
+}
+
+/// `Some` when `reported` doesn't match `received`.
+fn count_mismatch(reported: u32, received: usize) -> Option<ResourceCountMismatch> {
+    (usize::try_from(reported).ok() != Some(received))
+        .then_some(ResourceCountMismatch { reported, received })
+}
+
+/// Drop repeated ids from `resources`, keeping the entry with the newest
+/// `created_at` in the position of the id's first occurrence. Returns the
+/// duplicated ids, in order of first occurrence.
+fn dedup_resource_ids(resources: &mut Vec<Resource>) -> Vec<i64> {
+    let mut first_index: std::collections::HashMap<i64, usize> = std::collections::HashMap::new();
+    let mut deduped: Vec<Resource> = Vec::with_capacity(resources.len());
+    let mut duplicates = Vec::new();
+    for resource in resources.drain(..) {
+        match first_index.get(&resource.id) {
+            Some(&index) => {
+                if !duplicates.contains(&resource.id) {
+                    duplicates.push(resource.id);
+                }
+                if resource.created_at > deduped[index].created_at {
+                    deduped[index] = resource;
+                }
+            }
+            None => {
+                first_index.insert(resource.id, deduped.len());
+                deduped.push(resource);
+            }
+        }
+    }
+    *resources = deduped;
+    duplicates
+}
+
+/// Resolves when `cancel_rx` sees a new value; never without a receiver, or
+/// once its sender is gone.
+async fn cancelled(cancel_rx: Option<watch::Receiver<u64>>) {
+    if let Some(mut rx) = cancel_rx {
+        if rx.changed().await.is_ok() {
+            return;
+        }
+    }
+    std::future::pending::<()>().await
 }
 
 /// Perform one full poll cycle: fetch the latest week (a single fail-fast
@@ -296,10 +605,102 @@ async fn fetch_latest_week(
 /// loop wraps this in `poll_once_with_cancellable_retry` for retries, while
 /// `force_poll` runs it once so the UI never blocks on a backoff.
 pub async fn poll_once(app: &AppHandle) -> Result<ResourceListResponse, String> {
+    poll_once_cancellable(app, None)
+        .await?
+        .ok_or_else(|| "Poll cancelled".to_string())
+}
+
+/// [`poll_once`] whose network fetch is abandoned as soon as `cancel_rx`
+/// sees a new value (`commands::cancel_poll`), giving `Ok(None)`. Only the
+/// fetch is cancellable: nothing has been touched yet at that point, so a
+/// cancelled poll leaves resources and status exactly as they were.
+pub async fn poll_once_cancellable(
+    app: &AppHandle,
+    cancel_rx: Option<watch::Receiver<u64>>,
+) -> Result<Option<ResourceListResponse>, String> {
     let state = app.state::<AppState>();
-    let url = format!("{}/api/resources/latest-week", api_base_url());
+    let (fallback_base, fetch_weeks, max_pages) = {
+        let config = state.config.read().map_err(|e| e.to_string())?;
+        (
+            config.effective_api_fallback_url().map(str::to_owned),
+            config.fetch_weeks,
+            config.max_poll_pages,
+        )
+    };
 
-    let api_response = fetch_latest_week(&state.shared_http_client, &url).await?;
+    let client = state.http_client();
+    let base = api_base_url();
+    let fetch = fetch_latest_week_with_fallback(
+        &client,
+        &base,
+        fallback_base.as_deref(),
+        fetch_weeks,
+        max_pages,
+    );
+    let (mut api_response, parse_errors, served_by_fallback) = tokio::select! {
+        result = fetch => result.map_err(|e| {
+            if let FetchError::RateLimited { url, wait, .. } = &e {
+                // Picked up by the polling loop before its next attempt.
+                state.note_poll_retry_after(*wait);
+                crate::services::http::emit_rate_limited(
+                    app,
+                    crate::services::http::RateLimited {
+                        source: crate::services::http::RateLimitedSource::Poll,
+                        url: url.clone(),
+                        resource_id: None,
+                        wait_secs: wait.as_secs(),
+                    },
+                );
+            }
+            e.into_message()
+        })?,
+        _ = cancelled(cancel_rx) => {
+            tracing::info!("Poll cancelled while waiting for the server");
+            return Ok(None);
+        }
+    };
+    if !parse_errors.is_empty() {
+        let _ = app.emit("resource-parse-errors", &parse_errors);
+    }
+    // Checked before anything is dropped: skipped entries were still sent.
+    let received = api_response.resources.len() + parse_errors.len();
+    if let Some(mismatch) = count_mismatch(api_response.count, received) {
+        tracing::warn!(
+            "API reported {} resources but sent {}; the response may be partial",
+            mismatch.reported,
+            mismatch.received
+        );
+        let _ = app.emit("resource-count-mismatch", &mismatch);
+    }
+
+    // Everything downstream keys on the id: collapse duplicates here rather
+    // than let a map silently drop one of them.
+    let duplicates = dedup_resource_ids(&mut api_response.resources);
+    if !duplicates.is_empty() {
+        tracing::warn!(
+            "API returned duplicate resource ids {:?}; keeping the newest of each",
+            duplicates
+        );
+        let _ = app.emit(
+            "resource-anomaly",
+            serde_json::json!({ "kind": "duplicate-ids", "resourceIds": duplicates }),
+        );
+    }
+
+    // Left out of the current-week computation by `latest_week`.
+    let now = chrono::Utc::now();
+    let future_dated: Vec<i64> = api_response
+        .resources
+        .iter()
+        .filter(|resource| resource.is_future_dated(now))
+        .map(|resource| resource.id)
+        .collect();
+    if !future_dated.is_empty() {
+        tracing::warn!(
+            "API returned resources created in the future {:?}; ignoring them for the current week",
+            future_dated
+        );
+    }
 
     // Get old resources for cache invalidation
     let old_resources = {
@@ -343,7 +744,7 @@ pub async fn poll_once(app: &AppHandle) -> Result<ResourceListResponse, String>
 
         // Remove cache entries for URLs that no longer exist
         let keys_to_remove: Vec<String> = cache
-            .keys()
+            .urls()
             .filter(|url| !current_urls.contains(*url))
             .cloned()
             .collect();
@@ -366,6 +767,7 @@ pub async fn poll_once(app: &AppHandle) -> Result<ResourceListResponse, String>
         let mut status = state.status.write().map_err(|e| e.to_string())?;
         status.last_poll_time = Some(chrono::Utc::now());
         status.total_resources = api_response.resources.len();
+        status.served_by_fallback = served_by_fallback;
 
         if let Some(week) = crate::models::latest_week(&api_response.resources) {
             if status.current_week.as_ref() != Some(&week) {
@@ -377,10 +779,18 @@ pub async fn poll_once(app: &AppHandle) -> Result<ResourceListResponse, String>
             crate::models::is_material_week_stale(status.current_week.as_ref());
     }
 
+    crate::commands::complete_setup_if_ready(app);
+
     // Emit event to frontend
     let _ = app.emit("resources-updated", &api_response);
     let _ = app.emit("poll-tick", ());
 
+    // Compared against the persisted last-announced week rather than
+    // `new_current_week`, which also fires on the first poll after launch.
+    if let Some(week) = crate::models::latest_week(&api_response.resources) {
+        crate::services::notifications::announce_new_week(app, &week);
+    }
+
     // Second, independent GET for the full category catalog (best-effort:
     // its own errors never fail the poll).
     refresh_categories(app).await;
@@ -394,11 +804,7 @@ pub async fn poll_once(app: &AppHandle) -> Result<ResourceListResponse, String>
     // Save file size cache (exclude negative cache entries from persistence)
     let cache_snapshot = {
         let cache = state.file_size_cache.read().map_err(|e| e.to_string())?;
-        cache
-            .iter()
-            .filter(|(_, &size)| size != u64::MAX) // Exclude negative cache
-            .map(|(k, v)| (k.clone(), *v))
-            .collect::<std::collections::HashMap<String, u64>>()
+        cache.known_sizes()
     };
     let cache_json = serde_json::to_value(&cache_snapshot).map_err(|e| e.to_string())?;
     store.set("file_size_cache", cache_json);
@@ -419,6 +825,10 @@ pub async fn poll_once(app: &AppHandle) -> Result<ResourceListResponse, String>
     // Initial check for auto-downloads
     state.download_queue.scan_and_queue(app.clone()).await;
 
+    // The size cache was just invalidated above: re-estimate pending bytes
+    // even if the scan queued nothing new.
+    state.download_queue.refresh_pending_status(app).await;
+
     // The current week just changed: archive the folders of the now-past
     // week(s) so enforce_retention (already scheduled daily) has something
     // to trash after retention_days (bl-desktop-archiving-not-called).
@@ -427,7 +837,11 @@ pub async fn poll_once(app: &AppHandle) -> Result<ResourceListResponse, String>
         crate::services::archive_previous_weeks_once(app, &week).await;
     }
 
-    Ok(api_response)
+    // Daily at most: the gate persists the last run, so frequent polls (or
+    // restarts) never rescan the archive more than once a day.
+    crate::services::run_retention_if_due(app).await;
+
+    Ok(Some(api_response))
 }
 
 /// A parsed-but-empty categories response (`{}` or
@@ -453,7 +867,7 @@ pub async fn refresh_categories(app: &AppHandle) {
     let state = app.state::<AppState>();
     let url = format!("{}/api/resources/categories/counts", api_base_url());
 
-    let response = match state.shared_http_client.get(&url).send().await {
+    let response = match state.http_client().get(&url).send().await {
         Ok(r) => r,
         Err(e) => {
             tracing::warn!("Categories fetch failed, keeping last known: {}", e);
@@ -494,6 +908,7 @@ pub async fn refresh_categories(app: &AppHandle) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::mock_http::{self, Response};
 
     // `start`/`restart` need an `AppHandle`, which can't be constructed in a
     // unit test, so the running-loop behavior is verified manually (see the
@@ -558,10 +973,29 @@ mod tests {
 
     #[test]
     fn valid_body_decodes() {
-        let parsed = parse_latest_week_body(r#"{"count":0,"resources":[]}"#)
+        let (parsed, errors) = parse_latest_week_body(r#"{"count":0,"resources":[]}"#)
             .expect("well-formed body must decode");
         assert_eq!(parsed.count, 0);
         assert!(parsed.resources.is_empty());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn malformed_resource_is_skipped_and_reported() {
+        let body = r#"{"count":3,"resources":[
+            {"id":1,"category":"video","title":"A","download_url":"https://x/1",
+             "is_active":true,"created_at":"2026-01-17T23:51:02Z"},
+            {"id":2,"category":"video","title":"B","download_url":"https://x/2",
+             "is_active":true,"created_at":"not a date"},
+            {"category":"video"}
+        ]}"#;
+        let (parsed, errors) = parse_latest_week_body(body).expect("envelope is valid");
+        assert_eq!(parsed.count, 3);
+        assert_eq!(parsed.resources.len(), 1);
+        assert_eq!(parsed.resources[0].id, 1);
+        assert_eq!(errors.len(), 2);
+        assert_eq!((errors[0].index, errors[0].id), (1, Some(2)));
+        assert_eq!((errors[1].index, errors[1].id), (2, None));
     }
 
     #[test]
@@ -580,6 +1014,7 @@ mod tests {
         let outcome: RetryOutcome<String> = run_with_backoff(
             &mut rx,
             &[Duration::from_secs(10), Duration::from_secs(30)],
+            || None,
             || {
                 calls += 1;
                 async { Ok::<(), String>(()) }
@@ -598,6 +1033,7 @@ mod tests {
         let outcome: RetryOutcome<i32> = run_with_backoff(
             &mut rx,
             &[Duration::from_millis(1), Duration::from_millis(1)],
+            || None,
             || {
                 calls += 1;
                 let n = calls;
@@ -619,13 +1055,310 @@ mod tests {
         // without sleeping out the (deliberately huge) backoff.
         tx.send(true).unwrap();
         let mut calls = 0;
-        let outcome: RetryOutcome<()> =
-            run_with_backoff(&mut rx, &[Duration::from_secs(3600)], || {
+        let outcome: RetryOutcome<()> = run_with_backoff(
+            &mut rx,
+            &[Duration::from_secs(3600)],
+            || None,
+            || {
                 calls += 1;
                 async { Err::<(), ()>(()) }
-            })
-            .await;
+            },
+        )
+        .await;
         assert!(matches!(outcome, RetryOutcome::Cancelled));
         assert_eq!(calls, 1, "only the initial attempt runs before the cancel");
     }
+
+    #[tokio::test]
+    async fn backoff_waits_out_a_longer_retry_after() {
+        let (_tx, mut rx) = watch::channel(false);
+        let mut calls = 0;
+        let started = std::time::Instant::now();
+        let outcome: RetryOutcome<()> = run_with_backoff(
+            &mut rx,
+            &[Duration::from_millis(1)],
+            || Some(Duration::from_millis(200)),
+            || {
+                calls += 1;
+                async { Err::<(), ()>(()) }
+            },
+        )
+        .await;
+        assert!(matches!(outcome, RetryOutcome::GaveUp(())));
+        assert_eq!(calls, 2);
+        assert!(started.elapsed() >= Duration::from_millis(200));
+    }
+
+    fn resource(id: i64, title: &str, day: u32) -> Resource {
+        use chrono::TimeZone;
+        Resource {
+            id,
+            category: "video".to_string(),
+            title: title.to_string(),
+            description: None,
+            download_url: format!("https://example.com/{id}.mp4"),
+            thumbnail_url: None,
+            file_type: None,
+            checksum: None,
+            is_active: true,
+            created_at: chrono::Utc
+                .with_ymd_and_hms(2026, 1, day, 12, 0, 0)
+                .unwrap(),
+            week_date: None,
+            optimized_video_url: None,
+            optimized_videos: None,
+        }
+    }
+
+    #[test]
+    fn test_dedup_resource_ids_keeps_newest_in_first_position() {
+        let mut resources = vec![
+            resource(1, "old", 19),
+            resource(2, "other", 19),
+            resource(1, "new", 20),
+            resource(3, "third", 19),
+            resource(1, "older", 18),
+        ];
+        let duplicates = dedup_resource_ids(&mut resources);
+
+        assert_eq!(duplicates, vec![1]);
+        let titles: Vec<&str> = resources.iter().map(|r| r.title.as_str()).collect();
+        assert_eq!(titles, vec!["new", "other", "third"]);
+    }
+
+    #[test]
+    fn test_dedup_resource_ids_leaves_unique_list_alone() {
+        let mut resources = vec![resource(1, "a", 19), resource(2, "b", 19)];
+        assert!(dedup_resource_ids(&mut resources).is_empty());
+        assert_eq!(resources.len(), 2);
+    }
+
+    #[test]
+    fn test_jittered_interval_stays_within_bounds() {
+        let base = Duration::from_secs(3600);
+        let secs = |percent, sample| jittered_interval(base, percent, sample).as_secs_f64();
+        assert_eq!(jittered_interval(base, 0, 0.9), base);
+        assert!((secs(10, 0.0) - 3240.0).abs() < 1e-3);
+        assert!((secs(10, 0.5) - 3600.0).abs() < 1e-3);
+        for _ in 0..1000 {
+            let wait = secs(10, fastrand::f64());
+            assert!((3240.0 - 1e-3..=3960.0).contains(&wait), "{wait}");
+        }
+    }
+
+    #[test]
+    fn test_count_mismatch() {
+        assert_eq!(count_mismatch(3, 3), None);
+        assert_eq!(
+            count_mismatch(12, 10),
+            Some(ResourceCountMismatch {
+                reported: 12,
+                received: 10
+            })
+        );
+        assert!(count_mismatch(0, 1).is_some());
+    }
+
+    /// Serve `body` with `status` to each of up to `n` requests; returns the
+    /// base URL.
+    fn serve_latest_week(n: usize, status: &'static str, body: &'static str) -> String {
+        mock_http::serve(n, move |_, _| Response::new(status).body(body))
+    }
+
+    /// Base URL nothing listens on.
+    fn unreachable_base() -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    const EMPTY_WEEK: &str = r#"{"count":0,"resources":[]}"#;
+
+    #[tokio::test]
+    async fn fallback_serves_when_primary_is_unreachable() {
+        let client = reqwest::Client::new();
+        let fallback = serve_latest_week(1, "200 OK", EMPTY_WEEK);
+
+        let (response, _, served_by_fallback) =
+            fetch_latest_week_with_fallback(&client, &unreachable_base(), Some(&fallback), 1, 10)
+                .await
+                .expect("fallback must serve the week");
+        assert_eq!(response.count, 0);
+        assert!(served_by_fallback);
+
+        let err = fetch_latest_week_with_fallback(&client, &unreachable_base(), None, 1, 10)
+            .await
+            .expect_err("no fallback configured")
+            .into_message();
+        assert!(err.starts_with("API request failed"), "got: {err}");
+    }
+
+    #[tokio::test]
+    async fn fallback_is_not_tried_on_http_errors() {
+        let client = reqwest::Client::new();
+        let primary = serve_latest_week(1, "404 Not Found", "");
+        let fallback = serve_latest_week(1, "200 OK", EMPTY_WEEK);
+
+        let err = fetch_latest_week_with_fallback(&client, &primary, Some(&fallback), 1, 10)
+            .await
+            .expect_err("a 404 must not fall back")
+            .into_message();
+        assert!(err.contains("404"), "got: {err}");
+
+        let primary = serve_latest_week(1, "200 OK", EMPTY_WEEK);
+        let (_, _, served_by_fallback) =
+            fetch_latest_week_with_fallback(&client, &primary, Some(&fallback), 1, 10)
+                .await
+                .expect("primary answers");
+        assert!(!served_by_fallback);
+    }
+
+    #[tokio::test]
+    async fn throttled_fetch_carries_the_retry_after() {
+        let client = reqwest::Client::new();
+        let base = mock_http::serve(1, |_, _| {
+            Response::new("429 Too Many Requests").header("Retry-After", "7")
+        });
+
+        let err = fetch_latest_week_with_fallback(&client, &base, None, 1, 10)
+            .await
+            .expect_err("a 429 is an error");
+        match err {
+            FetchError::RateLimited { url, wait, .. } => {
+                assert_eq!(wait, Duration::from_secs(7));
+                assert!(url.ends_with("/api/resources/latest-week"), "{url}");
+            }
+            other => panic!("expected RateLimited, got {}", other.into_message()),
+        }
+    }
+
+    /// Serve each `(path, body)` route with 200 OK, for up to `n` requests;
+    /// unknown paths get a 404. Returns the base URL.
+    fn serve_pages(n: usize, routes: &'static [(&'static str, &'static str)]) -> String {
+        mock_http::serve(n, move |_, request| {
+            routes
+                .iter()
+                .find(|(route, _)| *route == request.target)
+                .map_or(Response::new("404 Not Found"), |(_, body)| {
+                    Response::ok(*body)
+                })
+        })
+    }
+
+    const PAGED_WEEK: &[(&str, &str)] = &[
+        (
+            "/api/resources/latest-week",
+            r#"{"count":1,"next":"latest-week?page=2","resources":[
+                {"id":1,"category":"video","title":"A","download_url":"https://x/1",
+                 "is_active":true,"created_at":"2026-01-17T23:51:02Z"}]}"#,
+        ),
+        (
+            "/api/resources/latest-week?page=2",
+            r#"{"count":2,"resources":[
+                {"category":"video"},
+                {"id":2,"category":"video","title":"B","download_url":"https://x/2",
+                 "is_active":true,"created_at":"2026-01-17T23:51:02Z"}]}"#,
+        ),
+    ];
+
+    #[tokio::test]
+    async fn pages_are_followed_and_accumulated() {
+        let client = reqwest::Client::new();
+        let base = serve_pages(2, PAGED_WEEK);
+
+        let (response, errors, _) = fetch_latest_week_with_fallback(&client, &base, None, 1, 10)
+            .await
+            .expect("both pages load");
+        assert_eq!(response.count, 3);
+        let ids: Vec<i64> = response.resources.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(response.next, None);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].index, 1);
+    }
+
+    #[tokio::test]
+    async fn page_cap_stops_early_and_keeps_next() {
+        let client = reqwest::Client::new();
+        let base = serve_pages(1, PAGED_WEEK);
+
+        let (response, _, _) = fetch_latest_week_with_fallback(&client, &base, None, 1, 1)
+            .await
+            .expect("first page loads");
+        assert_eq!(response.resources.len(), 1);
+        assert_eq!(
+            response.next,
+            Some(format!("{base}/api/resources/latest-week?page=2"))
+        );
+    }
+
+    #[tokio::test]
+    async fn next_linking_back_stops_the_loop() {
+        let client = reqwest::Client::new();
+        let base = serve_pages(
+            1,
+            &[(
+                "/api/resources/latest-week",
+                r#"{"count":0,"next":"latest-week","resources":[]}"#,
+            )],
+        );
+
+        let (response, _, _) = fetch_latest_week_with_fallback(&client, &base, None, 1, 10)
+            .await
+            .expect("the page loads once");
+        assert_eq!(response.count, 0);
+        assert_eq!(response.next, None);
+    }
+
+    const ONE_RESOURCE: &str = r#"{"count":1,"resources":[
+        {"id":1,"category":"video","title":"A","download_url":"https://x/1",
+         "is_active":true,"created_at":"2026-01-17T23:51:02Z"}]}"#;
+    const TWO_RESOURCES: &str = r#"{"count":2,"resources":[
+        {"id":1,"category":"video","title":"A","download_url":"https://x/1",
+         "is_active":true,"created_at":"2026-01-17T23:51:02Z"},
+        {"id":2,"category":"video","title":"B","download_url":"https://x/2",
+         "is_active":true,"created_at":"2026-01-10T23:51:02Z"}]}"#;
+
+    #[tokio::test]
+    async fn several_weeks_come_from_the_recent_endpoint() {
+        let client = reqwest::Client::new();
+        let base = serve_pages(
+            1,
+            &[
+                ("/api/resources/latest-week", ONE_RESOURCE),
+                ("/api/resources/recent?weeks=2", TWO_RESOURCES),
+            ],
+        );
+
+        let (response, _, _) = fetch_latest_week_with_fallback(&client, &base, None, 2, 10)
+            .await
+            .expect("recent weeks load");
+        assert_eq!(response.resources.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn missing_recent_endpoint_falls_back_to_latest_week() {
+        let client = reqwest::Client::new();
+        let base = serve_pages(2, &[("/api/resources/latest-week", ONE_RESOURCE)]);
+
+        let (response, _, _) = fetch_latest_week_with_fallback(&client, &base, None, 4, 10)
+            .await
+            .expect("latest week loads after the 404");
+        assert_eq!(response.resources.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn cancelled_fires_only_on_a_new_value() {
+        let (tx, rx) = watch::channel(0u64);
+        let wait = tokio::spawn(cancelled(Some(rx)));
+        tokio::task::yield_now().await;
+        assert!(!wait.is_finished());
+        tx.send_modify(|generation| *generation += 1);
+        tokio::time::timeout(Duration::from_secs(1), wait)
+            .await
+            .expect("cancel must resolve the wait")
+            .unwrap();
+
+        let never = tokio::time::timeout(Duration::from_millis(20), cancelled(None)).await;
+        assert!(never.is_err());
+    }
 }
@@ -3,18 +3,35 @@
 //! Manages a queue of download tasks, executing them sequentially or in parallel
 //! based on the configuration.
 
-use crate::models::{DownloadMode, Resource, WeekIdentifier};
+use crate::models::{DownloadMode, QueueOverflowPolicy, Resource, WeekIdentifier};
+use crate::services::download_events::{emit_phase, DownloadEvent, DownloadPhase};
+use crate::services::size_cache::{CachedSize, FileSizeCache};
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::sync::{Mutex, Notify};
+use tokio::sync::{Mutex, Notify, OwnedSemaphorePermit, Semaphore};
+
+/// Minimum time between two `overall-progress` events. Each download already
+/// throttles its own `download-progress`, but four parallel downloads would
+/// still fire the aggregate four times as often without this.
+const OVERALL_PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Quiet period after a config change before its rescan runs: a settings
+/// screen saving on every keystroke or slider step gets one scan per burst.
+const SCAN_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Quiet period after a completed download before its folder is opened
+/// (`open_folder_on_complete`), so a parallel burst opens it once.
+const OPEN_FOLDER_DEBOUNCE: Duration = Duration::from_secs(2);
 
 /// Queue service for managing downloads
 pub struct DownloadQueue {
     queue: Arc<Mutex<VecDeque<Resource>>>,
-    /// Track active downloads
-    active_count: Arc<AtomicUsize>,
+    /// Download slots; the permit count is the concurrency limit, kept in
+    /// step with `mode` by `update_mode`.
+    slots: Arc<SlotPool>,
     mode: Arc<Mutex<DownloadMode>>,
     /// Flag to ensure worker is started only once
     worker_started: Arc<AtomicBool>,
@@ -33,6 +50,188 @@ pub struct DownloadQueue {
     /// limit. The worker parks on `notified()` whenever the queue is empty or
     /// at the concurrency limit, so it no longer busy-waits.
     notify: Arc<Notify>,
+    /// Byte counts behind the `overall-progress` event.
+    progress: Arc<OverallProgressTracker>,
+    /// While set, the worker starts nothing new; in-flight downloads carry
+    /// on. Distinct from pausing a single download (its signal).
+    queue_paused: Arc<AtomicBool>,
+    /// Generation of the latest `request_scan`; a debounced scan only runs
+    /// if no newer request arrived during its quiet period.
+    scan_generation: Arc<AtomicU64>,
+    /// Generation of the latest `request_open_folder`, debounced the same way.
+    open_folder_generation: Arc<AtomicU64>,
+    /// Whether the last published status had queued or active work; the
+    /// busy → idle edge fires `all-downloads-complete` once.
+    busy: Arc<AtomicBool>,
+    /// Queued resources added by `add_task_priority` (manual downloads).
+    /// They stay at the front, ahead of the category-priority ordering that
+    /// `add_task` applies to everything else.
+    manual: Arc<std::sync::Mutex<HashSet<i64>>>,
+}
+
+/// Wait `delay`, then tell whether `generation` is still the latest one
+/// handed out by `counter` (nothing newer arrived meanwhile).
+async fn still_latest_after(counter: &AtomicU64, generation: u64, delay: Duration) -> bool {
+    tokio::time::sleep(delay).await;
+    counter.load(Ordering::SeqCst) == generation
+}
+
+/// Shape version of the `queue-status-changed` payload, bumped whenever a
+/// field is removed or changes meaning so the frontend can detect it.
+pub const QUEUE_STATUS_VERSION: u32 = 2;
+
+/// Payload of the `queue-status-changed` event. `active` keeps the bare ids
+/// of version 1; `active_items` carries the details.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct QueueStatusPayload {
+    pub version: u32,
+    pub queued: Vec<QueuedItem>,
+    pub active: Vec<i64>,
+    pub active_items: Vec<ActiveItem>,
+}
+
+/// One waiting download, `position` 1-based. `file_size` is the cached size
+/// of the URL that will be downloaded, `None` when unknown.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct QueuedItem {
+    pub id: i64,
+    pub position: usize,
+    pub title: String,
+    pub category: String,
+    pub file_size: Option<u64>,
+}
+
+/// One running download. Title and category come from the resource list and
+/// are `None` if it no longer contains the resource; the byte counts are
+/// `None` until the download reports its first progress.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct ActiveItem {
+    pub id: i64,
+    pub title: Option<String>,
+    pub category: Option<String>,
+    pub file_size: Option<u64>,
+    pub downloaded_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+}
+
+/// Payload of the `overall-progress` event: bytes received so far across
+/// all active downloads against the bytes expected for active and queued
+/// ones. `percent` is 0 while nothing is pending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct OverallProgress {
+    pub downloaded_bytes: u64,
+    pub total_bytes: u64,
+    pub percent: u8,
+}
+
+#[derive(Debug, Default)]
+struct ProgressBook {
+    /// `(downloaded, total)` last reported by each active download.
+    reported: HashMap<i64, (u64, u64)>,
+    /// Cached size of each active download, standing in until it reports.
+    active_estimates: HashMap<i64, u64>,
+    /// Cached sizes of the queued (not yet started) downloads.
+    queued_bytes: u64,
+    last_emit: Option<Instant>,
+}
+
+impl ProgressBook {
+    fn overall(&self) -> OverallProgress {
+        let mut downloaded_bytes = 0u64;
+        let mut total_bytes = self.queued_bytes;
+        for (downloaded, total) in self.reported.values() {
+            downloaded_bytes = downloaded_bytes.saturating_add(*downloaded);
+            total_bytes = total_bytes.saturating_add(*total);
+        }
+        for (id, estimate) in &self.active_estimates {
+            if !self.reported.contains_key(id) {
+                total_bytes = total_bytes.saturating_add(*estimate);
+            }
+        }
+        let percent = if total_bytes == 0 {
+            0
+        } else {
+            (u128::from(downloaded_bytes) * 100 / u128::from(total_bytes)).min(100) as u8
+        };
+        OverallProgress {
+            downloaded_bytes,
+            total_bytes,
+            percent,
+        }
+    }
+}
+
+/// Aggregates per-download progress into `overall-progress`, for a single
+/// global progress bar. Fed by the download loop (`report`), the pending
+/// estimate (`set_pending`) and the worker when a download ends (`finish`).
+#[derive(Debug, Default)]
+pub struct OverallProgressTracker {
+    book: std::sync::Mutex<ProgressBook>,
+}
+
+impl OverallProgressTracker {
+    fn book(&self) -> std::sync::MutexGuard<'_, ProgressBook> {
+        self.book
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Record a download's byte counts; emits `overall-progress` at most
+    /// every [`OVERALL_PROGRESS_INTERVAL`].
+    pub fn report(&self, app: &AppHandle, id: i64, downloaded: u64, total: u64) {
+        let payload = {
+            let mut book = self.book();
+            book.reported.insert(id, (downloaded, total));
+            let now = Instant::now();
+            if book
+                .last_emit
+                .is_some_and(|last| now.duration_since(last) < OVERALL_PROGRESS_INTERVAL)
+            {
+                return;
+            }
+            book.last_emit = Some(now);
+            book.overall()
+        };
+        emit_overall_progress(app, payload);
+    }
+
+    /// `(downloaded, total)` last reported by each active download.
+    fn reported(&self) -> HashMap<i64, (u64, u64)> {
+        self.book().reported.clone()
+    }
+
+    /// Replace the cached-size estimates for queued and active downloads.
+    fn set_pending(&self, queued_bytes: u64, active_estimates: HashMap<i64, u64>) {
+        let mut book = self.book();
+        book.queued_bytes = queued_bytes;
+        book.active_estimates = active_estimates;
+    }
+
+    /// Drop a download that ended (any outcome) and emit the new aggregate
+    /// right away, so the bar doesn't stall on the throttle at the end.
+    fn finish(&self, app: &AppHandle, id: i64) {
+        let payload = {
+            let mut book = self.book();
+            book.reported.remove(&id);
+            book.active_estimates.remove(&id);
+            book.last_emit = Some(Instant::now());
+            book.overall()
+        };
+        emit_overall_progress(app, payload);
+    }
+}
+
+fn set_queue_paused_status(app: &AppHandle, paused: bool) {
+    match app.state::<crate::commands::AppState>().status.write() {
+        Ok(mut status) => status.queue_paused = paused,
+        Err(e) => tracing::error!("Queue pause: status lock poisoned: {}", e),
+    }
+}
+
+fn emit_overall_progress(app: &AppHandle, payload: OverallProgress) {
+    if let Err(e) = app.emit("overall-progress", payload) {
+        tracing::error!("Failed to emit overall-progress: {:?}", e);
+    }
 }
 
 /// Pure enqueue guard (A2): a resource may be queued only if it is neither
@@ -42,6 +241,117 @@ fn can_enqueue(queue: &VecDeque<Resource>, active_ids: &[i64], id: i64) -> bool
     !active_ids.contains(&id) && !queue.iter().any(|r| r.id == id)
 }
 
+/// Result of [`make_room`].
+#[derive(Debug, PartialEq)]
+enum Room {
+    /// Below the limit: nothing to do.
+    Fits,
+    /// Full, and the policy keeps the queue as it is.
+    Full,
+    /// The item with this id was dropped to make room.
+    Evicted(i64),
+}
+
+/// Make room for one more item in `queue`, capped at `limit`. A priority
+/// add evicts the tail (the item that would start last); otherwise `policy`
+/// decides between refusing the newcomer and dropping the oldest item.
+fn make_room(
+    queue: &mut VecDeque<Resource>,
+    limit: Option<usize>,
+    policy: QueueOverflowPolicy,
+    priority: bool,
+) -> Room {
+    match limit {
+        Some(limit) if queue.len() >= limit => {}
+        _ => return Room::Fits,
+    }
+    let evicted = if priority {
+        queue.pop_back()
+    } else {
+        match policy {
+            QueueOverflowPolicy::Reject => None,
+            QueueOverflowPolicy::DropOldest => queue.pop_front(),
+        }
+    };
+    evicted.map_or(Room::Full, |r| Room::Evicted(r.id))
+}
+
+/// Payload of the `queue-overflow` event.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct QueueOverflow {
+    pub limit: usize,
+    /// The resource that was not queued (`Reject`).
+    pub rejected_id: Option<i64>,
+    /// The queued resource dropped to make room (`DropOldest`, priority).
+    pub evicted_id: Option<i64>,
+}
+
+/// Rank of `category` in `priority` (lower starts sooner); unlisted
+/// categories rank after every listed one.
+fn category_rank(priority: &[String], category: &str) -> usize {
+    priority
+        .iter()
+        .position(|c| c == category)
+        .unwrap_or(priority.len())
+}
+
+/// Where `add_task` puts `resource` in `queue`: after the leading run of
+/// `manual` downloads, before the first item of a later-ranked category and
+/// after those of the same rank (stable). An empty `priority` is plain FIFO.
+fn insert_position(
+    queue: &VecDeque<Resource>,
+    resource: &Resource,
+    priority: &[String],
+    manual: &HashSet<i64>,
+) -> usize {
+    let rank = category_rank(priority, &resource.category);
+    let manual_front = queue
+        .iter()
+        .take_while(|queued| manual.contains(&queued.id))
+        .count();
+    queue
+        .iter()
+        .enumerate()
+        .skip(manual_front)
+        .find(|(_, queued)| category_rank(priority, &queued.category) > rank)
+        .map_or(queue.len(), |(index, _)| index)
+}
+
+/// `AppConfig::download_window`; `None` (any time) if the config can't be
+/// read.
+fn download_window(app: &AppHandle) -> Option<crate::models::DownloadWindow> {
+    match app.state::<crate::commands::AppState>().config.read() {
+        Ok(config) => config.download_window,
+        Err(e) => {
+            tracing::error!("Download window: config lock poisoned: {}", e);
+            None
+        }
+    }
+}
+
+/// `AppConfig::category_priority`; empty (FIFO) if the config can't be read.
+fn category_priority(app: &AppHandle) -> Vec<String> {
+    match app.state::<crate::commands::AppState>().config.read() {
+        Ok(config) => config.category_priority.clone(),
+        Err(e) => {
+            tracing::error!("Queue priority: config lock poisoned: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// `max_queue_size` and overflow policy from the live config; unbounded if
+/// the config lock is poisoned.
+fn overflow_settings(app: &AppHandle) -> (Option<usize>, QueueOverflowPolicy) {
+    match app.state::<crate::commands::AppState>().config.read() {
+        Ok(config) => (config.max_queue_size, config.queue_overflow_policy),
+        Err(e) => {
+            tracing::error!("Queue limit: config lock poisoned: {}", e);
+            (None, QueueOverflowPolicy::default())
+        }
+    }
+}
+
 /// Pure queue removal (A5): drops `id` from `queue` in place and reports
 /// whether anything was actually removed. Free-standing for unit testing
 /// without an `AppHandle`.
@@ -60,6 +370,139 @@ fn concurrency_limit(mode: &DownloadMode) -> usize {
     }
 }
 
+/// Download slots. The semaphore holds exactly as many permits as the
+/// current concurrency limit, so claiming a permit *is* the capacity check.
+///
+/// Shrinking the limit never interrupts running downloads: permits that are
+/// free get retired immediately, the rest are recorded as `debt` and retired
+/// one by one as downloads finish instead of being handed back.
+struct SlotPool {
+    semaphore: Arc<Semaphore>,
+    /// Permits still to retire after a limit decrease.
+    debt: AtomicUsize,
+    /// Downloads currently holding a [`SlotGuard`].
+    active: AtomicUsize,
+    notify: Arc<Notify>,
+}
+
+impl SlotPool {
+    fn new(limit: usize, notify: Arc<Notify>) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            debt: AtomicUsize::new(0),
+            active: AtomicUsize::new(0),
+            notify,
+        }
+    }
+
+    /// A free permit, if the limit allows another download right now.
+    fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.semaphore.clone().try_acquire_owned().ok()
+    }
+
+    /// Give a permit back, or retire it if a shrink is still owed one.
+    fn release(&self, permit: OwnedSemaphorePermit) {
+        let owed = self
+            .debt
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |d| d.checked_sub(1))
+            .is_ok();
+        if owed {
+            permit.forget();
+        } else {
+            drop(permit);
+        }
+    }
+
+    /// Move the limit from `from` to `to` permits. Growing first cancels any
+    /// outstanding debt, then adds what's left.
+    fn resize(&self, from: usize, to: usize) {
+        if to > from {
+            let grow = to - from;
+            let previous_debt = self
+                .debt
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |d| {
+                    Some(d.saturating_sub(grow))
+                })
+                .unwrap_or(0);
+            self.semaphore.add_permits(grow - previous_debt.min(grow));
+        } else if to < from {
+            let shrink = from - to;
+            let retired = self.semaphore.forget_permits(shrink);
+            self.debt.fetch_add(shrink - retired, Ordering::SeqCst);
+        }
+    }
+
+    fn active(&self) -> usize {
+        self.active.load(Ordering::SeqCst)
+    }
+}
+
+/// A download's claim on a worker slot. Moved into the supervisor task and
+/// dropped when it finishes; because `Drop` also runs while unwinding, a
+/// panicking task can't leak its slot. Releases the permit *before* waking
+/// the worker, so the woken worker sees the freed slot.
+struct SlotGuard {
+    permit: Option<OwnedSemaphorePermit>,
+    pool: Arc<SlotPool>,
+}
+
+impl SlotGuard {
+    fn new(permit: OwnedSemaphorePermit, pool: Arc<SlotPool>) -> Self {
+        pool.active.fetch_add(1, Ordering::SeqCst);
+        Self {
+            permit: Some(permit),
+            pool,
+        }
+    }
+}
+
+impl Drop for SlotGuard {
+    fn drop(&mut self) {
+        self.pool.active.fetch_sub(1, Ordering::SeqCst);
+        if let Some(permit) = self.permit.take() {
+            self.pool.release(permit);
+        }
+        self.pool.notify.notify_one();
+    }
+}
+
+/// Log, record (`AppState::download_errors`) and emit `download-failed` for
+/// `id`. Every way out of a started download ends in either this or
+/// `download-complete` (or the explicit paused/cancelled events), so the UI
+/// never sees an item just vanish.
+fn emit_download_failed(app: &AppHandle, id: i64, error: &str) {
+    tracing::error!("Download failed for resource {}: {}", id, error);
+    app.state::<crate::commands::AppState>()
+        .record_download_error(id, error);
+    if let Err(e) = app.emit(
+        "download-failed",
+        serde_json::json!({"id": id, "error": error}),
+    ) {
+        tracing::error!("Failed to emit download-failed for {}: {:?}", id, e);
+    }
+    DownloadEvent::new(id, DownloadPhase::Failed)
+        .with_error(error)
+        .emit(app);
+}
+
+/// `resource` with its download URLs normalized
+/// (`download::normalize_resource_urls`), or `None` after emitting
+/// `download-failed` with the reason, so a malformed URL never reaches the
+/// queue.
+fn checked_urls(app: &AppHandle, resource: Resource) -> Option<Resource> {
+    let id = resource.id;
+    match crate::services::download::normalize_resource_urls(
+        resource,
+        &crate::constants::api_base_url(),
+    ) {
+        Ok(resource) => Some(resource),
+        Err(e) => {
+            emit_download_failed(app, id, &e.to_string());
+            None
+        }
+    }
+}
+
 /// Pure savings computation (A1): bytes saved by downloading the optimized
 /// variant instead of the original. `None` whenever either size is unknown,
 /// or when the "original" doesn't actually turn out larger (a stale/wrong
@@ -78,47 +521,43 @@ fn compute_saved_bytes(original_bytes: Option<u64>, optimized_bytes: Option<u64>
 /// a network request — a cache miss returns `None` rather than blocking the
 /// event on a HEAD request; see `resolve_original_size_bytes` for the
 /// network-fallback used by the detached `savings-resolved` follow-up.
-/// Filters out the `u64::MAX` negative-cache sentinel (see
-/// `AppState::file_size_cache`'s doc comment), since that means "never
-/// successfully HEAD-ed", not "known to be 0 bytes".
+/// A negative-cache entry (failed HEAD) counts as unknown, not 0 bytes.
 fn cached_original_size_bytes(app: &AppHandle, url: &str) -> Option<u64> {
     let state = app.state::<crate::commands::AppState>();
     state
         .file_size_cache
-        .read()
+        .write()
         .ok()
-        .and_then(|cache| cache.get(url).copied())
-        .filter(|&size| size != u64::MAX)
+        .and_then(|mut cache| cache.get(url))
+        .and_then(CachedSize::known)
 }
 
 /// Best-effort resolution of the *original* (non-optimized) file's size, for
 /// A1 savings reporting.
 ///
-/// Checks `AppState::file_size_cache` first (filtering out its `u64::MAX`
-/// negative-cache sentinel — see the field's doc comment — since that means
-/// "never successfully HEAD-ed", not "known to be 0 bytes"). On a miss, falls
+/// Checks `AppState::file_size_cache` first (a negative-cache entry there
+/// means "never successfully HEAD-ed", not "known to be 0 bytes"). On a miss, falls
 /// back to a HEAD request bounded by a short timeout so a slow/unreachable
 /// origin can never stall the caller. Only a successful outcome is written
 /// back to the cache: unlike `get_file_size`, this path deliberately does NOT
 /// negative-cache a failure here, since a transient blip shouldn't poison a
 /// future on-demand lookup (e.g. the resource detail view opening moments
 /// later). Called only from the DETACHED `savings-resolved` background task
-/// (see `start_worker`), never inline in the download body, so its up-to-5s
-/// latency never delays `download-complete` or holds a worker slot.
+/// (see `AppHandle`'s `WorkerHost::run`), never inline in the download body,
+/// so its up-to-5s latency never delays `download-complete` or holds a
+/// worker slot.
 async fn resolve_original_size_bytes(app: &AppHandle, url: &str) -> Option<u64> {
     let state = app.state::<crate::commands::AppState>();
 
-    if let Ok(cache) = state.file_size_cache.read() {
-        if let Some(&size) = cache.get(url) {
-            if size != u64::MAX {
-                return Some(size);
-            }
+    if let Ok(mut cache) = state.file_size_cache.write() {
+        if let Some(CachedSize::Known(size)) = cache.get(url) {
+            return Some(size);
         }
     }
 
     let response = tokio::time::timeout(
         std::time::Duration::from_secs(5),
-        state.shared_http_client.head(url).send(),
+        state.http_client().head(url).send(),
     )
     .await
     .ok()?
@@ -141,6 +580,523 @@ async fn resolve_original_size_bytes(app: &AppHandle, url: &str) -> Option<u64>
     Some(size)
 }
 
+/// Whether `scan_and_queue` should consider `resource` at all: its category
+/// is enabled for auto-download and the server hasn't deactivated it
+/// (`is_active == false` means withdrawn/hidden upstream).
+pub(crate) fn is_auto_download_candidate(resource: &Resource, categories: &[String]) -> bool {
+    resource.is_active && categories.contains(&resource.category)
+}
+
+/// Sum of the cached sizes of `resources`' effective download URLs, skipping
+/// unknown and failed (negative-cached) sizes. Saturates rather than
+/// overflowing.
+pub(crate) fn estimate_pending_bytes<'a>(
+    resources: impl IntoIterator<Item = &'a Resource>,
+    prefer_optimized: bool,
+    size_cache: &FileSizeCache,
+) -> u64 {
+    resources
+        .into_iter()
+        .filter_map(|r| size_cache.known_size(r.get_effective_download_url(prefer_optimized)))
+        .fold(0, u64::saturating_add)
+}
+
+/// Roll a queue snapshot into `AppStatus::pending_downloads`/`pending_bytes`.
+/// Active downloads are only known by id here, so their sizes are looked up
+/// through the current resource list. Synchronous: the std locks taken here
+/// are all released before returning.
+fn update_pending_status(app: &AppHandle, queue: &VecDeque<Resource>, active: &[i64]) {
+    let state = app.state::<crate::commands::AppState>();
+    let prefer_optimized = match state.config.read() {
+        Ok(config) => config.prefer_optimized,
+        Err(e) => {
+            tracing::error!("Pending status: config lock poisoned: {}", e);
+            return;
+        }
+    };
+    let (queued_bytes, active_estimates) =
+        match (state.resources.read(), state.file_size_cache.read()) {
+            (Ok(resources), Ok(cache)) => {
+                let active_estimates: HashMap<i64, u64> = resources
+                    .iter()
+                    .filter(|r| active.contains(&r.id))
+                    .filter_map(|r| {
+                        let size = estimate_pending_bytes([r], prefer_optimized, &cache);
+                        (size > 0).then_some((r.id, size))
+                    })
+                    .collect();
+                (
+                    estimate_pending_bytes(queue.iter(), prefer_optimized, &cache),
+                    active_estimates,
+                )
+            }
+            _ => {
+                tracing::error!("Pending status: resources or size cache lock poisoned");
+                return;
+            }
+        };
+    let pending_bytes = active_estimates
+        .values()
+        .fold(queued_bytes, |sum, size| sum.saturating_add(*size));
+    state
+        .download_queue
+        .progress()
+        .set_pending(queued_bytes, active_estimates);
+    match state.status.write() {
+        Ok(mut status) => {
+            status.pending_downloads = queue.len() + active.len();
+            status.pending_bytes = pending_bytes;
+        }
+        Err(e) => tracing::error!("Pending status: status lock poisoned: {}", e),
+    };
+}
+
+/// Emit `queue-status-changed` for a queue/active snapshot and refresh the
+/// pending figures in `AppStatus` from the same snapshot, so the event and
+/// `get_status` never disagree. Shared by the producers
+/// (`emit_queue_status`) and the worker.
+fn publish_queue_status(app: &AppHandle, queue: &VecDeque<Resource>, active: &[i64]) {
+    let state = app.state::<crate::commands::AppState>();
+    let prefer_optimized = state
+        .config
+        .read()
+        .map(|config| config.prefer_optimized)
+        .unwrap_or_default();
+    let reported = state.download_queue.progress().reported();
+    let payload = match (state.resources.read(), state.file_size_cache.read()) {
+        (Ok(resources), Ok(cache)) => build_queue_status(
+            queue,
+            active,
+            &resources,
+            &cache,
+            prefer_optimized,
+            &reported,
+        ),
+        _ => {
+            tracing::error!("Queue status: resources or size cache lock poisoned");
+            build_queue_status(
+                queue,
+                active,
+                &[],
+                &FileSizeCache::new(),
+                prefer_optimized,
+                &reported,
+            )
+        }
+    };
+
+    if let Err(e) = app.emit("queue-status-changed", payload) {
+        tracing::error!("Failed to emit queue-status-changed: {:?}", e);
+    }
+
+    let busy = !queue.is_empty() || !active.is_empty();
+    if became_idle(&state.download_queue.busy, busy) {
+        tracing::info!("All downloads complete");
+        if let Err(e) = app.emit("all-downloads-complete", ()) {
+            tracing::error!("Failed to emit all-downloads-complete: {:?}", e);
+        }
+    }
+
+    update_pending_status(app, queue, active);
+}
+
+/// Record `busy` in `flag` and tell whether this is the busy → idle edge.
+/// Repeated idle updates return `false`, so the event fires once per batch
+/// (a batch emptied by cancelling counts as done too).
+fn became_idle(flag: &AtomicBool, busy: bool) -> bool {
+    flag.swap(busy, Ordering::SeqCst) && !busy
+}
+
+/// Pure assembly of the `queue-status-changed` payload. Queued items carry
+/// their own resource; active ones are looked up in `resources` by id.
+fn build_queue_status(
+    queue: &VecDeque<Resource>,
+    active: &[i64],
+    resources: &[Resource],
+    size_cache: &FileSizeCache,
+    prefer_optimized: bool,
+    reported: &HashMap<i64, (u64, u64)>,
+) -> QueueStatusPayload {
+    let queued = queue
+        .iter()
+        .enumerate()
+        .map(|(i, r)| QueuedItem {
+            id: r.id,
+            position: i + 1,
+            title: r.title.clone(),
+            category: r.category.clone(),
+            file_size: size_cache.known_size(r.get_effective_download_url(prefer_optimized)),
+        })
+        .collect();
+
+    let active_items = active
+        .iter()
+        .map(|&id| {
+            let resource = resources.iter().find(|r| r.id == id);
+            let progress = reported.get(&id);
+            ActiveItem {
+                id,
+                title: resource.map(|r| r.title.clone()),
+                category: resource.map(|r| r.category.clone()),
+                file_size: resource.and_then(|r| {
+                    size_cache.known_size(r.get_effective_download_url(prefer_optimized))
+                }),
+                downloaded_bytes: progress.map(|(downloaded, _)| *downloaded),
+                total_bytes: progress.map(|(_, total)| *total),
+            }
+        })
+        .collect();
+
+    QueueStatusPayload {
+        version: QUEUE_STATUS_VERSION,
+        queued,
+        active: active.to_vec(),
+        active_items,
+    }
+}
+
+/// What the queue worker needs beyond the queue's own bookkeeping: the
+/// offline and download-window gates, running one download, and reporting.
+/// Implemented by `AppHandle`; the tests drive the worker with a stand-in
+/// whose downloads finish on cue.
+trait WorkerHost: Clone + Send + Sync + 'static {
+    /// Offline mode: start nothing new.
+    fn is_offline(&self) -> bool;
+    /// Time until the download window opens; `None` while downloads may run.
+    fn window_wait(&self) -> Option<Duration>;
+    /// Publish `queue-status-changed`.
+    fn publish_status(&self, queue: &VecDeque<Resource>, active: &[i64]);
+    /// Download `resource`, reporting its outcome.
+    fn run(&self, resource: Resource) -> impl std::future::Future<Output = ()> + Send + 'static;
+    /// The download of `id` panicked.
+    fn panicked(&self, id: i64);
+    /// `id` left the active set: drop its per-download state.
+    fn finished(&self, id: i64);
+    /// Take `id` out of the `overall-progress` totals.
+    fn progress_finished(&self, progress: &OverallProgressTracker, id: i64);
+}
+
+impl WorkerHost for AppHandle {
+    fn is_offline(&self) -> bool {
+        self.state::<crate::commands::AppState>().is_offline()
+    }
+
+    fn window_wait(&self) -> Option<Duration> {
+        download_window(self).and_then(|window| window.wait_until_open(chrono::Local::now().time()))
+    }
+
+    fn publish_status(&self, queue: &VecDeque<Resource>, active: &[i64]) {
+        publish_queue_status(self, queue, active);
+    }
+
+    fn run(&self, resource: Resource) -> impl std::future::Future<Output = ()> + Send + 'static {
+        let app_clone = self.clone();
+        async move {
+            // Execute download
+            // Resolve state at the top level of the task
+            let state = app_clone.state::<crate::commands::AppState>();
+            // A new attempt (first try or retry) supersedes
+            // the previous failure; success leaves it cleared.
+            state.clear_download_error(resource.id);
+
+            let config = match crate::commands::get_config(state) {
+                Ok(config) => config,
+                Err(e) => {
+                    emit_download_failed(
+                        &app_clone,
+                        resource.id,
+                        &format!("could not read configuration: {}", e.message),
+                    );
+                    return;
+                }
+            };
+            // `dest_dir` is created by `download_resource`; a
+            // failure comes back as `CreateDirectoryFailed`.
+            let (Some(root), Some(dest_dir)) = (
+                config.download_root(&resource.category),
+                crate::services::download::destination_dir(&config, &resource),
+            ) else {
+                emit_download_failed(&app_clone, resource.id, "work directory not configured");
+                return;
+            };
+            // Never `create_dir_all` below an unplugged drive's
+            // mount point: that would download onto the
+            // internal disk instead. A category override is
+            // checked without touching the work directory's
+            // availability status.
+            let available = if config.category_destination(&resource.category).is_some() {
+                crate::services::workdir::is_available(root)
+            } else {
+                crate::services::workdir::refresh_availability(&app_clone, root)
+            };
+            if !available {
+                emit_download_failed(&app_clone, resource.id, "work directory unavailable");
+                return;
+            }
+            let download_service = crate::services::DownloadService::with_client(
+                app_clone
+                    .state::<crate::commands::AppState>()
+                    .download_client(),
+            )
+            .with_staging(config.stage_incomplete_downloads);
+            let prefer_optimized = config.prefer_optimized;
+
+            // Register signal
+            let signal = std::sync::Arc::new(std::sync::atomic::AtomicU8::new(
+                crate::services::download::STATUS_RUNNING,
+            ));
+
+            {
+                let signal_state = app_clone.state::<crate::commands::AppState>();
+                let signals_res = signal_state.download_signals.write();
+                if let Ok(mut signals) = signals_res {
+                    signals.insert(resource.id, signal.clone());
+                }
+            }
+
+            tracing::info!("Queue starting download: {}", resource.title);
+
+            // Emit download started event to frontend
+            emit_phase(&app_clone, resource.id, DownloadPhase::Started);
+            if let Err(e) = app_clone.emit("download-started", resource.id) {
+                tracing::error!(
+                    "Failed to emit download-started event for {}: {:?}",
+                    resource.id,
+                    e
+                );
+            } else {
+                tracing::trace!(
+                    "Emitted download-started event for resource {}",
+                    resource.id
+                );
+            }
+
+            let started_at = std::time::Instant::now();
+            match download_service
+                .download_resource(
+                    &resource,
+                    &dest_dir,
+                    Some(&app_clone),
+                    Some(signal),
+                    prefer_optimized,
+                )
+                .await
+            {
+                Ok((path, hash)) => {
+                    tracing::info!(
+                        "Download completed successfully: {} -> {:?} (hash: {})",
+                        resource.title,
+                        path,
+                        hash
+                    );
+
+                    // The frontend needs to know whether the
+                    // *actually downloaded* URL was an optimized
+                    // variant (auto-downloads never enter the
+                    // frontend's activeDownloads map, so it cannot
+                    // derive this itself). `resource` here is the
+                    // effective resource (manual variant picks
+                    // included), so this also covers non-default
+                    // picks.
+                    let optimized = resource.get_effective_download_url(prefer_optimized)
+                        != resource.download_url;
+
+                    // A1: savings are only meaningful when the
+                    // optimized variant is what actually landed
+                    // on disk — a non-optimized download has
+                    // nothing to compare against. Read before
+                    // `record_downloaded_file` moves `path`.
+                    let optimized_bytes = if optimized {
+                        tokio::fs::metadata(&path).await.ok().map(|m| m.len())
+                    } else {
+                        None
+                    };
+
+                    let downloaded_bytes = match optimized_bytes {
+                        Some(bytes) => Some(bytes),
+                        None => tokio::fs::metadata(&path).await.ok().map(|m| m.len()),
+                    };
+                    // Read before `record_downloaded_file`
+                    // moves `path`.
+                    let open_dir = if config.open_folder_on_complete {
+                        path.parent().map(std::path::Path::to_path_buf)
+                    } else {
+                        None
+                    };
+
+                    crate::services::history::record_download(
+                        &app_clone,
+                        crate::models::DownloadHistoryEntry {
+                            resource_id: resource.id,
+                            title: resource.title.clone(),
+                            week: resource.week(),
+                            bytes: downloaded_bytes,
+                            duration_secs: started_at.elapsed().as_secs_f64(),
+                            completed_at: chrono::Utc::now(),
+                            hash: hash.clone(),
+                        },
+                    );
+
+                    // adr-0007 step 2: record the file in the
+                    // errata registry so a later poll can
+                    // detect it being superseded.
+                    crate::services::record_downloaded_file(
+                        &app_clone,
+                        &resource,
+                        path,
+                        prefer_optimized,
+                    );
+
+                    // A1: the original size is only ever read
+                    // from the shared HEAD-size cache here — NOT
+                    // fetched over the network. A blocking HEAD
+                    // request (up to 5s) used to run inline in
+                    // this task body, delaying both the
+                    // `download-complete` event and the worker
+                    // slot freeing (the slot isn't released
+                    // until this body returns). When
+                    // the size isn't cached yet, the event below
+                    // reports `original_bytes`/`saved_bytes` as
+                    // `null` and a DETACHED task (spawned further
+                    // down) resolves it in the background and
+                    // emits `savings-resolved` once it lands.
+                    let original_bytes = if optimized {
+                        cached_original_size_bytes(&app_clone, &resource.download_url)
+                    } else {
+                        None
+                    };
+
+                    let saved_bytes = compute_saved_bytes(original_bytes, optimized_bytes);
+
+                    // A2: fold this download's savings (if any)
+                    // into the persistent running total; when
+                    // there's nothing to add, still report its
+                    // current value so the UI's counter never
+                    // goes stale relative to the backend. Only
+                    // counted here when already known — a `None`
+                    // saved_bytes with `optimized` true is folded
+                    // in later by the detached resolution task
+                    // below instead, never both (no double count).
+                    let total_saved_bytes = match saved_bytes {
+                        Some(bytes) => crate::commands::add_saved_bytes(&app_clone, bytes),
+                        None => crate::commands::current_saved_bytes(
+                            &app_clone.state::<crate::commands::AppState>(),
+                        ),
+                    };
+
+                    let _ = app_clone.emit(
+                        "download-complete",
+                        serde_json::json!({
+                            "id": resource.id,
+                            "optimized": optimized,
+                            "optimized_bytes": optimized_bytes,
+                            "original_bytes": original_bytes,
+                            "saved_bytes": saved_bytes,
+                            "total_saved_bytes": total_saved_bytes,
+                        }),
+                    );
+                    DownloadEvent::new(resource.id, DownloadPhase::Completed)
+                        .with_progress(Some(100), downloaded_bytes, downloaded_bytes)
+                        .emit(&app_clone);
+                    crate::services::notifications::notify_download_complete(&app_clone, &resource);
+                    if let Some(dir) = open_dir {
+                        app_clone
+                            .state::<crate::commands::AppState>()
+                            .download_queue
+                            .request_open_folder(app_clone.clone(), dir);
+                    }
+
+                    // Original size wasn't cached: resolve it in
+                    // a task detached from this body (never
+                    // awaited here), so the up-to-5s HEAD request
+                    // cannot delay the worker slot freeing above.
+                    // Needs optimized_bytes too (already known) to
+                    // ever compute a saving.
+                    if optimized && original_bytes.is_none() {
+                        if let Some(optimized_bytes) = optimized_bytes {
+                            let app_detached = app_clone.clone();
+                            let download_url = resource.download_url.clone();
+                            let resource_id = resource.id;
+                            tauri::async_runtime::spawn(async move {
+                                let Some(resolved_original) =
+                                    resolve_original_size_bytes(&app_detached, &download_url).await
+                                else {
+                                    return;
+                                };
+                                let resolved_saved = compute_saved_bytes(
+                                    Some(resolved_original),
+                                    Some(optimized_bytes),
+                                );
+                                // Counted exactly once: the
+                                // immediate emission above only
+                                // added to the total when
+                                // saved_bytes was already known,
+                                // which is not the branch we're
+                                // in (original_bytes was None).
+                                let resolved_total = match resolved_saved {
+                                    Some(bytes) => {
+                                        crate::commands::add_saved_bytes(&app_detached, bytes)
+                                    }
+                                    None => crate::commands::current_saved_bytes(
+                                        &app_detached.state::<crate::commands::AppState>(),
+                                    ),
+                                };
+                                let _ = app_detached.emit(
+                                    "savings-resolved",
+                                    serde_json::json!({
+                                        "id": resource_id,
+                                        "saved_bytes": resolved_saved,
+                                        "original_bytes": resolved_original,
+                                        "total_saved_bytes": resolved_total,
+                                    }),
+                                );
+                            });
+                        }
+                    }
+                }
+                Err(crate::error::DownloadError::Paused) => {
+                    tracing::info!("Download paused: {}", resource.title);
+                    let _ = app_clone.emit("download-paused", resource.id);
+                    emit_phase(&app_clone, resource.id, DownloadPhase::Paused);
+                }
+                Err(crate::error::DownloadError::Cancelled) => {
+                    tracing::info!("Download cancelled: {}", resource.title);
+                    let _ = app_clone.emit("download-cancelled", resource.id);
+                    emit_phase(&app_clone, resource.id, DownloadPhase::Cancelled);
+                }
+                Err(crate::error::DownloadError::Stopped) => {
+                    tracing::info!("Download stopped: {}", resource.title);
+                    let _ = app_clone.emit("download-stopped", resource.id);
+                    emit_phase(&app_clone, resource.id, DownloadPhase::Stopped);
+                }
+                Err(e) => {
+                    emit_download_failed(&app_clone, resource.id, &e.to_string());
+                }
+            }
+        }
+    }
+
+    fn panicked(&self, id: i64) {
+        emit_download_failed(self, id, "internal error");
+    }
+
+    fn finished(&self, id: i64) {
+        // Guaranteed signal removal: the body registers the signal, so a
+        // panic before its own cleanup would leak it in `download_signals`
+        // without this.
+        let signal_state = self.state::<crate::commands::AppState>();
+        let signals_res = signal_state.download_signals.write();
+        if let Ok(mut signals) = signals_res {
+            signals.remove(&id);
+        }
+        signal_state.clear_active_progress(id);
+    }
+
+    fn progress_finished(&self, progress: &OverallProgressTracker, id: i64) {
+        progress.finish(self, id);
+    }
+}
+
 impl Default for DownloadQueue {
     fn default() -> Self {
         Self::new()
@@ -150,15 +1106,76 @@ impl Default for DownloadQueue {
 impl DownloadQueue {
     pub fn new() -> Self {
         // Default to Queue (1 concurrent) initially, updated via config
+        let notify = Arc::new(Notify::new());
         Self {
             queue: Arc::new(Mutex::new(VecDeque::new())),
-            active_count: Arc::new(AtomicUsize::new(0)),
+            slots: Arc::new(SlotPool::new(
+                concurrency_limit(&DownloadMode::Queue),
+                notify.clone(),
+            )),
             mode: Arc::new(Mutex::new(DownloadMode::Queue)),
             worker_started: Arc::new(AtomicBool::new(false)),
             active_ids: Arc::new(Mutex::new(Vec::new())),
             active_weeks: Arc::new(Mutex::new(HashMap::new())),
-            notify: Arc::new(Notify::new()),
+            notify,
+            progress: Arc::new(OverallProgressTracker::default()),
+            queue_paused: Arc::new(AtomicBool::new(false)),
+            scan_generation: Arc::new(AtomicU64::new(0)),
+            open_folder_generation: Arc::new(AtomicU64::new(0)),
+            busy: Arc::new(AtomicBool::new(false)),
+            manual: Arc::new(std::sync::Mutex::new(HashSet::new())),
+        }
+    }
+
+    fn manual(&self) -> std::sync::MutexGuard<'_, HashSet<i64>> {
+        self.manual
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Whether the worker is currently holding back queued items.
+    pub fn is_paused(&self) -> bool {
+        self.queue_paused.load(Ordering::SeqCst)
+    }
+
+    /// Set the paused flag; returns whether it changed. Resuming wakes the
+    /// worker so queued items start without waiting for another event.
+    fn set_paused(&self, paused: bool) -> bool {
+        let changed = self.queue_paused.swap(paused, Ordering::SeqCst) != paused;
+        if changed && !paused {
+            self.notify.notify_one();
         }
+        changed
+    }
+
+    /// Stop starting queued downloads (in-flight ones continue) and emit
+    /// `queue-paused`.
+    pub fn pause_queue(&self, app: &AppHandle) {
+        if self.set_paused(true) {
+            tracing::info!("Download queue paused");
+            set_queue_paused_status(app, true);
+            let _ = app.emit("queue-paused", ());
+        }
+    }
+
+    /// Let the worker start queued downloads again and emit `queue-resumed`.
+    pub fn resume_queue(&self, app: &AppHandle) {
+        if self.set_paused(false) {
+            tracing::info!("Download queue resumed");
+            set_queue_paused_status(app, false);
+            let _ = app.emit("queue-resumed", ());
+        }
+    }
+
+    /// Wake the worker so it re-checks whether it may start downloads (e.g.
+    /// offline mode was just turned off).
+    pub fn wake(&self) {
+        self.notify.notify_one();
+    }
+
+    /// Aggregate progress across active and queued downloads.
+    pub fn progress(&self) -> &OverallProgressTracker {
+        &self.progress
     }
 
     /// Weeks that currently have a download in flight — either actively
@@ -174,11 +1191,17 @@ impl DownloadQueue {
         weeks
     }
 
-    /// Update the concurrency limit based on mode
+    /// Update the concurrency limit based on mode. Parallel → Queue lets
+    /// in-flight downloads finish and starts nothing new until only one is
+    /// left; Queue → Parallel adds the permits right away.
     pub async fn update_mode(&self, mode: DownloadMode) {
         let changed = {
             let mut current_mode = self.mode.lock().await;
             if *current_mode != mode {
+                // Resized under the mode lock so two racing calls can't apply
+                // their diffs against the same `from`.
+                self.slots
+                    .resize(concurrency_limit(&current_mode), concurrency_limit(&mode));
                 *current_mode = mode;
                 true
             } else {
@@ -192,54 +1215,142 @@ impl DownloadQueue {
         }
     }
 
-    /// Add a resource to the queue and trigger processing
-    pub async fn add_task(&self, app: AppHandle, resource: Resource) {
-        {
-            let mut queue = self.queue.lock().await;
-            let active = self.active_ids.lock().await;
-            // A2: skip if already queued OR already downloading. Without the
-            // `active_ids` check a poll landing mid-download would re-enqueue
-            // the same resource — its `.part` doesn't trip `check_file_exists`,
-            // so two tasks would write the same file concurrently.
-            if can_enqueue(&queue, &active, resource.id) {
-                queue.push_back(resource);
-                tracing::info!("Added task to queue. Queue size: {}", queue.len());
-            } else {
-                tracing::trace!(
-                    "Skipping enqueue for resource {}: already queued or active",
-                    resource.id
-                );
-            }
+    /// Add a resource to the queue and trigger processing. Returns `false`
+    /// when it was skipped as already queued or downloading, or rejected by
+    /// a full queue (`max_queue_size` with [`QueueOverflowPolicy::Reject`]).
+    /// Overflow emits `queue-overflow`.
+    pub async fn add_task(&self, app: AppHandle, resource: Resource) -> bool {
+        let Some(resource) = checked_urls(&app, resource) else {
+            return false;
+        };
+        let (limit, policy) = overflow_settings(&app);
+        let priority = category_priority(&app);
+        let (added, overflow) = self.enqueue(resource, limit, policy, &priority).await;
+        if let Some(overflow) = overflow {
+            let _ = app.emit("queue-overflow", overflow);
         }
         self.emit_queue_status(&app).await;
         self.notify.notify_one();
         self.ensure_worker_started(app).await;
+        added
     }
 
-    /// Add a resource to the queue with priority (for manual downloads)
-    /// Priority tasks are added to the front of the queue
-    pub async fn add_task_priority(&self, app: AppHandle, resource: Resource) {
-        {
-            let mut queue = self.queue.lock().await;
-            let active = self.active_ids.lock().await;
-            // A2: never front-jump a resource that's already downloading —
-            // that would spawn a second concurrent write to the same file.
-            // (Queue duplicates are handled below by `retain`.)
-            if active.contains(&resource.id) {
-                tracing::trace!(
-                    "Skipping priority enqueue for resource {}: already active",
+    /// [`add_task`](Self::add_task)'s queue update, once the URLs are
+    /// checked and the settings read: place `resource` by category
+    /// `priority` within `limit`. Returns whether it was added, and the
+    /// overflow to report if the queue was full.
+    async fn enqueue(
+        &self,
+        resource: Resource,
+        limit: Option<usize>,
+        policy: QueueOverflowPolicy,
+        priority: &[String],
+    ) -> (bool, Option<QueueOverflow>) {
+        let mut queue = self.queue.lock().await;
+        let active = self.active_ids.lock().await;
+        // A2: skip if already queued OR already downloading. Without the
+        // `active_ids` check a poll landing mid-download would re-enqueue
+        // the same resource — its `.part` doesn't trip `check_file_exists`,
+        // so two tasks would write the same file concurrently.
+        if !can_enqueue(&queue, &active, resource.id) {
+            tracing::trace!(
+                "Skipping enqueue for resource {}: already queued or active",
+                resource.id
+            );
+            return (false, None);
+        }
+        let overflow = |rejected_id, evicted_id| {
+            limit.map(|limit| QueueOverflow {
+                limit,
+                rejected_id,
+                evicted_id,
+            })
+        };
+        match make_room(&mut queue, limit, policy, false) {
+            Room::Full => {
+                tracing::warn!(
+                    "Queue full ({:?}), rejecting resource {}",
+                    limit,
                     resource.id
                 );
-            } else {
-                // Remove if already exists (to avoid duplicates)
-                queue.retain(|r| r.id != resource.id);
-                // Add to front for priority
-                queue.push_front(resource);
+                (false, overflow(Some(resource.id), None))
+            }
+            room => {
+                let evicted = match room {
+                    Room::Evicted(id) => overflow(None, Some(id)),
+                    _ => None,
+                };
+                let position = insert_position(&queue, &resource, priority, &self.manual());
+                queue.insert(position, resource);
+                tracing::info!("Added task to queue. Queue size: {}", queue.len());
+                (true, evicted)
             }
         }
+    }
+
+    /// Add a resource to the queue with priority (for manual downloads)
+    /// Priority tasks are added to the front of the queue. A full queue
+    /// makes room by evicting its tail, whatever the overflow policy.
+    /// Returns `false` when it was skipped (invalid URL, already downloading)
+    /// or was already queued and only moved to the front.
+    pub async fn add_task_priority(&self, app: AppHandle, resource: Resource) -> bool {
+        let Some(resource) = checked_urls(&app, resource) else {
+            return false;
+        };
+        let (limit, _) = overflow_settings(&app);
+        let (added, overflow) = self.enqueue_priority(resource, limit).await;
+        if let Some(overflow) = overflow {
+            let _ = app.emit("queue-overflow", overflow);
+        }
         self.emit_queue_status(&app).await;
         self.notify.notify_one();
         self.ensure_worker_started(app).await;
+        added
+    }
+
+    /// [`add_task_priority`](Self::add_task_priority)'s queue update: put
+    /// `resource` at the front, evicting the tail if the queue is at `limit`.
+    /// Returns whether it was newly added, and the eviction to report.
+    async fn enqueue_priority(
+        &self,
+        resource: Resource,
+        limit: Option<usize>,
+    ) -> (bool, Option<QueueOverflow>) {
+        let mut queue = self.queue.lock().await;
+        let active = self.active_ids.lock().await;
+        // A2: never front-jump a resource that's already downloading —
+        // that would spawn a second concurrent write to the same file.
+        // (Queue duplicates are handled below by `retain`.)
+        if active.contains(&resource.id) {
+            tracing::trace!(
+                "Skipping priority enqueue for resource {}: already active",
+                resource.id
+            );
+            return (false, None);
+        }
+        // Remove if already exists (to avoid duplicates)
+        let was_queued = queue.iter().any(|r| r.id == resource.id);
+        queue.retain(|r| r.id != resource.id);
+        let mut overflow = None;
+        if let (Some(limit), Room::Evicted(id)) = (
+            limit,
+            make_room(&mut queue, limit, QueueOverflowPolicy::Reject, true),
+        ) {
+            tracing::warn!(
+                "Queue full ({:?}), evicted resource {} for priority download",
+                limit,
+                id
+            );
+            overflow = Some(QueueOverflow {
+                limit,
+                rejected_id: None,
+                evicted_id: Some(id),
+            });
+        }
+        // Add to front for priority
+        self.manual().insert(resource.id);
+        queue.push_front(resource);
+        (!was_queued, overflow)
     }
 
     /// Remove a still-queued resource and notify the frontend (A5).
@@ -251,6 +1362,7 @@ impl DownloadQueue {
     pub async fn remove_queued(&self, app: &AppHandle, id: i64) -> bool {
         let removed = {
             let mut queue = self.queue.lock().await;
+            self.manual().remove(&id);
             drain_queued(&mut queue, id)
         };
         if removed {
@@ -260,31 +1372,38 @@ impl DownloadQueue {
         removed
     }
 
+    /// Drop every still-queued resource ("stop everything"), leaving active
+    /// downloads to the caller's cancel signals. Always emits
+    /// `queue-status-changed`; returns how many items were removed.
+    pub async fn clear(&self, app: &AppHandle) -> usize {
+        let cleared = {
+            let mut queue = self.queue.lock().await;
+            let cleared = queue.len();
+            queue.clear();
+            self.manual().clear();
+            cleared
+        };
+        if cleared > 0 {
+            tracing::info!("Cleared {} queued download(s)", cleared);
+        }
+        self.emit_queue_status(app).await;
+        cleared
+    }
+
     /// Emit current queue status to frontend
     async fn emit_queue_status(&self, app: &AppHandle) {
         let queue = self.queue.lock().await;
         let active = self.active_ids.lock().await;
+        publish_queue_status(app, &queue, &active);
+    }
 
-        // Create list of queued items with their position
-        let queued_items: Vec<serde_json::Value> = queue
-            .iter()
-            .enumerate()
-            .map(|(i, r)| {
-                serde_json::json!({
-                    "id": r.id,
-                    "position": i + 1
-                })
-            })
-            .collect();
-
-        let payload = serde_json::json!({
-            "queued": queued_items,
-            "active": *active
-        });
-
-        if let Err(e) = app.emit("queue-status-changed", payload) {
-            tracing::error!("Failed to emit queue-status-changed: {:?}", e);
-        }
+    /// Recompute `AppStatus`'s pending figures without emitting an event.
+    /// Called at the end of a poll, whose size-cache invalidation can change
+    /// `pending_bytes` even when the queue itself didn't move.
+    pub async fn refresh_pending_status(&self, app: &AppHandle) {
+        let queue = self.queue.lock().await;
+        let active = self.active_ids.lock().await;
+        update_pending_status(app, &queue, &active);
     }
 
     /// Ensure the worker is started (idempotent: the CAS lets exactly one
@@ -295,10 +1414,26 @@ impl DownloadQueue {
             .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
             .is_ok()
         {
-            self.start_worker(app).await;
+            self.spawn_worker(app);
         }
     }
 
+    /// Debounced [`scan_and_queue`](Self::scan_and_queue) for config changes:
+    /// the scan runs [`SCAN_DEBOUNCE`] after the last of a burst of
+    /// requests, once. Returns immediately.
+    pub fn request_scan(&self, app: AppHandle) {
+        let generation = self.scan_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let scan_generation = self.scan_generation.clone();
+        tauri::async_runtime::spawn(async move {
+            if still_latest_after(&scan_generation, generation, SCAN_DEBOUNCE).await {
+                app.state::<crate::commands::AppState>()
+                    .download_queue
+                    .scan_and_queue(app.clone())
+                    .await;
+            }
+        });
+    }
+
     /// scan resources and add to queue if matching auto-download criteria
     pub async fn scan_and_queue(&self, app: AppHandle) {
         let state = app.state::<crate::commands::AppState>();
@@ -334,15 +1469,17 @@ impl DownloadQueue {
         );
 
         if let Some(work_dir) = &config.work_directory {
+            if !crate::services::workdir::refresh_availability(&app, work_dir) {
+                tracing::info!("Auto-download scan paused: work directory unavailable");
+                return;
+            }
             let mut queued_count = 0;
             for resource in resources {
-                if config.auto_download_categories.contains(&resource.category) {
+                if is_auto_download_candidate(&resource, &config.auto_download_categories) {
                     // Check if already downloaded
                     let is_downloaded =
                         crate::services::download::DownloadService::check_file_exists(
-                            &resource,
-                            work_dir,
-                            config.prefer_optimized,
+                            &resource, &config,
                         );
                     if !is_downloaded {
                         tracing::trace!(
@@ -350,8 +1487,9 @@ impl DownloadQueue {
                             resource.title,
                             resource.category
                         );
-                        self.add_task(app.clone(), resource).await;
-                        queued_count += 1;
+                        if self.add_task(app.clone(), resource).await {
+                            queued_count += 1;
+                        }
                     }
                 }
             }
@@ -364,14 +1502,83 @@ impl DownloadQueue {
         }
     }
 
-    /// Start the queue worker (called once)
-    async fn start_worker(&self, app: AppHandle) {
+    /// Reconcile the manifests of the cached resources' folders with the
+    /// disk and re-queue the downloads a previous session left `in_progress`
+    /// with their `.part` still there, so they pick up where they stopped.
+    pub async fn resume_interrupted_downloads(&self, app: AppHandle) {
+        let state = app.state::<crate::commands::AppState>();
+        let (config, resources) = match (state.config.read(), state.resources.read()) {
+            (Ok(config), Ok(resources)) => (config.clone(), resources.clone()),
+            _ => {
+                tracing::error!("resume_interrupted_downloads: state lock poisoned, skipping");
+                return;
+            }
+        };
+        let Some(work_dir) = &config.work_directory else {
+            return;
+        };
+        if !crate::services::workdir::refresh_availability(&app, work_dir) {
+            tracing::info!("Resume pass skipped: work directory unavailable");
+            return;
+        }
+
+        let dirs: HashSet<std::path::PathBuf> = resources
+            .iter()
+            .filter_map(|resource| crate::services::download::destination_dir(&config, resource))
+            .collect();
+        let mut resumable: HashSet<i64> = HashSet::new();
+        for dir in dirs {
+            let report = crate::services::manifest::reconcile_downloads(&dir);
+            if report.backfilled > 0 || report.dropped > 0 {
+                tracing::debug!(
+                    "Reconciled {:?}: {} backfilled, {} dropped",
+                    dir,
+                    report.backfilled,
+                    report.dropped
+                );
+            }
+            resumable.extend(report.resumable);
+        }
+
+        let mut resumed = 0;
+        for resource in resources {
+            if resumable.contains(&resource.id) && self.add_task(app.clone(), resource).await {
+                resumed += 1;
+            }
+        }
+        tracing::info!("Resumed {} interrupted downloads", resumed);
+    }
+
+    /// Open `dir` in the file manager [`OPEN_FOLDER_DEBOUNCE`] after the
+    /// last of a burst of completed downloads, once (the last one's folder).
+    /// Returns immediately.
+    pub fn request_open_folder(&self, app: AppHandle, dir: std::path::PathBuf) {
+        let generation = self.open_folder_generation.fetch_add(1, Ordering::SeqCst) + 1;
+        let open_folder_generation = self.open_folder_generation.clone();
+        tauri::async_runtime::spawn(async move {
+            if still_latest_after(&open_folder_generation, generation, OPEN_FOLDER_DEBOUNCE).await {
+                use tauri_plugin_opener::OpenerExt;
+                if let Err(e) = app
+                    .opener()
+                    .open_path(dir.to_string_lossy().into_owned(), None::<&str>)
+                {
+                    tracing::warn!("Failed to open download folder {:?}: {}", dir, e);
+                }
+            }
+        });
+    }
+
+    /// Start the queue worker (called once), with `host` for everything
+    /// outside the queue's own bookkeeping (see [`WorkerHost`]).
+    fn spawn_worker<H: WorkerHost>(&self, host: H) {
         let queue = self.queue.clone();
-        let mode_lock = self.mode.clone();
-        let active_count = self.active_count.clone();
+        let slots = self.slots.clone();
         let active_ids = self.active_ids.clone();
         let active_weeks = self.active_weeks.clone();
         let notify = self.notify.clone();
+        let progress = self.progress.clone();
+        let queue_paused = self.queue_paused.clone();
+        let manual = self.manual.clone();
 
         tracing::info!("Download queue worker started");
 
@@ -382,22 +1589,24 @@ impl DownloadQueue {
         // finished download's `notify_one` wake it back up.
         tauri::async_runtime::spawn(async move {
             loop {
-                // Determine concurrency limit
-                let limit = {
-                    let mode = mode_lock.lock().await;
-                    concurrency_limit(&mode)
-                };
-
-                // Check if we can start more downloads
-                let current_active = active_count.load(Ordering::SeqCst);
+                // Paused queue or offline mode: start nothing new, leaving
+                // queued items in place. `resume_queue` / `wake` wake us.
+                if queue_paused.load(Ordering::SeqCst) || host.is_offline() {
+                    notify.notified().await;
+                    continue;
+                }
 
-                if current_active >= limit {
+                // Claim a slot before touching the queue. The semaphore holds
+                // exactly `concurrency_limit(mode)` permits (see
+                // `update_mode`), so the claim is the capacity check: the two
+                // can't drift apart the way a separate counter could.
+                let Some(permit) = slots.try_acquire() else {
                     // At capacity: park until a slot frees up or the limit
                     // grows. A `notify_one` issued before this point is latched
                     // by `Notify`, so a completion racing this check is not lost.
                     notify.notified().await;
                     continue;
-                }
+                };
 
                 // Try to get next task from queue. Register it in `active_ids`
                 // AND `active_weeks` while still holding the queue lock, so the
@@ -408,305 +1617,63 @@ impl DownloadQueue {
                 // (weeks_with_pending_downloads) never sees the week as free
                 // while a folder is about to be written into. Lock order
                 // queue→active_ids matches `add_task` to avoid deadlock.
-                let resource = {
+                let window_wait = host.window_wait();
+                let (resource, deferred) = {
                     let mut q = queue.lock().await;
-                    let popped = q.pop_front();
+                    // Outside the download window only manual downloads (kept
+                    // at the front) may start; auto downloads stay queued.
+                    let may_start = window_wait.is_none()
+                        || q.front().is_some_and(|r| {
+                            manual
+                                .lock()
+                                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                                .contains(&r.id)
+                        });
+                    let popped = if may_start { q.pop_front() } else { None };
                     if let Some(resource) = &popped {
+                        manual
+                            .lock()
+                            .unwrap_or_else(|poisoned| poisoned.into_inner())
+                            .remove(&resource.id);
                         active_ids.lock().await.push(resource.id);
                         active_weeks
                             .lock()
                             .await
                             .insert(resource.id, resource.week());
                     }
-                    popped
+                    let deferred = if may_start || q.is_empty() {
+                        None
+                    } else {
+                        window_wait
+                    };
+                    (popped, deferred)
                 };
 
                 if let Some(resource) = resource {
                     // We have a task and have capacity, start it
-                    active_count.fetch_add(1, Ordering::SeqCst);
+                    let slot = SlotGuard::new(permit, slots.clone());
 
-                    let active_count_clone = active_count.clone();
                     let active_ids_clone = active_ids.clone();
                     let active_weeks_clone = active_weeks.clone();
-                    let notify_clone = notify.clone();
-                    let app_clone = app.clone();
-                    // Separate handle for the supervisor: its cleanup must run
-                    // even if `app_clone` is moved into the download body below.
-                    let app_super = app.clone();
+                    let progress_clone = progress.clone();
+                    let queue_clone = queue.clone();
+                    let host_clone = host.clone();
                     let resource_id = resource.id;
 
                     // Emit status update immediately as queue changed (popped item) AND active changed
                     {
                         let q = queue.lock().await;
                         let a = active_ids.lock().await;
-                        let queued_items: Vec<serde_json::Value> = q
-                            .iter()
-                            .enumerate()
-                            .map(|(i, r)| {
-                                serde_json::json!({
-                                    "id": r.id,
-                                    "position": i + 1
-                                })
-                            })
-                            .collect();
-                        let payload = serde_json::json!({
-                            "queued": queued_items,
-                            "active": *a
-                        });
-                        let _ = app_clone.emit("queue-status-changed", payload);
+                        host.publish_status(&q, &a);
                     }
 
                     // A4: supervise the download body so bookkeeping is ALWAYS
                     // reconciled — even if the body panics. Previously the
-                    // `fetch_sub`/`active_ids` cleanup lived inside the body, so
-                    // a panic left `active_count` permanently inflated and the
-                    // worker stalled once it hit the concurrency limit.
+                    // `active_ids` cleanup lived inside the body, so a panic
+                    // left the slot taken and the worker stalled once it hit
+                    // the concurrency limit.
                     tauri::async_runtime::spawn(async move {
-                        let body = tauri::async_runtime::spawn(async move {
-                            // Execute download
-                            // Resolve state at the top level of the task
-                            let state = app_clone.state::<crate::commands::AppState>();
-
-                            if let Ok(config) = crate::commands::get_config(state) {
-                                if let Some(work_dir) = config.work_directory {
-                                    let download_service =
-                                        crate::services::DownloadService::with_client(
-                                            app_clone
-                                                .state::<crate::commands::AppState>()
-                                                .shared_http_client
-                                                .clone(),
-                                        );
-                                    let prefer_optimized = config.prefer_optimized;
-                                    let dest_dir = crate::services::download::resolve_week_dir(
-                                        &resource,
-                                        &work_dir,
-                                        prefer_optimized,
-                                    );
-
-                                    if !dest_dir.exists() {
-                                        let _ = std::fs::create_dir_all(&dest_dir);
-                                    }
-
-                                    // Register signal
-                                    let signal =
-                                        std::sync::Arc::new(std::sync::atomic::AtomicU8::new(
-                                            crate::services::download::STATUS_RUNNING,
-                                        ));
-
-                                    {
-                                        let signal_state =
-                                            app_clone.state::<crate::commands::AppState>();
-                                        let signals_res = signal_state.download_signals.write();
-                                        if let Ok(mut signals) = signals_res {
-                                            signals.insert(resource.id, signal.clone());
-                                        }
-                                    }
-
-                                    tracing::info!("Queue starting download: {}", resource.title);
-
-                                    // Emit download started event to frontend
-                                    if let Err(e) = app_clone.emit("download-started", resource.id)
-                                    {
-                                        tracing::error!(
-                                            "Failed to emit download-started event for {}: {:?}",
-                                            resource.id,
-                                            e
-                                        );
-                                    } else {
-                                        tracing::trace!(
-                                            "Emitted download-started event for resource {}",
-                                            resource.id
-                                        );
-                                    }
-
-                                    match download_service
-                                        .download_resource(
-                                            &resource,
-                                            &dest_dir,
-                                            Some(&app_clone),
-                                            Some(signal),
-                                            prefer_optimized,
-                                        )
-                                        .await
-                                    {
-                                        Ok((path, hash)) => {
-                                            tracing::info!("Download completed successfully: {} -> {:?} (hash: {})", resource.title, path, hash);
-
-                                            // The frontend needs to know whether the
-                                            // *actually downloaded* URL was an optimized
-                                            // variant (auto-downloads never enter the
-                                            // frontend's activeDownloads map, so it cannot
-                                            // derive this itself). `resource` here is the
-                                            // effective resource (manual variant picks
-                                            // included), so this also covers non-default
-                                            // picks.
-                                            let optimized = resource
-                                                .get_effective_download_url(prefer_optimized)
-                                                != resource.download_url;
-
-                                            // A1: savings are only meaningful when the
-                                            // optimized variant is what actually landed
-                                            // on disk — a non-optimized download has
-                                            // nothing to compare against. Read before
-                                            // `record_downloaded_file` moves `path`.
-                                            let optimized_bytes = if optimized {
-                                                tokio::fs::metadata(&path)
-                                                    .await
-                                                    .ok()
-                                                    .map(|m| m.len())
-                                            } else {
-                                                None
-                                            };
-
-                                            // adr-0007 step 2: record the file in the
-                                            // errata registry so a later poll can
-                                            // detect it being superseded.
-                                            crate::services::record_downloaded_file(
-                                                &app_clone,
-                                                &resource,
-                                                path,
-                                                prefer_optimized,
-                                            );
-
-                                            // A1: the original size is only ever read
-                                            // from the shared HEAD-size cache here — NOT
-                                            // fetched over the network. A blocking HEAD
-                                            // request (up to 5s) used to run inline in
-                                            // this task body, delaying both the
-                                            // `download-complete` event and the worker
-                                            // slot freeing (active_count isn't
-                                            // decremented until this body returns). When
-                                            // the size isn't cached yet, the event below
-                                            // reports `original_bytes`/`saved_bytes` as
-                                            // `null` and a DETACHED task (spawned further
-                                            // down) resolves it in the background and
-                                            // emits `savings-resolved` once it lands.
-                                            let original_bytes = if optimized {
-                                                cached_original_size_bytes(
-                                                    &app_clone,
-                                                    &resource.download_url,
-                                                )
-                                            } else {
-                                                None
-                                            };
-
-                                            let saved_bytes = compute_saved_bytes(
-                                                original_bytes,
-                                                optimized_bytes,
-                                            );
-
-                                            // A2: fold this download's savings (if any)
-                                            // into the persistent running total; when
-                                            // there's nothing to add, still report its
-                                            // current value so the UI's counter never
-                                            // goes stale relative to the backend. Only
-                                            // counted here when already known — a `None`
-                                            // saved_bytes with `optimized` true is folded
-                                            // in later by the detached resolution task
-                                            // below instead, never both (no double count).
-                                            let total_saved_bytes = match saved_bytes {
-                                                Some(bytes) => crate::commands::add_saved_bytes(
-                                                    &app_clone, bytes,
-                                                ),
-                                                None => crate::commands::current_saved_bytes(
-                                                    &app_clone.state::<crate::commands::AppState>(),
-                                                ),
-                                            };
-
-                                            let _ = app_clone.emit(
-                                                "download-complete",
-                                                serde_json::json!({
-                                                    "id": resource.id,
-                                                    "optimized": optimized,
-                                                    "optimized_bytes": optimized_bytes,
-                                                    "original_bytes": original_bytes,
-                                                    "saved_bytes": saved_bytes,
-                                                    "total_saved_bytes": total_saved_bytes,
-                                                }),
-                                            );
-
-                                            // Original size wasn't cached: resolve it in
-                                            // a task detached from this body (never
-                                            // awaited here), so the up-to-5s HEAD request
-                                            // cannot delay the worker slot freeing above.
-                                            // Needs optimized_bytes too (already known) to
-                                            // ever compute a saving.
-                                            if optimized && original_bytes.is_none() {
-                                                if let Some(optimized_bytes) = optimized_bytes {
-                                                    let app_detached = app_clone.clone();
-                                                    let download_url =
-                                                        resource.download_url.clone();
-                                                    let resource_id = resource.id;
-                                                    tauri::async_runtime::spawn(async move {
-                                                        let Some(resolved_original) =
-                                                            resolve_original_size_bytes(
-                                                                &app_detached,
-                                                                &download_url,
-                                                            )
-                                                            .await
-                                                        else {
-                                                            return;
-                                                        };
-                                                        let resolved_saved = compute_saved_bytes(
-                                                            Some(resolved_original),
-                                                            Some(optimized_bytes),
-                                                        );
-                                                        // Counted exactly once: the
-                                                        // immediate emission above only
-                                                        // added to the total when
-                                                        // saved_bytes was already known,
-                                                        // which is not the branch we're
-                                                        // in (original_bytes was None).
-                                                        let resolved_total =
-                                                            match resolved_saved {
-                                                                Some(bytes) => {
-                                                                    crate::commands::add_saved_bytes(
-                                                                        &app_detached, bytes,
-                                                                    )
-                                                                }
-                                                                None => {
-                                                                    crate::commands::current_saved_bytes(
-                                                                        &app_detached.state::<crate::commands::AppState>(),
-                                                                    )
-                                                                }
-                                                            };
-                                                        let _ = app_detached.emit(
-                                                            "savings-resolved",
-                                                            serde_json::json!({
-                                                                "id": resource_id,
-                                                                "saved_bytes": resolved_saved,
-                                                                "original_bytes": resolved_original,
-                                                                "total_saved_bytes": resolved_total,
-                                                            }),
-                                                        );
-                                                    });
-                                                }
-                                            }
-                                        }
-                                        Err(crate::error::DownloadError::Paused) => {
-                                            tracing::info!("Download paused: {}", resource.title);
-                                            let _ = app_clone.emit("download-paused", resource.id);
-                                        }
-                                        Err(crate::error::DownloadError::Cancelled) => {
-                                            tracing::info!(
-                                                "Download cancelled: {}",
-                                                resource.title
-                                            );
-                                            let _ =
-                                                app_clone.emit("download-cancelled", resource.id);
-                                        }
-                                        Err(e) => {
-                                            tracing::error!(
-                                                "Download failed for {}: {}",
-                                                resource.title,
-                                                e
-                                            );
-                                            let _ = app_clone.emit("download-failed", serde_json::json!({"id": resource.id, "error": e.to_string()}));
-                                        }
-                                    }
-                                }
-                            }
-                        });
+                        let body = tauri::async_runtime::spawn(host_clone.run(resource));
 
                         // A4: this cleanup runs unconditionally — including when
                         // the body panicked (surfaced here as a JoinError).
@@ -716,22 +1683,13 @@ impl DownloadQueue {
                                 resource_id,
                                 join_err
                             );
-                            let _ = app_super.emit(
-                                "download-failed",
-                                serde_json::json!({"id": resource_id, "error": "internal error"}),
-                            );
+                            host_clone.panicked(resource_id);
                         }
 
-                        let previous = active_count_clone.fetch_sub(1, Ordering::SeqCst);
-                        tracing::trace!(
-                            "Download worker finished. Active count decremented from {} to {}",
-                            previous,
-                            previous.saturating_sub(1)
-                        );
-                        // A slot just freed: wake the worker so it can pull the
-                        // next queued task. Must follow `fetch_sub` so the woken
-                        // worker observes the decremented count.
-                        notify_clone.notify_one();
+                        // A slot just freed: returning the permit wakes the
+                        // worker so it can pull the next queued task.
+                        drop(slot);
+                        tracing::trace!("Download worker finished for resource {}", resource_id);
 
                         // Remove from active IDs
                         {
@@ -744,23 +1702,36 @@ impl DownloadQueue {
                             let mut weeks = active_weeks_clone.lock().await;
                             weeks.remove(&resource_id);
                         }
-                        // Guaranteed signal removal: the body registers the
-                        // signal, so a panic before its own cleanup would leak
-                        // it in `download_signals` without this.
+                        host_clone.finished(resource_id);
+                        // The finished download left `active`: publish it so
+                        // pending counts drop without waiting for the next pop.
                         {
-                            let signal_state = app_super.state::<crate::commands::AppState>();
-                            let signals_res = signal_state.download_signals.write();
-                            if let Ok(mut signals) = signals_res {
-                                signals.remove(&resource_id);
-                            }
+                            let q = queue_clone.lock().await;
+                            let a = active_ids_clone.lock().await;
+                            host_clone.publish_status(&q, &a);
                         }
+                        host_clone.progress_finished(&progress_clone, resource_id);
                     });
 
                     // In parallel mode, immediately check for more tasks
                     // In queue mode, the limit check will prevent starting another
                     continue;
+                } else if let Some(wait) = deferred {
+                    // Outside the download window: park until it opens, or
+                    // until a manual download or config change wakes us.
+                    slots.release(permit);
+                    tracing::debug!(
+                        "Auto downloads deferred {:?} until the download window",
+                        wait
+                    );
+                    tokio::select! {
+                        _ = notify.notified() => {}
+                        _ = tokio::time::sleep(wait) => {}
+                    }
                 } else {
-                    // Queue is empty: park until a producer enqueues something.
+                    // Queue is empty: return the slot (no guard yet, so no
+                    // self-wake) and park until a producer enqueues something.
+                    slots.release(permit);
                     // An enqueue's `notify_one` racing this branch is latched by
                     // `Notify`, so the wakeup is not lost.
                     notify.notified().await;
@@ -768,8 +1739,27 @@ impl DownloadQueue {
             }
         });
     }
+
+    /// Whether nothing is queued or downloading.
+    pub async fn is_idle(&self) -> bool {
+        let queue = self.queue.lock().await;
+        let active = self.active_ids.lock().await;
+        queue.is_empty() && active.is_empty()
+    }
+
+    /// Downloads currently holding a slot.
     pub fn active_count(&self) -> usize {
-        self.active_count.load(Ordering::SeqCst)
+        self.slots.active()
+    }
+
+    /// Whether `id` is downloading right now (not merely queued).
+    pub async fn is_active(&self, id: i64) -> bool {
+        self.active_ids.lock().await.contains(&id)
+    }
+
+    /// Whether `id` is waiting in the queue (not yet downloading).
+    pub async fn is_queued(&self, id: i64) -> bool {
+        self.queue.lock().await.iter().any(|r| r.id == id)
     }
 
     pub async fn queue_len(&self) -> usize {
@@ -823,7 +1813,7 @@ mod tests {
     async fn test_weeks_with_pending_downloads_includes_active_downloads() {
         let dq = DownloadQueue::new();
         {
-            // Simulates what start_worker records once a download actually
+            // Simulates what spawn_worker records once a download actually
             // starts (see the `active_weeks.insert` next to `ids.push`
             // above): by then the resource has already left `queue`.
             let mut active = dq.active_weeks.lock().await;
@@ -909,6 +1899,57 @@ mod tests {
         assert!(queue.iter().all(|r| r.id != 1));
     }
 
+    fn queue_of(ids: &[i64]) -> VecDeque<Resource> {
+        ids.iter()
+            .map(|&id| make_resource(id, 2026, 1, 19))
+            .collect()
+    }
+
+    fn ids(queue: &VecDeque<Resource>) -> Vec<i64> {
+        queue.iter().map(|r| r.id).collect()
+    }
+
+    #[test]
+    fn test_make_room_reject_keeps_full_queue() {
+        let mut queue = queue_of(&[1, 2]);
+        assert_eq!(
+            make_room(&mut queue, Some(2), QueueOverflowPolicy::Reject, false),
+            Room::Full
+        );
+        assert_eq!(ids(&queue), vec![1, 2]);
+
+        assert_eq!(
+            make_room(&mut queue, Some(3), QueueOverflowPolicy::Reject, false),
+            Room::Fits
+        );
+        assert_eq!(
+            make_room(&mut queue, None, QueueOverflowPolicy::Reject, false),
+            Room::Fits
+        );
+    }
+
+    #[test]
+    fn test_make_room_drop_oldest_evicts_head() {
+        let mut queue = queue_of(&[1, 2]);
+        assert_eq!(
+            make_room(&mut queue, Some(2), QueueOverflowPolicy::DropOldest, false),
+            Room::Evicted(1)
+        );
+        assert_eq!(ids(&queue), vec![2]);
+    }
+
+    #[test]
+    fn test_make_room_priority_evicts_tail_under_any_policy() {
+        for policy in [QueueOverflowPolicy::Reject, QueueOverflowPolicy::DropOldest] {
+            let mut queue = queue_of(&[1, 2]);
+            assert_eq!(
+                make_room(&mut queue, Some(2), policy, true),
+                Room::Evicted(2)
+            );
+            assert_eq!(ids(&queue), vec![1]);
+        }
+    }
+
     #[test]
     fn test_drain_queued_reports_false_when_absent() {
         let mut queue: VecDeque<Resource> = VecDeque::new();
@@ -935,4 +1976,367 @@ mod tests {
         assert!(weeks.contains(&WeekIdentifier::new(2026, 4)));
         assert!(weeks.contains(&WeekIdentifier::new(2025, 52)));
     }
+
+    #[test]
+    fn test_estimate_pending_bytes_sums_known_sizes_only() {
+        let a = make_resource(1, 2026, 1, 19);
+        let b = make_resource(2, 2026, 1, 19);
+        let mut c = make_resource(3, 2026, 1, 19);
+        c.optimized_video_url = Some("https://example.com/3-small.mp4".to_string());
+        let d = make_resource(4, 2026, 1, 19);
+
+        let mut cache = FileSizeCache::new();
+        cache.insert(a.download_url.clone(), 100);
+        cache.record_failure(b.download_url.clone()); // negative cache
+        cache.insert(c.download_url.clone(), 1_000);
+        cache.insert("https://example.com/3-small.mp4".to_string(), 10);
+        // d is not cached at all.
+
+        let resources = [a, b, c, d];
+        assert_eq!(estimate_pending_bytes(&resources, true, &cache), 110);
+        assert_eq!(estimate_pending_bytes(&resources, false, &cache), 1_100);
+        assert_eq!(estimate_pending_bytes(std::iter::empty(), true, &cache), 0);
+    }
+
+    #[test]
+    fn test_inactive_resource_is_never_auto_queued() {
+        let categories = vec!["test".to_string()];
+        let active = make_resource(1, 2026, 1, 19);
+        let mut withdrawn = make_resource(2, 2026, 1, 19);
+        withdrawn.is_active = false;
+        let mut other_category = make_resource(3, 2026, 1, 19);
+        other_category.category = "audio".to_string();
+
+        assert!(is_auto_download_candidate(&active, &categories));
+        assert!(!is_auto_download_candidate(&withdrawn, &categories));
+        assert!(!is_auto_download_candidate(&other_category, &categories));
+    }
+
+    #[tokio::test]
+    async fn test_slot_guard_releases_permit_when_task_panics() {
+        let dq = DownloadQueue::new();
+        let permit = dq.slots.try_acquire().unwrap();
+        let slot = SlotGuard::new(permit, dq.slots.clone());
+        assert_eq!(dq.active_count(), 1);
+
+        let task = tokio::spawn(async move {
+            let _slot = slot;
+            panic!("download body blew up");
+        });
+        assert!(task.await.is_err());
+
+        assert_eq!(dq.active_count(), 0);
+        assert!(dq.slots.try_acquire().is_some());
+    }
+
+    /// Claims slots the way the worker does, until the limit says no.
+    fn claim_all(dq: &DownloadQueue, held: &mut Vec<SlotGuard>) {
+        while let Some(permit) = dq.slots.try_acquire() {
+            held.push(SlotGuard::new(permit, dq.slots.clone()));
+        }
+    }
+
+    /// Worker host whose downloads each wait for a permit from `finish`, so
+    /// the test decides when they complete.
+    #[derive(Clone)]
+    struct ScriptedHost {
+        finish: Arc<Semaphore>,
+    }
+
+    impl WorkerHost for ScriptedHost {
+        fn is_offline(&self) -> bool {
+            false
+        }
+
+        fn window_wait(&self) -> Option<Duration> {
+            None
+        }
+
+        fn publish_status(&self, _queue: &VecDeque<Resource>, _active: &[i64]) {}
+
+        fn run(
+            &self,
+            _resource: Resource,
+        ) -> impl std::future::Future<Output = ()> + Send + 'static {
+            let finish = self.finish.clone();
+            async move {
+                finish.acquire().await.unwrap().forget();
+            }
+        }
+
+        fn panicked(&self, _id: i64) {}
+
+        fn finished(&self, _id: i64) {}
+
+        fn progress_finished(&self, _progress: &OverallProgressTracker, _id: i64) {}
+    }
+
+    /// Wait for the worker to reach `active` running and `queued` waiting
+    /// downloads, then check it stays there.
+    async fn settle(dq: &DownloadQueue, active: usize, queued: usize) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let now = (dq.active_count(), dq.queue_len().await);
+            if now == (active, queued) {
+                break;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "stuck at {now:?}, expected {:?}",
+                (active, queued)
+            );
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!((dq.active_count(), dq.queue_len().await), (active, queued));
+    }
+
+    #[tokio::test]
+    async fn test_mode_switch_converges_active_count() {
+        let dq = DownloadQueue::new();
+        let finish = Arc::new(Semaphore::new(0));
+        for id in 1..=6 {
+            let (added, _) = dq
+                .enqueue(
+                    make_resource(id, 2026, 1, 19),
+                    None,
+                    QueueOverflowPolicy::Reject,
+                    &[],
+                )
+                .await;
+            assert!(added);
+        }
+        dq.spawn_worker(ScriptedHost {
+            finish: finish.clone(),
+        });
+        settle(&dq, 1, 5).await;
+
+        dq.update_mode(DownloadMode::Parallel).await;
+        settle(&dq, 4, 2).await;
+
+        // Parallel → Queue mid-run: nothing is interrupted, and no queued
+        // item starts until the active count has drained below the new limit.
+        dq.update_mode(DownloadMode::Queue).await;
+        settle(&dq, 4, 2).await;
+        for remaining in (1..=3).rev() {
+            finish.add_permits(1);
+            settle(&dq, remaining, 2).await;
+        }
+
+        // Then one at a time, never more.
+        finish.add_permits(1);
+        settle(&dq, 1, 1).await;
+        finish.add_permits(1);
+        settle(&dq, 1, 0).await;
+        finish.add_permits(1);
+        settle(&dq, 0, 0).await;
+        assert!(dq.is_idle().await);
+    }
+
+    #[tokio::test]
+    async fn test_regrowing_before_shrink_is_paid_cancels_debt() {
+        let dq = DownloadQueue::new();
+        let mut held = Vec::new();
+        dq.update_mode(DownloadMode::Parallel).await;
+        claim_all(&dq, &mut held);
+
+        dq.update_mode(DownloadMode::Queue).await;
+        dq.update_mode(DownloadMode::Parallel).await;
+        held.pop();
+        claim_all(&dq, &mut held);
+        assert_eq!(dq.active_count(), 4);
+    }
+
+    #[test]
+    fn test_overall_progress_mixes_reported_and_estimated_bytes() {
+        let mut book = ProgressBook {
+            queued_bytes: 400,
+            ..Default::default()
+        };
+        assert_eq!(book.overall().percent, 0);
+
+        book.active_estimates.insert(1, 100);
+        book.active_estimates.insert(2, 500);
+        // Download 1 reports a (more accurate) total; 2 hasn't started.
+        book.reported.insert(1, (50, 100));
+        // Download 3 has no cached size but reports its own total.
+        book.reported.insert(3, (250, 1000));
+
+        let overall = book.overall();
+        assert_eq!(overall.downloaded_bytes, 300);
+        assert_eq!(overall.total_bytes, 400 + 100 + 500 + 1000);
+        assert_eq!(overall.percent, 15);
+    }
+
+    #[test]
+    fn test_overall_progress_empty_is_zero() {
+        let book = ProgressBook::default();
+        assert_eq!(
+            book.overall(),
+            OverallProgress {
+                downloaded_bytes: 0,
+                total_bytes: 0,
+                percent: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_set_paused_reports_changes_only() {
+        let queue = DownloadQueue::new();
+        assert!(!queue.is_paused());
+        assert!(queue.set_paused(true));
+        assert!(!queue.set_paused(true));
+        assert!(queue.is_paused());
+        assert!(queue.set_paused(false));
+        assert!(!queue.is_paused());
+    }
+
+    #[test]
+    fn test_build_queue_status_details_queued_and_active_items() {
+        let queued = VecDeque::from([make_resource(2, 2026, 1, 20), make_resource(3, 2026, 1, 20)]);
+        let running = make_resource(1, 2026, 1, 20);
+        let mut cache = FileSizeCache::new();
+        cache.insert(queued[0].download_url.clone(), 2_000);
+        cache.insert(running.download_url.clone(), 1_000);
+        let reported = HashMap::from([(1, (250, 1_000))]);
+
+        // Active id 9 has left the resource list: reported with no details.
+        let payload = build_queue_status(
+            &queued,
+            &[1, 9],
+            std::slice::from_ref(&running),
+            &cache,
+            false,
+            &reported,
+        );
+
+        assert_eq!(payload.version, QUEUE_STATUS_VERSION);
+        assert_eq!(payload.active, vec![1, 9]);
+        assert_eq!(
+            payload.queued[0],
+            QueuedItem {
+                id: 2,
+                position: 1,
+                title: "Resource 2".to_string(),
+                category: "test".to_string(),
+                file_size: Some(2_000),
+            }
+        );
+        assert_eq!(payload.queued[1].position, 2);
+        assert_eq!(payload.queued[1].file_size, None);
+        assert_eq!(
+            payload.active_items[0],
+            ActiveItem {
+                id: 1,
+                title: Some("Resource 1".to_string()),
+                category: Some("test".to_string()),
+                file_size: Some(1_000),
+                downloaded_bytes: Some(250),
+                total_bytes: Some(1_000),
+            }
+        );
+        assert_eq!(payload.active_items[1].title, None);
+        assert_eq!(payload.active_items[1].downloaded_bytes, None);
+    }
+
+    #[tokio::test]
+    async fn test_still_latest_after_keeps_only_last_of_burst() {
+        let counter = Arc::new(AtomicU64::new(0));
+        let mut waits = Vec::new();
+        for _ in 0..3 {
+            let generation = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            let counter = counter.clone();
+            waits.push(tokio::spawn(async move {
+                still_latest_after(&counter, generation, Duration::from_millis(100)).await
+            }));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        let mut results = Vec::new();
+        for wait in waits {
+            results.push(wait.await.unwrap());
+        }
+        assert_eq!(results, vec![false, false, true]);
+    }
+
+    #[test]
+    fn test_became_idle_fires_once_per_busy_to_idle_edge() {
+        let flag = AtomicBool::new(false);
+        assert!(!became_idle(&flag, false));
+        assert!(!became_idle(&flag, true));
+        assert!(!became_idle(&flag, true));
+        assert!(became_idle(&flag, false));
+        assert!(!became_idle(&flag, false));
+        assert!(!became_idle(&flag, true));
+        assert!(became_idle(&flag, false));
+    }
+
+    fn with_category(id: i64, category: &str) -> Resource {
+        let mut resource = make_resource(id, 2026, 1, 19);
+        resource.category = category.to_string();
+        resource
+    }
+
+    /// Queues `(id, category)` pairs through [`DownloadQueue::enqueue`]
+    /// (`add_task`'s path), or [`DownloadQueue::enqueue_priority`] for the
+    /// ids in `manual`, in order; returns the resulting queue order.
+    async fn queued_order(priority: &[String], items: &[(i64, &str)], manual: &[i64]) -> Vec<i64> {
+        let dq = DownloadQueue::new();
+        for &(id, category) in items {
+            let resource = with_category(id, category);
+            let (added, _) = if manual.contains(&id) {
+                dq.enqueue_priority(resource, None).await
+            } else {
+                dq.enqueue(resource, None, QueueOverflowPolicy::Reject, priority)
+                    .await
+            };
+            assert!(added);
+        }
+        let queue = dq.queue.lock().await;
+        ids(&queue)
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_without_priority_is_fifo() {
+        let order = queued_order(&[], &[(1, "pdf"), (2, "video"), (3, "audio")], &[]).await;
+        assert_eq!(order, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_orders_by_category_and_is_stable() {
+        let priority = vec!["video".to_string(), "pdf".to_string()];
+        let order = queued_order(
+            &priority,
+            &[
+                (1, "audio"),
+                (2, "pdf"),
+                (3, "video"),
+                (4, "pdf"),
+                (5, "video"),
+            ],
+            &[],
+        )
+        .await;
+        // Videos, then PDFs, then unlisted; arrival order within each.
+        assert_eq!(order, vec![3, 5, 2, 4, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_manual_downloads_stay_ahead_of_category_priority() {
+        let priority = vec!["video".to_string()];
+        let order = queued_order(
+            &priority,
+            &[
+                (1, "pdf"),
+                (2, "pdf"),
+                (3, "video"),
+                (4, "audio"),
+                (5, "video"),
+            ],
+            &[2, 4],
+        )
+        .await;
+        // Manual adds (newest first) lead; the videos go right after them.
+        assert_eq!(order, vec![4, 2, 3, 5, 1]);
+    }
 }
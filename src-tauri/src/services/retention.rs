@@ -3,8 +3,9 @@
 //! Handles archiving of old week files and retention policy enforcement.
 
 use crate::error::FileError;
-use crate::models::WeekIdentifier;
-use chrono::{Datelike, Duration, NaiveDate, Utc};
+use crate::models::{DownloadedFile, FolderLayout, WeekIdentifier};
+use crate::services::download::{hide_dir, is_partial_download_name};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -14,32 +15,71 @@ use tauri::{AppHandle, Manager};
 use tokio::sync::watch;
 use tokio::time::{interval, Duration as TokioDuration};
 
-/// Archive directory name
+/// Default archive directory name, inside the work directory
 const ARCHIVE_DIR: &str = ".archive";
 /// Superseded files subdirectory within week archive
 const SUPERSEDED_DIR: &str = ".superseded";
-/// How often the background scheduler re-checks the retention policy.
-const RETENTION_CHECK_INTERVAL_SECS: u64 = 24 * 60 * 60; // once a day
+/// How often the background scheduler checks whether retention is due.
+/// Cheap: the actual run is gated by [`RETENTION_MIN_INTERVAL_HOURS`].
+const RETENTION_CHECK_INTERVAL_SECS: u64 = 60 * 60; // hourly
+/// Minimum time between two retention runs, across restarts: the last run
+/// is persisted under [`LAST_RETENTION_RUN_KEY`].
+const RETENTION_MIN_INTERVAL_HOURS: i64 = 24;
+/// `settings.json` key holding when retention last ran (RFC 3339).
+const LAST_RETENTION_RUN_KEY: &str = "last_retention_run";
+/// Set while a gated run is in flight, so the scheduler and a poll landing
+/// together don't both scan the archive.
+static RETENTION_RUNNING: AtomicBool = AtomicBool::new(false);
 /// Startup grace period before the first retention run, so it doesn't
 /// contend with the rest of app initialization (frontend listener
 /// registration, the initial poll). Mirrors the delay already used for the
 /// auto-download scan in `lib.rs`.
 const STARTUP_DELAY_SECS: u64 = 5;
 
+/// Where archived weeks live: `archive_directory`
+/// (`AppConfig::effective_archive_directory`) when configured, else
+/// `.archive` inside the work directory.
+pub fn resolve_archive_root(work_dir: &Path, archive_directory: Option<&Path>) -> PathBuf {
+    archive_directory
+        .map(Path::to_path_buf)
+        .unwrap_or_else(|| work_dir.join(ARCHIVE_DIR))
+}
+
 /// Service for managing file retention and archiving
 pub struct FileRetentionService {
     work_dir: PathBuf,
+    archive_root: PathBuf,
 }
 
 impl FileRetentionService {
-    /// Create a new FileRetentionService
-    pub fn new(work_dir: PathBuf) -> Self {
-        Self { work_dir }
+    /// Create a new FileRetentionService. `archive_root` comes from
+    /// [`resolve_archive_root`].
+    pub fn new(work_dir: PathBuf, archive_root: PathBuf) -> Self {
+        Self {
+            work_dir,
+            archive_root,
+        }
     }
 
     /// Get the archive directory path
     pub fn archive_dir(&self) -> PathBuf {
-        self.work_dir.join(ARCHIVE_DIR)
+        self.archive_root.clone()
+    }
+
+    /// `create_dir_all` for a folder inside the archive. The first time the
+    /// default in-work-dir archive is created it is marked hidden on Windows,
+    /// where the leading dot alone doesn't keep it out of Explorer. A
+    /// configured archive folder was picked by the user and stays visible.
+    fn create_archive_subdir(&self, path: &Path) -> Result<(), FileError> {
+        let root_existed = self.archive_root.exists();
+        fs::create_dir_all(path).map_err(|e| FileError::CreateDirectoryFailed {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        if !root_existed && self.archive_root == self.work_dir.join(ARCHIVE_DIR) {
+            hide_dir(&self.archive_root);
+        }
+        Ok(())
     }
 
     /// Get the archive path for a specific week
@@ -63,10 +103,7 @@ impl FileRetentionService {
         let archive_path = self.week_archive_path(week);
 
         // Create archive directory if it doesn't exist
-        fs::create_dir_all(&archive_path).map_err(|e| FileError::CreateDirectoryFailed {
-            path: archive_path.clone(),
-            source: e,
-        })?;
+        self.create_archive_subdir(&archive_path)?;
 
         // Get filename and construct destination
         let file_name = file_path
@@ -79,7 +116,7 @@ impl FileRetentionService {
         let dest_path = archive_path.join(file_name);
 
         // Move the file
-        fs::rename(file_path, &dest_path).map_err(|e| FileError::MoveFileFailed {
+        move_file(file_path, &dest_path).map_err(|e| FileError::MoveFileFailed {
             from: file_path.to_path_buf(),
             to: dest_path.clone(),
             source: e,
@@ -99,10 +136,7 @@ impl FileRetentionService {
         let superseded_path = self.superseded_path(week);
 
         // Create superseded directory if it doesn't exist
-        fs::create_dir_all(&superseded_path).map_err(|e| FileError::CreateDirectoryFailed {
-            path: superseded_path.clone(),
-            source: e,
-        })?;
+        self.create_archive_subdir(&superseded_path)?;
 
         let file_name = file_path
             .file_name()
@@ -113,7 +147,7 @@ impl FileRetentionService {
             })?;
         let dest_path = superseded_path.join(file_name);
 
-        fs::rename(file_path, &dest_path).map_err(|e| FileError::MoveFileFailed {
+        move_file(file_path, &dest_path).map_err(|e| FileError::MoveFileFailed {
             from: file_path.to_path_buf(),
             to: dest_path.clone(),
             source: e,
@@ -163,10 +197,14 @@ impl FileRetentionService {
     ///
     /// - retention_days = None: Keep forever
     /// - retention_days = Some(0): Delete immediately (move to trash)
-    /// - retention_days = Some(n): Move to trash after n days
+    /// - retention_days = Some(n): Move to trash n days after the week ended
     ///
     /// Returns the number of weeks moved to trash
-    pub fn enforce_retention(&self, retention_days: Option<u32>) -> Result<u32, FileError> {
+    pub fn enforce_retention(
+        &self,
+        retention_days: Option<u32>,
+        pinned_weeks: &[WeekIdentifier],
+    ) -> Result<u32, FileError> {
         let retention_days = match retention_days {
             None => {
                 tracing::debug!("Retention policy is 'keep forever', nothing to enforce");
@@ -175,53 +213,53 @@ impl FileRetentionService {
             Some(days) => days,
         };
 
-        let cutoff_date = Utc::now() - Duration::days(retention_days as i64);
+        let cutoff_date = Utc::now().date_naive() - Duration::days(retention_days as i64);
         let archived_weeks = self.archived_week_dirs();
         tracing::debug!(
             "Enforcing retention policy: {} archived week(s) found in {:?}, retention_days={}, cutoff={}",
             archived_weeks.len(),
             self.archive_dir(),
             retention_days,
-            cutoff_date.to_rfc3339()
+            cutoff_date
         );
         let mut deleted_count = 0;
 
         for (week, week_path) in archived_weeks {
-            // Check if the week is old enough to delete
-            if let Ok(metadata) = fs::metadata(&week_path) {
-                if let Ok(modified) = metadata.modified() {
-                    let modified_datetime: chrono::DateTime<Utc> = modified.into();
-
-                    if modified_datetime < cutoff_date {
-                        // Best-effort per week: one week that can't be trashed
-                        // (permissions, locked file, ...) must not abort the
-                        // whole pass and starve the remaining weeks.
-                        match trash::delete(&week_path) {
-                            Ok(()) => {
-                                tracing::info!(
-                                    "Retention: moved archived week {} to trash (archived {}, older than {} day(s))",
-                                    week,
-                                    modified_datetime.to_rfc3339(),
-                                    retention_days
-                                );
-                                deleted_count += 1;
-                            }
-                            Err(e) => {
-                                tracing::warn!(
-                                    "Retention: failed to move archived week {} to trash, skipping: {}",
-                                    week,
-                                    e
-                                );
-                            }
-                        }
-                    } else {
-                        tracing::trace!(
-                            "Retention: keeping archived week {} (archived {}, within {} day(s))",
-                            week,
-                            modified_datetime.to_rfc3339(),
-                            retention_days
-                        );
-                    }
+            if pinned_weeks.contains(&week) {
+                tracing::trace!("Retention: keeping pinned archived week {}", week);
+                continue;
+            }
+
+            // Age comes from the week itself, not the directory mtime: that
+            // changes whenever a file is added or removed, so restoring or
+            // touching an archived week would reset its retention clock.
+            if !is_week_expired(&week, cutoff_date) {
+                tracing::trace!(
+                    "Retention: keeping archived week {} (within {} day(s))",
+                    week,
+                    retention_days
+                );
+                continue;
+            }
+
+            // Best-effort per week: one week that can't be trashed
+            // (permissions, locked file, ...) must not abort the
+            // whole pass and starve the remaining weeks.
+            match trash::delete(&week_path) {
+                Ok(()) => {
+                    tracing::info!(
+                        "Retention: moved archived week {} to trash (older than {} day(s))",
+                        week,
+                        retention_days
+                    );
+                    deleted_count += 1;
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Retention: failed to move archived week {} to trash, skipping: {}",
+                        week,
+                        e
+                    );
                 }
             }
         }
@@ -314,7 +352,7 @@ impl FileRetentionService {
             // Never descend into `.archive`, `.superseded`, or any other
             // dotdir (defensive: only ever touch directories that parse as
             // a week name).
-            if name.starts_with('.') {
+            if name.starts_with('.') || entry.path() == self.archive_root {
                 continue;
             }
             let Some(week) = parse_week_dir_name(&name) else {
@@ -350,10 +388,11 @@ impl FileRetentionService {
                     continue;
                 }
                 let file_name = file_entry.file_name();
-                if file_name.to_string_lossy().ends_with(".part") {
-                    // In-progress/resumable download (services/download.rs):
-                    // never move it, even if the queue itself doesn't (yet,
-                    // or anymore) know about it.
+                if is_partial_download_name(&file_name.to_string_lossy()) {
+                    // In-progress/resumable download (services/download.rs)
+                    // or its sidecar: never move either, even if the queue
+                    // itself doesn't (yet, or anymore) know about it. A
+                    // `.part` separated from its sidecar can't be resumed.
                     skipped_any = true;
                     continue;
                 }
@@ -396,68 +435,78 @@ impl FileRetentionService {
 
         Ok(archived_weeks)
     }
+
+    /// Archive the previous-week downloads that don't live in a week folder
+    /// (flat and by-category layouts), found through the download registry:
+    /// each file still in place moves to `.archive/{week}/`, exactly where
+    /// `archive_previous_weeks` would have put it, so retention keeps
+    /// reasoning about weeks whatever the layout. Same skips as
+    /// `archive_previous_weeks` (current and busy weeks); superseded entries
+    /// are the errata pass's business. Returns the number of files moved.
+    pub fn archive_registered_files(
+        &self,
+        registry: &[DownloadedFile],
+        current_week: &WeekIdentifier,
+        busy_weeks: &HashSet<WeekIdentifier>,
+    ) -> u32 {
+        let mut moved = 0u32;
+        for entry in registry {
+            if entry.is_superseded
+                || &entry.week == current_week
+                || busy_weeks.contains(&entry.week)
+                || entry.local_path.starts_with(&self.archive_root)
+                || !entry.local_path.is_file()
+            {
+                continue;
+            }
+            match self.archive_file(&entry.local_path, &entry.week) {
+                Ok(_) => moved += 1,
+                Err(FileError::MoveFileFailed { source, .. })
+                    if source.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => tracing::warn!(
+                    "Archiving: failed to move {} for week {}, skipping: {}",
+                    entry.local_path.display(),
+                    entry.week,
+                    e
+                ),
+            }
+        }
+        if moved > 0 {
+            tracing::info!("Archived {} file(s) of previous weeks", moved);
+        }
+        moved
+    }
+}
+
+/// Whether an archived `week` is past retention: its last day (Sunday) fell
+/// before `cutoff`. A week that ended yesterday is one day old, whatever the
+/// directory's timestamps say. Weeks with no valid calendar date are kept.
+fn is_week_expired(week: &WeekIdentifier, cutoff: NaiveDate) -> bool {
+    match week.last_day() {
+        Some(last_day) => last_day < cutoff,
+        None => {
+            tracing::warn!("Retention: week {} has no calendar date, keeping it", week);
+            false
+        }
+    }
 }
 
 /// Parse a week-named directory to a `WeekIdentifier`, recognizing both the
 /// current self-explanatory format ("W{week}-{year}-{MM}-{DD}", the Saturday
 /// of that ISO week — see `WeekIdentifier::as_dir_name`) and the legacy
 /// format ("{year}-W{week}") written by older builds, so archived/retained
-/// weeks from before the naming migration are still found. Tries the new
-/// format first, then falls back to legacy.
+/// weeks from before the naming migration are still found. See
+/// `WeekIdentifier`'s `FromStr`.
 fn parse_week_dir_name(name: &str) -> Option<WeekIdentifier> {
-    parse_new_week_dir_name(name).or_else(|| parse_legacy_week_dir_name(name))
-}
-
-/// Parse "W{week:02}-{year}-{MM}-{DD}" (or its dateless fallback
-/// "W{week:02}-{year}", written when `as_dir_name` couldn't compute a
-/// Saturday for an invalid week/year combination). When a date is present,
-/// the returned `WeekIdentifier.year` is recomputed from that date's own ISO
-/// week-year (via `NaiveDate::iso_week`) rather than taken from the embedded
-/// `{year}` component, so this round-trips correctly even across an ISO
-/// year boundary, where the Saturday's Gregorian year can differ from the
-/// ISO week-year embedded by `as_dir_name`.
-fn parse_new_week_dir_name(name: &str) -> Option<WeekIdentifier> {
-    let rest = name.strip_prefix('W')?;
-    let (week_str, tail) = rest.split_once('-')?;
-    if week_str.len() != 2 {
-        return None;
-    }
-    let week: u32 = week_str.parse().ok()?;
-    if !(1..=53).contains(&week) {
-        return None;
-    }
-
-    if let Ok(date) = NaiveDate::parse_from_str(tail, "%Y-%m-%d") {
-        let iso = date.iso_week();
-        return (iso.week() == week).then(|| WeekIdentifier::new(iso.year(), week));
-    }
-
-    // Dateless fallback: "WNN-YYYY".
-    let year: i32 = tail.parse().ok()?;
-    Some(WeekIdentifier::new(year, week))
-}
-
-/// Parse the legacy "YYYY-WNN" directory name format.
-fn parse_legacy_week_dir_name(name: &str) -> Option<WeekIdentifier> {
-    let parts: Vec<&str> = name.split("-W").collect();
-    if parts.len() != 2 {
-        return None;
-    }
-
-    let year: i32 = parts[0].parse().ok()?;
-    let week: u32 = parts[1].parse().ok()?;
-
-    if (1..=53).contains(&week) {
-        Some(WeekIdentifier::new(year, week))
-    } else {
-        None
-    }
+    name.parse().ok()
 }
 
 /// Background scheduler that periodically enforces the retention policy.
 ///
-/// Mirrors `PollingService` (see `services/polling.rs`): runs once shortly
-/// after startup and then every `RETENTION_CHECK_INTERVAL_SECS`, reading
+/// Mirrors `PollingService` (see `services/polling.rs`): checks shortly
+/// after startup and then every `RETENTION_CHECK_INTERVAL_SECS`, running
+/// retention only when the last run is a day old (`run_retention_if_due`;
+/// the polling loop calls the same gate after each poll), reading
 /// `work_directory`/`retention_days` fresh from `AppState` on every run so
 /// config changes (e.g. the user updating the retention policy in Settings)
 /// take effect on the next scheduled run without needing a restart.
@@ -503,8 +552,7 @@ impl RetentionScheduler {
             // start before touching the filesystem.
             tokio::time::sleep(TokioDuration::from_secs(STARTUP_DELAY_SECS)).await;
 
-            tracing::info!("Performing initial retention enforcement on startup");
-            run_retention_once(&app).await;
+            run_retention_if_due(&app).await;
 
             let mut ticker = interval(TokioDuration::from_secs(RETENTION_CHECK_INTERVAL_SECS));
             // `interval` fires its first tick immediately upon creation; consume
@@ -520,7 +568,7 @@ impl RetentionScheduler {
                         if !is_running.load(Ordering::SeqCst) {
                             break;
                         }
-                        run_retention_once(&app).await;
+                        run_retention_if_due(&app).await;
                     }
                     _ = cancel_rx.changed() => {
                         if *cancel_rx.borrow() {
@@ -556,37 +604,116 @@ impl Default for RetentionScheduler {
     }
 }
 
+/// Whether a retention run is due at `now`, given when the last one ran.
+/// Never run → due. A last run in the future (clock moved back) is treated
+/// as stale rather than postponing retention until the clock catches up.
+fn is_retention_due(last_run: Option<DateTime<Utc>>, now: DateTime<Utc>) -> bool {
+    match last_run {
+        None => true,
+        Some(last) if last > now => true,
+        Some(last) => now - last >= Duration::hours(RETENTION_MIN_INTERVAL_HOURS),
+    }
+}
+
+fn load_last_retention_run(app: &AppHandle) -> Option<DateTime<Utc>> {
+    use tauri_plugin_store::StoreExt;
+    let store = match app.store("settings.json") {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::error!("Retention: failed to access settings store: {}", e);
+            return None;
+        }
+    };
+    store
+        .get(LAST_RETENTION_RUN_KEY)
+        .and_then(|json| serde_json::from_value::<DateTime<Utc>>(json).ok())
+}
+
+fn persist_last_retention_run(app: &AppHandle, at: DateTime<Utc>) {
+    use tauri_plugin_store::StoreExt;
+    let store = match app.store("settings.json") {
+        Ok(store) => store,
+        Err(e) => {
+            tracing::error!("Retention: failed to access settings store: {}", e);
+            return;
+        }
+    };
+    match serde_json::to_value(at) {
+        Ok(json) => {
+            store.set(LAST_RETENTION_RUN_KEY, json);
+            if let Err(e) = store.save() {
+                tracing::error!("Retention: failed to persist last run: {}", e);
+            }
+        }
+        Err(e) => tracing::error!("Retention: failed to serialize last run: {}", e),
+    }
+}
+
+/// Enforce retention if the last run (persisted in `settings.json`) is at
+/// least a day old. Called by `RetentionScheduler` and after every poll, so
+/// neither restarts nor frequent polls make it thrash the disk.
+pub async fn run_retention_if_due(app: &AppHandle) {
+    if !is_retention_due(load_last_retention_run(app), Utc::now()) {
+        tracing::trace!("Retention: last run less than a day ago, skipping");
+        return;
+    }
+    if RETENTION_RUNNING
+        .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return;
+    }
+    if run_retention_once(app).await {
+        persist_last_retention_run(app, Utc::now());
+    }
+    RETENTION_RUNNING.store(false, Ordering::SeqCst);
+}
+
 /// Read the current work directory/retention policy from `AppState` and
 /// enforce the retention policy once. No-ops (with a debug log) if the work
 /// directory isn't configured yet, matching how `scan_and_queue` treats a
-/// missing work directory in `services/queue.rs`.
-async fn run_retention_once(app: &AppHandle) {
+/// missing work directory in `services/queue.rs`. Returns whether the
+/// policy was actually enforced.
+async fn run_retention_once(app: &AppHandle) -> bool {
     let state = app.state::<crate::commands::AppState>();
-    let (work_dir, retention_days) = match state.config.read() {
-        Ok(config) => (config.work_directory.clone(), config.retention_days),
+    let (work_dir, archive_directory, retention_days, pinned_weeks) = match state.config.read() {
+        Ok(config) => (
+            config.work_directory.clone(),
+            config.effective_archive_directory().map(Path::to_path_buf),
+            config.retention_days,
+            config.pinned_weeks.clone(),
+        ),
         Err(e) => {
             tracing::error!("Retention: failed to read config: {}", e);
-            return;
+            return false;
         }
     };
 
     let Some(work_dir) = work_dir else {
         tracing::debug!("Retention: work directory not configured yet, skipping");
-        return;
+        return false;
     };
 
     // The filesystem scan + trash move is blocking I/O; run it off the async
     // runtime (same pattern used for the filesystem checks in
     // commands::get_resource_summary).
     let result = tauri::async_runtime::spawn_blocking(move || {
-        FileRetentionService::new(work_dir).enforce_retention(retention_days)
+        let archive_root = resolve_archive_root(&work_dir, archive_directory.as_deref());
+        FileRetentionService::new(work_dir, archive_root)
+            .enforce_retention(retention_days, &pinned_weeks)
     })
     .await;
 
     match result {
-        Ok(Ok(_)) => {} // enforce_retention already logs a clear summary
-        Ok(Err(e)) => tracing::error!("Retention enforcement failed: {}", e),
-        Err(e) => tracing::error!("Retention enforcement task panicked: {}", e),
+        Ok(Ok(_)) => true, // enforce_retention already logs a clear summary
+        Ok(Err(e)) => {
+            tracing::error!("Retention enforcement failed: {}", e);
+            false
+        }
+        Err(e) => {
+            tracing::error!("Retention enforcement task panicked: {}", e);
+            false
+        }
     }
 }
 
@@ -602,8 +729,12 @@ async fn run_retention_once(app: &AppHandle) {
 /// mirroring `run_retention_once` above.
 pub async fn archive_previous_weeks_once(app: &AppHandle, current_week: &WeekIdentifier) {
     let state = app.state::<crate::commands::AppState>();
-    let work_dir = match state.config.read() {
-        Ok(config) => config.work_directory.clone(),
+    let (work_dir, archive_directory, layout) = match state.config.read() {
+        Ok(config) => (
+            config.work_directory.clone(),
+            config.effective_archive_directory().map(Path::to_path_buf),
+            config.folder_layout,
+        ),
         Err(e) => {
             tracing::error!("Archiving: failed to read config: {}", e);
             return;
@@ -619,11 +750,28 @@ pub async fn archive_previous_weeks_once(app: &AppHandle, current_week: &WeekIde
     // under a download that's still writing into it.
     let busy_weeks = state.download_queue.weeks_with_pending_downloads().await;
     let current_week = current_week.clone();
+    // Outside the week layout, files are only tied to their week by the
+    // registry.
+    let registry = if layout == FolderLayout::ByWeek {
+        Vec::new()
+    } else {
+        match state.downloaded_files.read() {
+            Ok(registry) => registry.clone(),
+            Err(e) => {
+                tracing::error!("Archiving: failed to read download registry: {}", e);
+                Vec::new()
+            }
+        }
+    };
 
     // The filesystem scan + file moves are blocking I/O; run them off the
-    // async runtime (same pattern as `run_retention_once` above).
+    // async runtime (same pattern as `run_retention_once` above). Week
+    // folders are archived whatever the layout: they may predate a switch.
     let result = tauri::async_runtime::spawn_blocking(move || {
-        FileRetentionService::new(work_dir).archive_previous_weeks(&current_week, &busy_weeks)
+        let archive_root = resolve_archive_root(&work_dir, archive_directory.as_deref());
+        let service = FileRetentionService::new(work_dir, archive_root);
+        service.archive_registered_files(&registry, &current_week, &busy_weeks);
+        service.archive_previous_weeks(&current_week, &busy_weeks)
     })
     .await;
 
@@ -634,14 +782,87 @@ pub async fn archive_previous_weeks_once(app: &AppHandle, current_week: &WeekIde
     }
 }
 
+/// Move `from` to `to`. A rename can't cross drives, and the archive may
+/// live on another one (`archive_directory` on an external disk or NAS), so
+/// a cross-device failure falls back to copying and removing the source.
+fn move_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    move_file_with(from, to, |from, to| fs::rename(from, to))
+}
+
+/// [`move_file`] with the rename injectable, so the fallback is testable
+/// without a second drive.
+fn move_file_with(
+    from: &Path,
+    to: &Path,
+    rename: impl FnOnce(&Path, &Path) -> std::io::Result<()>,
+) -> std::io::Result<()> {
+    match rename(from, to) {
+        Err(e) if e.kind() == std::io::ErrorKind::CrossesDevices => {
+            tracing::debug!("Retention: {:?} is on another drive, copying instead", to);
+            let modified = fs::metadata(from).and_then(|m| m.modified()).ok();
+            if let Err(e) = fs::copy(from, to) {
+                let _ = fs::remove_file(to);
+                return Err(e);
+            }
+            // Keep the publication time stamped on the download.
+            if let Some(modified) = modified {
+                let _ = fs::File::options()
+                    .write(true)
+                    .open(to)
+                    .and_then(|file| file.set_modified(modified));
+            }
+            fs::remove_file(from).inspect_err(|_| {
+                let _ = fs::remove_file(to);
+            })
+        }
+        result => result,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_move_file_copies_across_devices() {
+        let temp_dir = TempDir::new().unwrap();
+        let from = temp_dir.path().join("sermon.mp4");
+        let to = temp_dir.path().join("archived.mp4");
+        fs::write(&from, b"sermon").unwrap();
+        let stamped =
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        fs::File::options()
+            .write(true)
+            .open(&from)
+            .unwrap()
+            .set_modified(stamped)
+            .unwrap();
+
+        move_file_with(&from, &to, |_, _| {
+            Err(std::io::ErrorKind::CrossesDevices.into())
+        })
+        .unwrap();
+
+        assert!(!from.exists());
+        assert_eq!(fs::read(&to).unwrap(), b"sermon");
+        assert_eq!(fs::metadata(&to).unwrap().modified().unwrap(), stamped);
+
+        // Any other failure is passed through untouched.
+        fs::write(&from, b"again").unwrap();
+        let err = move_file_with(&from, &temp_dir.path().join("other.mp4"), |_, _| {
+            Err(std::io::ErrorKind::PermissionDenied.into())
+        })
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+        assert!(from.exists());
+    }
+
     fn setup_test_dir() -> (TempDir, FileRetentionService) {
         let temp_dir = TempDir::new().unwrap();
-        let service = FileRetentionService::new(temp_dir.path().to_path_buf());
+        let work_dir = temp_dir.path().to_path_buf();
+        let service =
+            FileRetentionService::new(work_dir.clone(), resolve_archive_root(&work_dir, None));
         (temp_dir, service)
     }
 
@@ -873,7 +1094,7 @@ mod tests {
     #[test]
     fn test_retention_keep_forever() {
         let (_temp_dir, service) = setup_test_dir();
-        let result = service.enforce_retention(None).unwrap();
+        let result = service.enforce_retention(None, &[]).unwrap();
         assert_eq!(result, 0);
     }
 
@@ -892,21 +1113,17 @@ mod tests {
     fn test_enforce_retention_trashes_old_weeks_keeps_recent() {
         let (temp_dir, service) = setup_test_dir();
 
+        // Age is the week's own: 2025-W40 is long past a 7-day window, the
+        // current week is not.
         let old_week = temp_dir.path().join(".archive/2025-W40");
-        let recent_week = temp_dir.path().join(".archive/2026-W01");
+        let recent_week = temp_dir
+            .path()
+            .join(".archive")
+            .join(WeekIdentifier::current().legacy_dir_name());
         fs::create_dir_all(&old_week).unwrap();
         fs::create_dir_all(&recent_week).unwrap();
 
-        // Backdate the "old" week's mtime well past a 7-day retention window;
-        // leave the "recent" week at its just-created (now) mtime.
-        let old_mtime =
-            std::time::SystemTime::now() - std::time::Duration::from_secs(10 * 24 * 60 * 60);
-        fs::File::open(&old_week)
-            .unwrap()
-            .set_modified(old_mtime)
-            .unwrap();
-
-        let trashed_count = service.enforce_retention(Some(7)).unwrap();
+        let trashed_count = service.enforce_retention(Some(7), &[]).unwrap();
 
         assert_eq!(
             trashed_count, 1,
@@ -931,18 +1148,12 @@ mod tests {
 
         let old_week = temp_dir.path().join(".archive/2025-W40");
         fs::create_dir_all(&old_week).unwrap();
-        let old_mtime =
-            std::time::SystemTime::now() - std::time::Duration::from_secs(10 * 24 * 60 * 60);
-        fs::File::open(&old_week)
-            .unwrap()
-            .set_modified(old_mtime)
-            .unwrap();
 
-        let first_run = service.enforce_retention(Some(7)).unwrap();
+        let first_run = service.enforce_retention(Some(7), &[]).unwrap();
         assert_eq!(first_run, 1);
 
         // Nothing left to evaluate: must be a stable, error-free no-op.
-        let second_run = service.enforce_retention(Some(7)).unwrap();
+        let second_run = service.enforce_retention(Some(7), &[]).unwrap();
         assert_eq!(second_run, 0);
     }
 
@@ -959,22 +1170,80 @@ mod tests {
         fs::create_dir_all(&old_legacy_week).unwrap();
         fs::create_dir_all(&old_new_week).unwrap();
 
-        let old_mtime =
-            std::time::SystemTime::now() - std::time::Duration::from_secs(10 * 24 * 60 * 60);
-        for dir in [&old_legacy_week, &old_new_week] {
-            fs::File::open(dir)
-                .unwrap()
-                .set_modified(old_mtime)
-                .unwrap();
-        }
-
-        let trashed_count = service.enforce_retention(Some(7)).unwrap();
+        let trashed_count = service.enforce_retention(Some(7), &[]).unwrap();
 
         assert_eq!(trashed_count, 2);
         assert!(!old_legacy_week.exists());
         assert!(!old_new_week.exists());
     }
 
+    /// A pinned week is kept however old it is; unpinned ones around it are
+    /// still trashed.
+    #[test]
+    fn test_enforce_retention_keeps_pinned_weeks() {
+        let (temp_dir, service) = setup_test_dir();
+
+        let christmas = WeekIdentifier::new(2025, 52);
+        let pinned = temp_dir
+            .path()
+            .join(".archive")
+            .join(christmas.as_dir_name());
+        let unpinned = temp_dir.path().join(".archive/2025-W40");
+        fs::create_dir_all(&pinned).unwrap();
+        fs::create_dir_all(&unpinned).unwrap();
+
+        assert_eq!(service.enforce_retention(Some(7), &[christmas]).unwrap(), 1);
+        assert!(pinned.exists());
+        assert!(!unpinned.exists());
+    }
+
+    /// A freshly touched directory of an old week is still old: restoring or
+    /// adding a file to an archived week must not reset its retention clock.
+    #[test]
+    fn test_enforce_retention_ignores_directory_mtime() {
+        let (temp_dir, service) = setup_test_dir();
+
+        let old_week = temp_dir.path().join(".archive/2025-W40");
+        fs::create_dir_all(&old_week).unwrap();
+        fs::write(old_week.join("restored.mp4"), b"content").unwrap();
+        fs::File::open(&old_week)
+            .unwrap()
+            .set_modified(std::time::SystemTime::now())
+            .unwrap();
+
+        assert_eq!(service.enforce_retention(Some(7), &[]).unwrap(), 1);
+        assert!(!old_week.exists());
+    }
+
+    #[test]
+    fn test_is_week_expired_by_week_identity() {
+        let current = WeekIdentifier::current();
+        let today = Utc::now().date_naive();
+        let cutoff = today - Duration::days(7);
+
+        assert!(!is_week_expired(&current, cutoff));
+        assert!(is_week_expired(&WeekIdentifier::new(2020, 10), cutoff));
+        // Retention 0: everything that has fully ended is expired, the
+        // running week is not.
+        assert!(!is_week_expired(&current, today));
+        // Week 53 of a 52-week year has no date: kept, never trashed.
+        assert!(!is_week_expired(&WeekIdentifier::new(2021, 53), cutoff));
+    }
+
+    #[test]
+    fn test_week_last_day_is_iso_sunday() {
+        assert_eq!(
+            WeekIdentifier::new(2026, 1).last_day(),
+            NaiveDate::from_ymd_opt(2026, 1, 4)
+        );
+        // ISO week 1 of 2025 ends in the new year even though it starts
+        // in December 2024.
+        assert_eq!(
+            WeekIdentifier::new(2025, 1).last_day(),
+            NaiveDate::from_ymd_opt(2025, 1, 5)
+        );
+    }
+
     // -- archive_previous_weeks (bl-desktop-archiving-not-called) -----------
 
     /// Regression guard for bl-desktop-archiving-not-called: previous weeks'
@@ -1010,6 +1279,32 @@ mod tests {
         assert!(service.week_archive_path(&old2).join("video.mp4").exists());
     }
 
+    /// 2026-W53's folder is dated in 2027 ("W53-2027-01-02"): once 2027-W01
+    /// is current it must be archived and listed as 2026-W53, not misread as
+    /// a 2027 week or left behind.
+    #[test]
+    fn test_archive_previous_weeks_handles_w53() {
+        let (temp_dir, service) = setup_test_dir();
+        let w53 = WeekIdentifier::new(2026, 53);
+        let current = WeekIdentifier::new(2027, 1);
+
+        for week in [&w53, &current] {
+            let dir = temp_dir.path().join(week.as_dir_name());
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("video.mp4"), b"content").unwrap();
+        }
+
+        let archived = service
+            .archive_previous_weeks(&current, &HashSet::new())
+            .unwrap();
+
+        assert_eq!(archived, 1);
+        assert!(temp_dir.path().join(current.as_dir_name()).exists());
+        assert!(service.week_archive_path(&w53).ends_with("W53-2027-01-02"));
+        assert!(service.week_archive_path(&w53).join("video.mp4").exists());
+        assert_eq!(service.get_archived_weeks(), vec![w53]);
+    }
+
     /// Re-running after everything has already been archived must be a
     /// stable no-op: no errors, nothing re-counted, no duplicate files.
     #[test]
@@ -1083,6 +1378,7 @@ mod tests {
         fs::create_dir_all(&dir).unwrap();
         fs::write(dir.join("done.mp4"), b"complete").unwrap();
         fs::write(dir.join("still-downloading.mp4.part"), b"partial").unwrap();
+        fs::write(dir.join("still-downloading.mp4.part.meta"), b"{}").unwrap();
 
         let archived = service
             .archive_previous_weeks(&current, &HashSet::new())
@@ -1097,6 +1393,10 @@ mod tests {
             dir.join("still-downloading.mp4.part").exists(),
             ".part file must never be moved"
         );
+        assert!(
+            dir.join("still-downloading.mp4.part.meta").exists(),
+            ".part.meta sidecar must stay with its .part"
+        );
         assert!(
             dir.exists(),
             "week folder must stay in place while a .part file remains"
@@ -1150,11 +1450,102 @@ mod tests {
     fn test_archive_previous_weeks_missing_work_dir_is_a_noop() {
         let temp_dir = TempDir::new().unwrap();
         let missing = temp_dir.path().join("does-not-exist");
-        let service = FileRetentionService::new(missing);
+        let service =
+            FileRetentionService::new(missing.clone(), resolve_archive_root(&missing, None));
 
         let archived = service
             .archive_previous_weeks(&WeekIdentifier::new(2026, 4), &HashSet::new())
             .unwrap();
         assert_eq!(archived, 0);
     }
+
+    #[test]
+    fn test_configured_archive_root_is_used_for_archiving_and_listing() {
+        let work = TempDir::new().unwrap();
+        let elsewhere = TempDir::new().unwrap();
+        let archive_root = resolve_archive_root(work.path(), Some(elsewhere.path()));
+        assert_eq!(archive_root, elsewhere.path());
+        let service = FileRetentionService::new(work.path().to_path_buf(), archive_root);
+
+        let week = WeekIdentifier::new(2026, 4);
+        let week_dir = work.path().join(week.as_dir_name());
+        fs::create_dir_all(&week_dir).unwrap();
+        fs::write(week_dir.join("video.mp4"), b"content").unwrap();
+
+        let archived = service
+            .archive_previous_weeks(&WeekIdentifier::new(2026, 5), &HashSet::new())
+            .unwrap();
+        assert_eq!(archived, 1);
+        assert!(elsewhere
+            .path()
+            .join(week.as_dir_name())
+            .join("video.mp4")
+            .exists());
+        assert!(!work.path().join(ARCHIVE_DIR).exists());
+        assert_eq!(service.get_archived_weeks(), vec![week]);
+    }
+
+    /// An archive folder configured inside the work directory is never
+    /// mistaken for a week folder to archive.
+    #[test]
+    fn test_archive_root_inside_work_dir_is_not_archived_into_itself() {
+        let work = TempDir::new().unwrap();
+        let week = WeekIdentifier::new(2026, 4);
+        // Deliberately week-named, the worst case for the work-dir scan.
+        let archive_root = work.path().join(week.as_dir_name());
+        let service = FileRetentionService::new(work.path().to_path_buf(), archive_root.clone());
+        fs::create_dir_all(&archive_root).unwrap();
+        fs::write(archive_root.join("keep.txt"), b"x").unwrap();
+
+        let archived = service
+            .archive_previous_weeks(&WeekIdentifier::new(2026, 5), &HashSet::new())
+            .unwrap();
+        assert_eq!(archived, 0);
+        assert!(archive_root.join("keep.txt").exists());
+    }
+
+    #[test]
+    fn test_retention_due_once_a_day() {
+        let now = Utc::now();
+        assert!(is_retention_due(None, now));
+        assert!(!is_retention_due(Some(now - Duration::hours(23)), now));
+        assert!(is_retention_due(Some(now - Duration::hours(24)), now));
+        // Clock moved backwards: don't wait for it to catch up.
+        assert!(is_retention_due(Some(now + Duration::hours(2)), now));
+    }
+
+    /// Flat layout: previous-week files named by the registry move into
+    /// their week's archive folder; the current week's stay put.
+    #[test]
+    fn test_archive_registered_files_groups_flat_files_by_week() {
+        let (temp_dir, service) = setup_test_dir();
+        let current = WeekIdentifier::new(2026, 4);
+        let old = WeekIdentifier::new(2026, 3);
+        let entry = |name: &str, week: &WeekIdentifier| {
+            let path = temp_dir.path().join(name);
+            fs::write(&path, b"content").unwrap();
+            DownloadedFile {
+                resource_id: 1,
+                week: week.clone(),
+                local_path: path,
+                downloaded_at: Utc::now(),
+                source_url: String::new(),
+                is_superseded: false,
+            }
+        };
+        let registry = vec![entry("old.mp4", &old), entry("new.mp4", &current)];
+
+        let moved = service.archive_registered_files(&registry, &current, &HashSet::new());
+
+        assert_eq!(moved, 1);
+        assert!(service.week_archive_path(&old).join("old.mp4").exists());
+        assert!(!temp_dir.path().join("old.mp4").exists());
+        assert!(temp_dir.path().join("new.mp4").exists());
+
+        // Already archived: a second run finds nothing to move.
+        assert_eq!(
+            service.archive_registered_files(&registry, &current, &HashSet::new()),
+            0
+        );
+    }
 }
@@ -0,0 +1,271 @@
+//! Bounded cache of remote file sizes
+//!
+//! Backs `AppState::file_size_cache`: HEAD-derived sizes keyed by download
+//! URL, plus failed lookups (negative cache) so a broken URL isn't re-HEADed
+//! on every render. A failure only stands for [`NEGATIVE_SIZE_CACHE_TTL`]:
+//! after that the entry reads as a miss and the HEAD is retried, so a
+//! transient server hiccup doesn't leave the size "unavailable" forever.
+//! Polls drop URLs that left the resource list, but sizes fetched on demand
+//! (optimized variants, detail views) would otherwise pile up over months, so
+//! the cache holds at most [`FILE_SIZE_CACHE_CAPACITY`] entries and evicts the
+//! least recently accessed one on insert.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Maximum number of URLs whose size is remembered.
+pub const FILE_SIZE_CACHE_CAPACITY: usize = 1000;
+
+/// How long a failed size lookup is served from the cache before retrying.
+pub const NEGATIVE_SIZE_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// What the cache knows about one URL's size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachedSize {
+    Known(u64),
+    /// The HEAD failed (or had no usable Content-Length) at `since`.
+    Unavailable {
+        since: Instant,
+    },
+}
+
+impl CachedSize {
+    pub fn known(self) -> Option<u64> {
+        match self {
+            CachedSize::Known(size) => Some(size),
+            CachedSize::Unavailable { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    size: CachedSize,
+    /// Logical access time (`clock` at the last `get`/`insert`); the
+    /// smallest one is evicted first.
+    last_accessed: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileSizeCache {
+    entries: HashMap<String, Entry>,
+    clock: u64,
+    capacity: usize,
+}
+
+impl FileSizeCache {
+    pub fn new() -> Self {
+        Self::with_capacity(FILE_SIZE_CACHE_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            clock: 0,
+            capacity,
+        }
+    }
+
+    /// Build a cache from a persisted size map, keeping at most the capacity.
+    /// `u64::MAX` was the negative-cache sentinel of older builds and is
+    /// dropped, so those URLs get retried.
+    pub fn from_sizes(sizes: HashMap<String, u64>) -> Self {
+        let mut cache = Self::new();
+        for (url, size) in sizes {
+            if size != u64::MAX {
+                cache.insert(url, size);
+            }
+        }
+        cache
+    }
+
+    /// What's cached for `url`, marking it as recently used. A failure older
+    /// than [`NEGATIVE_SIZE_CACHE_TTL`] is dropped and reads as a miss.
+    pub fn get(&mut self, url: &str) -> Option<CachedSize> {
+        self.get_at(url, Instant::now())
+    }
+
+    fn get_at(&mut self, url: &str, now: Instant) -> Option<CachedSize> {
+        let size = self.entries.get(url)?.size;
+        if let CachedSize::Unavailable { since } = size {
+            if now.saturating_duration_since(since) >= NEGATIVE_SIZE_CACHE_TTL {
+                self.entries.remove(url);
+                return None;
+            }
+        }
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(url) {
+            entry.last_accessed = self.clock;
+        }
+        Some(size)
+    }
+
+    /// Store a successfully fetched size for `url`.
+    pub fn insert(&mut self, url: String, size: u64) {
+        self.put(url, CachedSize::Known(size));
+    }
+
+    /// Remember that looking up `url`'s size just failed.
+    pub fn record_failure(&mut self, url: String) {
+        self.put(
+            url,
+            CachedSize::Unavailable {
+                since: Instant::now(),
+            },
+        );
+    }
+
+    /// Evicts the least recently accessed entry when this pushes the cache
+    /// over capacity.
+    fn put(&mut self, url: String, size: CachedSize) {
+        self.clock += 1;
+        self.entries.insert(
+            url,
+            Entry {
+                size,
+                last_accessed: self.clock,
+            },
+        );
+
+        while self.entries.len() > self.capacity {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_accessed)
+                .map(|(url, _)| url.clone())
+            else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+    }
+
+    pub fn remove(&mut self, url: &str) -> Option<CachedSize> {
+        self.entries.remove(url).map(|entry| entry.size)
+    }
+
+    /// Successfully fetched size for `url`, without counting as an access:
+    /// for bulk lookups (status, pending bytes) where failures just mean
+    /// "unknown".
+    pub fn known_size(&self, url: &str) -> Option<u64> {
+        self.entries.get(url).and_then(|entry| entry.size.known())
+    }
+
+    /// Every successfully fetched size: what gets persisted to `cache.json`.
+    /// Failures are session-only.
+    pub fn known_sizes(&self) -> HashMap<String, u64> {
+        self.entries
+            .iter()
+            .filter_map(|(url, entry)| entry.size.known().map(|size| (url.clone(), size)))
+            .collect()
+    }
+
+    pub fn urls(&self) -> impl Iterator<Item = &String> {
+        self.entries.keys()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for FileSizeCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_evicts_least_recently_accessed() {
+        let mut cache = FileSizeCache::with_capacity(2);
+        cache.insert("a".to_string(), 1);
+        cache.insert("b".to_string(), 2);
+        // Touching "a" makes "b" the oldest.
+        assert_eq!(cache.get("a"), Some(CachedSize::Known(1)));
+        cache.insert("c".to_string(), 3);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("a"), Some(CachedSize::Known(1)));
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("c"), Some(CachedSize::Known(3)));
+    }
+
+    #[test]
+    fn test_reinsert_updates_without_growing() {
+        let mut cache = FileSizeCache::with_capacity(2);
+        cache.insert("a".to_string(), 1);
+        cache.record_failure("a".to_string());
+        assert_eq!(cache.len(), 1);
+        assert!(matches!(
+            cache.get("a"),
+            Some(CachedSize::Unavailable { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_sizes_respects_capacity() {
+        let sizes: HashMap<String, u64> = (0..FILE_SIZE_CACHE_CAPACITY + 50)
+            .map(|i| (format!("https://example.com/{i}.mp4"), i as u64))
+            .collect();
+        let cache = FileSizeCache::from_sizes(sizes);
+        assert_eq!(cache.len(), FILE_SIZE_CACHE_CAPACITY);
+    }
+
+    #[test]
+    fn test_remove_forgets_the_entry() {
+        let mut cache = FileSizeCache::with_capacity(3);
+        cache.insert("keep".to_string(), 1);
+        cache.insert("gone".to_string(), 3);
+
+        assert_eq!(cache.remove("gone"), Some(CachedSize::Known(3)));
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.known_size("keep"), Some(1));
+    }
+
+    /// A failed lookup is served from the cache within the TTL, then reads
+    /// as a miss so the caller retries the HEAD.
+    #[test]
+    fn test_negative_entry_is_retried_after_ttl() {
+        let mut cache = FileSizeCache::new();
+        cache.record_failure("https://example.com/a.mp4".to_string());
+        let Some(CachedSize::Unavailable { since }) = cache.get("https://example.com/a.mp4") else {
+            panic!("failure should be cached");
+        };
+
+        let just_before = since + NEGATIVE_SIZE_CACHE_TTL - Duration::from_secs(1);
+        assert!(cache
+            .get_at("https://example.com/a.mp4", just_before)
+            .is_some());
+
+        let expired = since + NEGATIVE_SIZE_CACHE_TTL;
+        assert_eq!(cache.get_at("https://example.com/a.mp4", expired), None);
+        assert!(cache.is_empty());
+    }
+
+    /// Failures are never persisted, and a legacy `u64::MAX` sentinel from
+    /// an older build's `cache.json` is not restored as a size.
+    #[test]
+    fn test_known_sizes_excludes_failures_and_legacy_sentinel() {
+        let mut cache = FileSizeCache::from_sizes(HashMap::from([
+            ("https://example.com/a.mp4".to_string(), 1024),
+            ("https://example.com/b.mp4".to_string(), u64::MAX),
+            ("https://example.com/c.pdf".to_string(), 0),
+        ]));
+        assert_eq!(cache.len(), 2);
+        cache.record_failure("https://example.com/d.mp4".to_string());
+
+        let persisted = cache.known_sizes();
+        assert_eq!(persisted.len(), 2);
+        assert_eq!(persisted.get("https://example.com/a.mp4"), Some(&1024));
+        assert_eq!(persisted.get("https://example.com/c.pdf"), Some(&0));
+        assert_eq!(cache.known_size("https://example.com/d.mp4"), None);
+    }
+}
@@ -0,0 +1,168 @@
+//! Thumbnail cache
+//!
+//! Fetches resource thumbnails through the shared HTTP client and keeps them
+//! in the app cache directory, keyed by a SHA-256 of the URL, so the resource
+//! list renders instantly (and offline) after the first load instead of
+//! re-downloading every image. Only actual images up to
+//! [`THUMBNAIL_MAX_BYTES`] are accepted and cached.
+
+use crate::error::ThumbnailError;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Largest thumbnail accepted; anything bigger isn't a thumbnail.
+pub const THUMBNAIL_MAX_BYTES: u64 = 2 * 1024 * 1024;
+/// Subdirectory of the app cache directory holding cached thumbnails.
+const THUMBNAIL_DIR: &str = "thumbnails";
+
+/// Where the thumbnail for `url` is cached under `cache_dir`.
+pub fn thumbnail_cache_path(cache_dir: &Path, url: &str) -> PathBuf {
+    let hash = hex::encode(Sha256::digest(url.as_bytes()));
+    cache_dir.join(THUMBNAIL_DIR).join(hash)
+}
+
+/// Bytes of the thumbnail at `url`: from the disk cache when present,
+/// otherwise downloaded, checked and cached. A failure to write the cache is
+/// only logged — the image is still returned.
+pub async fn get_thumbnail(
+    client: &reqwest::Client,
+    cache_dir: &Path,
+    url: &str,
+) -> Result<Vec<u8>, ThumbnailError> {
+    let path = thumbnail_cache_path(cache_dir, url);
+    if let Ok(bytes) = tokio::fs::read(&path).await {
+        tracing::debug!("Thumbnail cache hit: {}", url);
+        return Ok(bytes);
+    }
+
+    let mut response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(ThumbnailError::Status(response.status().as_u16()));
+    }
+
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if !content_type.starts_with("image/") {
+        return Err(ThumbnailError::NotAnImage(content_type));
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > THUMBNAIL_MAX_BYTES)
+    {
+        return Err(ThumbnailError::TooLarge(THUMBNAIL_MAX_BYTES));
+    }
+
+    // Content-Length can be missing or wrong: enforce the cap while reading.
+    let mut bytes = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if (bytes.len() + chunk.len()) as u64 > THUMBNAIL_MAX_BYTES {
+            return Err(ThumbnailError::TooLarge(THUMBNAIL_MAX_BYTES));
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+
+    if let Err(e) = write_cache(&path, &bytes).await {
+        tracing::warn!("Failed to cache thumbnail {}: {}", url, e);
+    }
+    Ok(bytes)
+}
+
+/// Delete every cached thumbnail under `cache_dir`; returns how many files
+/// were removed. A cache that was never created counts as empty.
+pub fn clear_thumbnail_cache(cache_dir: &Path) -> std::io::Result<usize> {
+    let dir = cache_dir.join(THUMBNAIL_DIR);
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let mut removed = 0;
+    for entry in entries.filter_map(Result::ok) {
+        if entry.file_type().is_ok_and(|t| t.is_file())
+            && std::fs::remove_file(entry.path()).is_ok()
+        {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// Write through a temp file and rename, so a crash mid-write never leaves a
+/// truncated image to be served from the cache.
+async fn write_cache(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await?;
+    }
+    let tmp = path.with_extension("tmp");
+    tokio::fs::write(&tmp, bytes).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::mock_http::{self, Response};
+
+    /// Answer `n` requests with `content_type` and `body`.
+    fn serve(n: usize, content_type: &'static str, body: &'static [u8]) -> String {
+        mock_http::serve(n, move |_, _| {
+            Response::ok(body).header("Content-Type", content_type)
+        })
+    }
+
+    #[test]
+    fn test_clear_thumbnail_cache_counts_removed_files() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        assert_eq!(clear_thumbnail_cache(tmp.path()).unwrap(), 0);
+
+        for url in ["https://example.com/a.jpg", "https://example.com/b.jpg"] {
+            let path = thumbnail_cache_path(tmp.path(), url);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, b"img").unwrap();
+        }
+        assert_eq!(clear_thumbnail_cache(tmp.path()).unwrap(), 2);
+        assert_eq!(clear_thumbnail_cache(tmp.path()).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_cache_path_is_stable_per_url() {
+        let dir = Path::new("/cache");
+        let a = thumbnail_cache_path(dir, "https://example.com/a.jpg");
+        assert_eq!(a, thumbnail_cache_path(dir, "https://example.com/a.jpg"));
+        assert_ne!(a, thumbnail_cache_path(dir, "https://example.com/b.jpg"));
+        assert!(a.starts_with("/cache/thumbnails"));
+    }
+
+    /// The server answers once: the second call can only succeed from disk.
+    #[tokio::test]
+    async fn test_get_thumbnail_serves_second_call_from_cache() {
+        let base = serve(1, "image/jpeg", b"jpeg bytes");
+        let tmp = tempfile::TempDir::new().unwrap();
+        let client = reqwest::Client::new();
+        let url = format!("{base}/thumb.jpg");
+
+        let first = get_thumbnail(&client, tmp.path(), &url).await.unwrap();
+        assert_eq!(first, b"jpeg bytes");
+        assert!(thumbnail_cache_path(tmp.path(), &url).exists());
+
+        let second = get_thumbnail(&client, tmp.path(), &url).await.unwrap();
+        assert_eq!(second, first);
+    }
+
+    #[tokio::test]
+    async fn test_get_thumbnail_rejects_non_image_without_caching() {
+        let base = serve(1, "text/html; charset=utf-8", b"<html>login</html>");
+        let tmp = tempfile::TempDir::new().unwrap();
+        let url = format!("{base}/thumb.jpg");
+
+        let err = get_thumbnail(&reqwest::Client::new(), tmp.path(), &url)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ThumbnailError::NotAnImage(ct) if ct.starts_with("text/html")));
+        assert!(!thumbnail_cache_path(tmp.path(), &url).exists());
+    }
+}
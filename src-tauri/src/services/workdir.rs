@@ -0,0 +1,85 @@
+//! Work directory availability
+//!
+//! The work directory often lives on a USB or network drive. When it goes
+//! away, downloads would fail one by one with raw IO errors (or, worse,
+//! `create_dir_all` would rebuild the path on the internal disk under an
+//! empty mount point). Callers check [`refresh_availability`] before
+//! queueing or writing; the result is mirrored in
+//! `AppStatus::work_directory_unavailable` and announced on each change with
+//! `work-directory-unavailable` / `work-directory-available`.
+
+use crate::commands::AppState;
+use std::path::Path;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Whether `work_dir` is usable right now: it exists and is a directory.
+pub fn is_available(work_dir: &Path) -> bool {
+    work_dir.is_dir()
+}
+
+/// Event to emit when availability goes from `was_unavailable` to
+/// `available`; `None` when nothing changed.
+fn transition_event(was_unavailable: bool, available: bool) -> Option<&'static str> {
+    match (was_unavailable, available) {
+        (false, false) => Some("work-directory-unavailable"),
+        (true, true) => Some("work-directory-available"),
+        _ => None,
+    }
+}
+
+/// Check `work_dir`, record the result in `AppStatus` and emit an event if
+/// it changed. Returns whether the directory is available.
+pub fn refresh_availability(app: &AppHandle, work_dir: &Path) -> bool {
+    let available = is_available(work_dir);
+    let state = app.state::<AppState>();
+    let was_unavailable = match state.status.write() {
+        Ok(mut status) => std::mem::replace(&mut status.work_directory_unavailable, !available),
+        Err(e) => {
+            tracing::error!("Work directory: failed to update status: {}", e);
+            return available;
+        }
+    };
+
+    if let Some(event) = transition_event(was_unavailable, available) {
+        if available {
+            tracing::info!("Work directory {:?} is available again", work_dir);
+        } else {
+            tracing::warn!("Work directory {:?} is unavailable", work_dir);
+        }
+        if let Err(e) = app.emit(event, work_dir) {
+            tracing::error!("Work directory: failed to emit {}: {}", event, e);
+        }
+    }
+    available
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_is_available() {
+        let tmp = TempDir::new().unwrap();
+        assert!(is_available(tmp.path()));
+        assert!(!is_available(&tmp.path().join("unplugged")));
+
+        let file = tmp.path().join("file.txt");
+        std::fs::write(&file, b"x").unwrap();
+        assert!(!is_available(&file));
+    }
+
+    #[test]
+    fn test_transition_event_only_on_change() {
+        assert_eq!(
+            transition_event(false, false),
+            Some("work-directory-unavailable")
+        );
+        assert_eq!(
+            transition_event(true, true),
+            Some("work-directory-available")
+        );
+        assert_eq!(transition_event(false, true), None);
+        assert_eq!(transition_event(true, false), None);
+    }
+}
@@ -0,0 +1,235 @@
+//! yt-dlp progress parsing
+//!
+//! yt-dlp reports progress as human-readable stdout lines such as
+//! `[download]  45.2% of 120.5MiB at 2.1MiB/s ETA 00:30`. This module turns
+//! those lines into the same `download-progress` payload HTTP downloads emit
+//! (`id`, `progress`, `current_bytes`, `total_bytes`), so the frontend treats
+//! both kinds of download uniformly. Once the download itself is done,
+//! yt-dlp merges/remuxes with ffmpeg for an unknown time: those lines map to
+//! an indeterminate `processing` state instead of a percentage.
+//!
+//! Parsing and eventing only: whatever spawns yt-dlp feeds each stdout line
+//! to [`emit_progress_line`].
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter};
+
+/// Post-processor prefixes yt-dlp prints after the download phase.
+const PROCESSING_PREFIXES: &[&str] = &[
+    "[Merger]",
+    "[ffmpeg]",
+    "[ExtractAudio]",
+    "[VideoConvertor]",
+    "[VideoRemuxer]",
+    "[Fixup",
+];
+
+/// One meaningful yt-dlp progress line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum YtDlpProgress {
+    Downloading {
+        percent: f64,
+        /// `None` when yt-dlp doesn't know the size yet.
+        total_bytes: Option<u64>,
+        speed_bytes_per_sec: Option<u64>,
+        eta_secs: Option<u64>,
+    },
+    /// Merge/ffmpeg post-processing: no measurable progress.
+    Processing,
+}
+
+/// `download-progress` payload, the HTTP download's shape plus the fields
+/// only yt-dlp reports. `progress` is `None` while `processing`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProgressPayload {
+    pub id: i64,
+    pub progress: Option<u8>,
+    pub current_bytes: Option<u64>,
+    pub total_bytes: Option<u64>,
+    pub speed_bytes_per_sec: Option<u64>,
+    pub eta_secs: Option<u64>,
+    pub processing: bool,
+}
+
+/// Parse one stdout line; `None` for lines that carry no progress
+/// (destination notices, warnings, …).
+pub fn parse_progress_line(line: &str) -> Option<YtDlpProgress> {
+    let line = line.trim();
+    if PROCESSING_PREFIXES.iter().any(|p| line.starts_with(p)) {
+        return Some(YtDlpProgress::Processing);
+    }
+
+    let rest = line.strip_prefix("[download]")?.trim_start();
+    let (percent, rest) = rest.split_once('%')?;
+    let percent: f64 = percent.trim().parse().ok()?;
+
+    let mut total_bytes = None;
+    let mut speed_bytes_per_sec = None;
+    let mut eta_secs = None;
+    let mut tokens = rest.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            // "of ~120.5MiB" when the size is an estimate.
+            "of" => {
+                total_bytes = tokens
+                    .next()
+                    .and_then(|t| parse_size(t.trim_start_matches('~')))
+            }
+            "at" => {
+                speed_bytes_per_sec = tokens
+                    .next()
+                    .and_then(|t| t.strip_suffix("/s"))
+                    .and_then(parse_size)
+            }
+            "ETA" => eta_secs = tokens.next().and_then(parse_duration),
+            _ => {}
+        }
+    }
+
+    Some(YtDlpProgress::Downloading {
+        percent: percent.clamp(0.0, 100.0),
+        total_bytes,
+        speed_bytes_per_sec,
+        eta_secs,
+    })
+}
+
+impl YtDlpProgress {
+    pub fn to_payload(&self, id: i64) -> ProgressPayload {
+        match self {
+            YtDlpProgress::Downloading {
+                percent,
+                total_bytes,
+                speed_bytes_per_sec,
+                eta_secs,
+            } => ProgressPayload {
+                id,
+                progress: Some(*percent as u8),
+                current_bytes: total_bytes.map(|total| (total as f64 * percent / 100.0) as u64),
+                total_bytes: *total_bytes,
+                speed_bytes_per_sec: *speed_bytes_per_sec,
+                eta_secs: *eta_secs,
+                processing: false,
+            },
+            YtDlpProgress::Processing => ProgressPayload {
+                id,
+                progress: None,
+                current_bytes: None,
+                total_bytes: None,
+                speed_bytes_per_sec: None,
+                eta_secs: None,
+                processing: true,
+            },
+        }
+    }
+}
+
+/// Emit `download-progress` (and its `download-event`) for resource `id` if
+/// `line` is a progress line.
+pub fn emit_progress_line(app: &AppHandle, id: i64, line: &str) {
+    use crate::services::download_events::{DownloadEvent, DownloadPhase};
+    if let Some(progress) = parse_progress_line(line) {
+        let payload = progress.to_payload(id);
+        DownloadEvent::new(id, DownloadPhase::Progress)
+            .with_progress(payload.progress, payload.current_bytes, payload.total_bytes)
+            .emit(app);
+        let _ = app.emit("download-progress", payload);
+    }
+}
+
+/// "120.5MiB" / "2.1MB" / "512B" → bytes.
+fn parse_size(s: &str) -> Option<u64> {
+    let split = s.find(|c: char| !(c.is_ascii_digit() || c == '.'))?;
+    let (number, unit) = s.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: f64 = match unit {
+        "B" => 1.0,
+        "KiB" => 1024.0,
+        "MiB" => 1024.0 * 1024.0,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        "KB" | "kB" => 1e3,
+        "MB" => 1e6,
+        "GB" => 1e9,
+        "TB" => 1e12,
+        _ => return None,
+    };
+    Some((number * multiplier) as u64)
+}
+
+/// "00:30" / "01:02:03" → seconds; "Unknown" → `None`.
+fn parse_duration(s: &str) -> Option<u64> {
+    s.split(':').try_fold(0u64, |acc, part| {
+        part.parse::<u64>().ok().map(|n| acc * 60 + n)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_download_line() {
+        let progress =
+            parse_progress_line("[download]  45.2% of 120.5MiB at 2.1MiB/s ETA 00:30").unwrap();
+        assert_eq!(
+            progress,
+            YtDlpProgress::Downloading {
+                percent: 45.2,
+                total_bytes: Some((120.5 * 1024.0 * 1024.0) as u64),
+                speed_bytes_per_sec: Some((2.1 * 1024.0 * 1024.0) as u64),
+                eta_secs: Some(30),
+            }
+        );
+
+        let payload = progress.to_payload(7);
+        assert_eq!(payload.id, 7);
+        assert_eq!(payload.progress, Some(45));
+        assert_eq!(payload.total_bytes, Some(126_353_408));
+        assert_eq!(payload.current_bytes, Some(57_111_740));
+        assert!(!payload.processing);
+    }
+
+    #[test]
+    fn test_parse_estimated_and_unknown_fields() {
+        let progress =
+            parse_progress_line("[download]   3.0% of ~1.20GiB at Unknown speed ETA Unknown")
+                .unwrap();
+        let YtDlpProgress::Downloading {
+            total_bytes,
+            speed_bytes_per_sec,
+            eta_secs,
+            ..
+        } = progress
+        else {
+            panic!("expected a download line");
+        };
+        assert_eq!(total_bytes, Some((1.2 * 1024.0 * 1024.0 * 1024.0) as u64));
+        assert_eq!(speed_bytes_per_sec, None);
+        assert_eq!(eta_secs, None);
+
+        let done = parse_progress_line("[download] 100% of 120.5MiB in 01:02:03").unwrap();
+        assert_eq!(done.to_payload(1).progress, Some(100));
+    }
+
+    #[test]
+    fn test_parse_processing_and_noise() {
+        assert_eq!(
+            parse_progress_line("[Merger] Merging formats into \"video.mp4\""),
+            Some(YtDlpProgress::Processing)
+        );
+        assert_eq!(
+            parse_progress_line("[FixupM3u8] Fixing MPEG-TS in MP4 container"),
+            Some(YtDlpProgress::Processing)
+        );
+        let payload = YtDlpProgress::Processing.to_payload(3);
+        assert!(payload.processing);
+        assert_eq!(payload.progress, None);
+
+        assert_eq!(
+            parse_progress_line("[download] Destination: video.f137.mp4"),
+            None
+        );
+        assert_eq!(parse_progress_line("WARNING: something"), None);
+    }
+}
@@ -4,12 +4,14 @@
 //! Its purpose is to guard the reqwest 0.13 upgrade, which switched the default
 //! TLS backend from native-tls to rustls: it performs a genuine HTTPS GET with a
 //! `reqwest::Client` built exactly like the app's `shared_http_client`
-//! (`reqwest::Client::new()`) and asserts the handshake succeeds, the status is
-//! 200, and the body deserializes into `ResourceListResponse`.
+//! (`services::http::build_http_client(None, &HashMap::new(), None)`) and asserts the handshake succeeds,
+//! the status is 200, and the body deserializes into `ResourceListResponse`.
 //!
 //! Run explicitly with: `cargo test --test real_api_tls_smoke -- --ignored`
 
 use church_helper_desktop_lib::models::ResourceListResponse;
+use church_helper_desktop_lib::services::http::build_http_client;
+use std::collections::HashMap;
 
 #[tokio::test]
 #[ignore = "hits the real production API over HTTPS; run with --ignored"]
@@ -17,7 +19,7 @@ async fn real_api_tls_smoke() {
     let url = "https://api.adventistyouth.it/api/resources/latest-week";
 
     // Same client configuration the app uses for its shared HTTP client.
-    let client = reqwest::Client::new();
+    let client = build_http_client(None, &HashMap::new(), None);
 
     let response = client
         .get(url)
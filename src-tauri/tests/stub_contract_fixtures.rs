@@ -7,7 +7,7 @@
 //! `POST /stub/scenario/:name` for the relevant scenario.
 
 use church_helper_desktop_lib::models::ResourceListResponse;
-use church_helper_desktop_lib::services::FileRetentionService;
+use church_helper_desktop_lib::services::{resolve_archive_root, FileRetentionService};
 
 const MULTI_VIDEO_JSON: &str = include_str!("fixtures/stub_multi_video_latest_week.json");
 const OLD_WEEKS_JSON: &str = include_str!("fixtures/stub_old_weeks_latest_week.json");
@@ -153,7 +153,9 @@ fn test_old_weeks_fixture_drives_archive_previous_weeks() {
         std::fs::write(dir.join("video.mp4"), b"fixture content").unwrap();
     }
 
-    let service = FileRetentionService::new(temp_dir.path().to_path_buf());
+    let work_dir = temp_dir.path().to_path_buf();
+    let archive_root = resolve_archive_root(&work_dir, None);
+    let service = FileRetentionService::new(work_dir, archive_root);
     let archived = service
         .archive_previous_weeks(&current, &std::collections::HashSet::new())
         .unwrap();
@@ -23,14 +23,34 @@ export interface ActiveDownload {
   startTime?: number;
 }
 
+// Mirrors the Rust `QueueStatusPayload` in src-tauri/src/services/queue.rs.
+// `version` is bumped when the shape changes incompatibly.
 export interface QueueStatusPayload {
-  queued: Array<{id: number, position: number}>;
+  version: number;
+  queued: Array<{
+    id: number,
+    position: number,
+    title: string,
+    category: string,
+    file_size: number|null
+  }>;
   active: number[];
+  active_items: Array<{
+    id: number,
+    title: string|null,
+    category: string|null,
+    file_size: number|null,
+    downloaded_bytes: number|null,
+    total_bytes: number|null
+  }>;
 }
 
 export interface ResourceSummary {
   total: number;
   downloaded: number;
+  // Not downloaded yet but with a .part on disk (running, paused or
+  // interrupted).
+  downloading: number;
   active: number;
   queued: number;
 }
@@ -453,6 +473,19 @@ export const useAppStore = create<AppState>(
             debouncedFetchStatuses();
           }),
 
+          // Listen for a stopped download (halted on the stop signal with its
+          // .part kept for a later manual resume). Like a cancel, it leaves
+          // the active downloads; the summary's partial count picks it up.
+          listen<number>('download-stopped', (event) => {
+            const resourceId = event.payload;
+            set(state => {
+              const {[resourceId]: _removed, ...rest} = state.activeDownloads;
+              return {activeDownloads: rest};
+            });
+            debouncedFetchSummary();
+            debouncedFetchStatuses();
+          }),
+
           // Errata corrige detected: the backend has already archived the old
           // file, marked the registry, and re-queued the updated download
           // (see errata.rs::process_errata). The UI stays dumb — just refresh
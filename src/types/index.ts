@@ -9,9 +9,12 @@ export interface WeekIdentifier {
 // identifier the UI can branch on (e.g. 'work-dir-not-set', 'api-unreachable');
 // `message` is the human-readable detail to show the user. Use `errorMessage()`
 // / `isCommandError()` from lib/utils to consume caught values safely.
+// `details` (absent when there is none) carries structured context such as
+// the `path` or `url` involved, or an HTTP `status`.
 export interface CommandError {
   code: string;
   message: string;
+  details?: Record<string, unknown>;
 }
 
 // A single optimized video variant produced by the re-encoder from a
@@ -54,6 +57,9 @@ export type ThemeSetting = 'System'|'Light'|'Dark';
 // UI language. Mirrors the Rust `LanguageSetting` enum (src-tauri/src/models.rs).
 export type LanguageSetting = 'System'|'Italian'|'English';
 
+// Log verbosity. Mirrors the Rust `LogLevel` enum (src-tauri/src/models.rs).
+export type LogLevel = 'Error'|'Warn'|'Info'|'Debug'|'Trace';
+
 export interface AppConfig {
   work_directory: string|null;
   polling_enabled: boolean;
@@ -68,25 +74,95 @@ export interface AppConfig {
   tray_close_os_notice_shown: boolean;
   theme: ThemeSetting;
   language: LanguageSetting;
+  // http://, https:// or socks5:// proxy for all requests; null = direct.
+  proxy_url: string|null;
+  // Native OS notifications for completed downloads and errata corrige.
+  notifications_enabled: boolean;
+  // Console + log file verbosity, applied immediately by set_config.
+  log_level: LogLevel;
+  // Absolute folder for archived weeks; null = ".archive" in the work directory.
+  archive_directory: string|null;
+  // Archived weeks exempt from retention; changed via pin_week/unpin_week.
+  pinned_weeks: WeekIdentifier[];
+  // How downloads are arranged in the work directory.
+  folder_layout: FolderLayout;
+  // No polls, size lookups or downloads; queued items wait until it's off.
+  offline_mode: boolean;
+  // Extra headers for every request (e.g. Authorization); values are secrets.
+  api_headers: Record<string, string>;
+  // Mirror of the resources API tried when the primary is unreachable.
+  api_fallback_url: string|null;
+  // Re-hash the current week's downloads at launch (slow, opt-in).
+  verify_on_startup: boolean;
+  // Cap on queued downloads (null = unbounded) and what a full queue does
+  // with a new auto download; manual downloads always evict the tail.
+  max_queue_size: number|null;
+  queue_overflow_policy: QueueOverflowPolicy;
+  // Backend-owned: a work directory was picked and a poll succeeded.
+  setup_completed: boolean;
+  // Category -> absolute folder its downloads go to (in week subfolders)
+  // instead of the work directory.
+  category_destinations: Record<string, string>;
+  // Keep .part files in a hidden .incomplete/ subfolder until complete.
+  stage_incomplete_downloads: boolean;
+  // Categories to download first, most important first; empty = FIFO.
+  category_priority: string[];
+  // Local hours auto downloads may start in; null = any time.
+  download_window: DownloadWindow|null;
+  // Total seconds a poll or HEAD request may take (1-600); downloads are exempt.
+  request_timeout_secs: number;
+  // Most pages of a paginated latest-week response followed per poll (1-100).
+  max_poll_pages: number;
+  // Open the destination folder when a download finishes.
+  open_folder_on_complete: boolean;
+  // Latest weeks fetched per poll (1-12); 1 = latest week only.
+  fetch_weeks: number;
+  // Random +/- spread of each background poll wait, in percent (0-50).
+  poll_jitter_percent: number;
+}
+
+// Mirrors the Rust `DownloadWindow` in src-tauri/src/models.rs: hours 0-23,
+// end exclusive, wrapping past midnight when start_hour > end_hour.
+export interface DownloadWindow {
+  start_hour: number;
+  end_hour: number;
 }
 
+// Mirrors the Rust `QueueOverflowPolicy` in src-tauri/src/models.rs.
+export type QueueOverflowPolicy = 'Reject' | 'DropOldest';
+
+// Mirrors the Rust `FolderLayout` in src-tauri/src/models.rs.
+export type FolderLayout = 'ByWeek'|'Flat'|'ByCategory';
+
 export interface AppStatus {
   polling_active: boolean;
   last_poll_time: string|null;  // ISO date string
   current_week: WeekIdentifier|null;
   total_resources: number;
+  // Queued + active downloads and their estimated remaining bytes (cached
+  // sizes only), kept current by the backend queue on every change.
   pending_downloads: number;
+  pending_bytes: number;
   has_superseded_files: boolean;
   // True when the material currently available belongs to a week earlier
   // than the calendar's current week (i.e. the backend hasn't found this
   // week's resources yet). Drives the "material not up to date" banner on
   // the Dashboard; the UI only reads this flag, it never derives it.
   material_week_stale: boolean;
+  // True while the configured work directory is missing (drive unplugged);
+  // auto-downloads are paused until check_work_directory sees it again.
+  work_directory_unavailable: boolean;
+  // True while the download queue is paused: nothing new starts.
+  queue_paused: boolean;
+  // True when the last poll was served by the fallback API endpoint.
+  served_by_fallback: boolean;
 }
 
 export interface ResourceListResponse {
   count: number;
   resources: Resource[];
+  // Next page not fetched (page cap reached); absent when complete.
+  next?: string;
 }
 
 // One category and its resource count from the `categories/counts` endpoint.
@@ -106,6 +182,13 @@ export interface ErrataDetectedPayload {
   resourceIds: number[];
 }
 
+// Payload of the `new-week-available` event, emitted after a poll finds
+// resources for a week newer than the last one announced (persisted in
+// settings.json, so a restart never re-announces). Mirrors the serialized
+// `WeekIdentifier` passed to `emit` in
+// src-tauri/src/services/notifications.rs::announce_new_week.
+export type NewWeekAvailablePayload = WeekIdentifier;
+
 // Payload of the `download-complete` event. Mirrors the
 // `serde_json::json!({ "id", "optimized", ... })` payload emitted in
 // src-tauri/src/services/queue.rs when a download finishes: `optimized` is
@@ -163,6 +246,93 @@ export interface SavingsStats {
   total_saved_bytes: number;
 }
 
+// One distinct category of the currently loaded resources, from the
+// `get_categories` command (most common first). Mirrors the Rust
+// `ResourceCategory` struct (src-tauri/src/commands.rs); `auto_download`
+// reflects the backend's own auto-download matching.
+export interface ResourceCategory {
+  category: string;
+  count: number;
+  auto_download: boolean;
+}
+
+// Argument of the `query_resources` command. Mirrors the Rust
+// `ResourceFilter` struct (src-tauri/src/commands.rs); omitted/null fields
+// match everything, results keep the `get_resources` order.
+export interface ResourceFilter {
+  category?: string|null;
+  text?: string|null;
+  youtube_only?: boolean|null;
+}
+
+// Result of the `cancel_all_downloads` command. Mirrors the Rust
+// `CancelAllResult` struct (src-tauri/src/commands.rs).
+export interface CancelAllResult {
+  cancelled_active: number;
+  cleared_queued: number;
+}
+
+// One completed download from the `get_download_history` command (newest
+// first). Mirrors the Rust `DownloadHistoryEntry` struct
+// (src-tauri/src/models.rs); `bytes` is null when the size couldn't be read.
+export interface DownloadHistoryEntry {
+  resource_id: number;
+  title: string;
+  week: WeekIdentifier;
+  bytes: number|null;
+  duration_secs: number;
+  completed_at: string;  // ISO date string
+  hash: string;
+}
+
+// One location in the `get_diagnostics` result. Mirrors the Rust
+// `PathDiagnostic` struct (src-tauri/src/services/diagnostics.rs); `path` is
+// null when it couldn't be resolved or, for the work directory, isn't set.
+export interface PathDiagnostic {
+  path: string|null;
+  exists: boolean;
+  writable: boolean;
+}
+
+// Result of the `get_diagnostics` command, meant to be pasted into support
+// requests. Mirrors the Rust `Diagnostics` struct.
+export interface Diagnostics {
+  app_version: string;
+  settings_store: PathDiagnostic;
+  cache_store: PathDiagnostic;
+  history_store: PathDiagnostic;
+  work_directory: PathDiagnostic;
+  log_directory: PathDiagnostic;
+  log_file: PathDiagnostic;
+}
+
+// Result of the `import_config` command. Mirrors the Rust
+// `ConfigImportResult` struct (src-tauri/src/commands.rs): `config` is the
+// configuration now in effect; `work_directory_cleared` is true when the
+// imported work directory doesn't exist on this machine, in which case none
+// is set any more and the UI should prompt for a folder.
+export interface ConfigImportResult {
+  config: AppConfig;
+  work_directory_cleared: boolean;
+}
+
+// Result of the `export_state_bundle` command. Mirrors the Rust
+// `BundleSummary` struct (src-tauri/src/services/state_bundle.rs): how many
+// week manifests and log files went into the support bundle.
+export interface BundleSummary {
+  manifests: number;
+  logs: number;
+}
+
+// Result of the dev-only `import_state_bundle` command. Mirrors the Rust
+// `StateBundleImportResult` struct (src-tauri/src/commands.rs). The restored
+// stores take effect after a restart.
+export interface StateBundleImportResult {
+  manifests_restored: number;
+  manifests_skipped: number;
+  work_directory_cleared: boolean;
+}
+
 // Batched per-resource status returned by the `get_resources_status` command.
 // Mirrors the Rust `ResourceStatus` struct (src-tauri/src/commands.rs). The
 // backing HashMap<i64, _> serializes its integer keys as strings, so the
@@ -173,3 +343,188 @@ export interface ResourceStatus {
   file_size: number|null;
   optimized_file_size: number|null;
 }
+
+// Result of the `refresh_resource` command. Mirrors the Rust `RefreshOutcome`
+// enum (src-tauri/src/commands.rs): `NotModified` when the server answered
+// 304 for the local copy, `Queued` when a fresh download was queued.
+export type RefreshOutcome = 'NotModified'|'Queued';
+
+// One file returned by the `get_week_files` command. Mirrors the Rust
+// `WeekFile` struct (src-tauri/src/services/manifest.rs): `resource_id` is
+// null for a file no resource claimed (a stray file in the week folder).
+export interface WeekFile {
+  filename: string;
+  size: number;
+  modified: string|null;  // ISO date string
+  resource_id: number|null;
+}
+
+// Result of the `get_week_stats` command. Mirrors the Rust `WeekStats`
+// struct (src-tauri/src/services/manifest.rs).
+export interface WeekStats {
+  file_count: number;
+  total_bytes: number;
+  total_download_secs: number;
+  avg_mbps: number;
+}
+
+// Payload of the `overall-progress` event: bytes received across all active
+// downloads against the bytes expected for active + queued ones (cached
+// sizes for those not started yet). Mirrors the Rust `OverallProgress`
+// struct (src-tauri/src/services/queue.rs); percent is 0 when idle.
+export interface OverallProgress {
+  downloaded_bytes: number;
+  total_bytes: number;
+  percent: number;
+}
+
+// Live progress of one active download, from `get_active_progress` (keyed
+// by resource id). Mirrors the Rust `ActiveProgress` struct
+// (src-tauri/src/services/download.rs); bps is the attempt's average rate.
+export interface ActiveProgress {
+  progress: number;
+  bytes: number;
+  total: number;
+  bps: number;
+}
+
+export type DownloadPhase =
+  | 'started'
+  | 'progress'
+  | 'completed'
+  | 'failed'
+  | 'paused'
+  | 'stopped'
+  | 'cancelled';
+
+// Payload of the unified `download-event` channel, one per download step.
+// Mirrors the Rust `DownloadEvent` in
+// src-tauri/src/services/download_events.rs; optional fields are absent
+// when they don't apply to the phase. timestamp is RFC 3339.
+export interface DownloadEvent {
+  id: number;
+  phase: DownloadPhase;
+  progress?: number;
+  bytes?: number;
+  total?: number;
+  error?: string;
+  timestamp: string;
+}
+
+// Result of the `clear_caches` command (also the `caches-cleared` event
+// payload). Mirrors the Rust `ClearedCaches` struct in
+// src-tauri/src/commands.rs.
+export interface ClearedCaches {
+  cleared_resources: boolean;
+  cleared_file_sizes: boolean;
+  cleared_thumbnails: number;
+}
+
+// Result of the `rebuild_manifests` command. Mirrors the Rust
+// `ManifestRebuildSummary` struct (src-tauri/src/commands.rs): week folders
+// rewritten, files matched to a resource, and how many of those were newly
+// added to the download registry.
+export interface ManifestRebuildSummary {
+  weeks: number;
+  files: number;
+  registered: number;
+}
+
+// One group of `find_duplicate_files`: files with the same content hash.
+// Mirrors the Rust `DuplicateGroup` in src-tauri/src/services/manifest.rs.
+export interface DuplicateGroup {
+  hash: string;
+  // Size of one copy.
+  bytes: number;
+  paths: string[];
+}
+
+// Payload of the `resource-anomaly` event, emitted when a poll response is
+// inconsistent. `kind` is 'duplicate-ids' when the API listed the same
+// resource id more than once (the newest entry was kept). Mirrors the
+// `serde_json::json!({ "kind", "resourceIds" })` payload in
+// src-tauri/src/services/polling.rs::poll_once.
+export interface ResourceAnomalyPayload {
+  kind: 'duplicate-ids';
+  resourceIds: number[];
+}
+
+// Result of the `estimate_download_size` command. Mirrors the Rust
+// `DownloadSizeEstimate` in src-tauri/src/commands.rs.
+export interface DownloadSizeEstimate {
+  total_bytes: number;
+  unknown_count: number;
+}
+
+// Why `check_api_connectivity` got no HTTP response. Mirrors the Rust
+// `ConnectivityFailure` in src-tauri/src/commands.rs.
+export type ConnectivityFailure = 'Dns' | 'Connection' | 'Timeout' | 'Other';
+
+// Result of the `check_api_connectivity` command. Mirrors the Rust
+// `ApiConnectivity` in src-tauri/src/commands.rs.
+export interface ApiConnectivity {
+  reachable: boolean;
+  latency_ms: number | null;
+  status: number | null;
+  failure: ConnectivityFailure | null;
+}
+
+// One entry of the `resource-parse-errors` event payload: a resource the
+// poll skipped because it failed to decode. Mirrors the Rust
+// `ResourceParseError` in src-tauri/src/services/polling.rs.
+export interface ResourceParseError {
+  index: number;
+  id: number | null;
+  error: string;
+}
+
+// Payload of the `resource-count-mismatch` event: the poll response's
+// `count` disagreed with the resources it carried. Mirrors the Rust
+// `ResourceCountMismatch` in src-tauri/src/services/polling.rs.
+export interface ResourceCountMismatch {
+  reported: number;
+  received: number;
+}
+
+// Payload of the `rate-limited` event: a request answered 429/503 with a
+// `Retry-After` and is held back `wait_secs`. Mirrors the Rust `RateLimited`
+// in src-tauri/src/services/http.rs.
+export interface RateLimited {
+  source: 'poll' | 'download' | 'file_size';
+  url: string;
+  resource_id?: number;
+  wait_secs: number;
+}
+
+// Payload of the `integrity-issue` event: a downloaded file no longer
+// matching its recorded hash (it is deleted and queued again). Mirrors the
+// Rust `IntegrityIssue` in src-tauri/src/services/integrity.rs.
+export interface IntegrityIssue {
+  resource_id: number;
+  path: string;
+  expected: string;
+  actual: string|null;
+}
+
+// Payload of the `queue-overflow` event. Mirrors the Rust `QueueOverflow`
+// in src-tauri/src/services/queue.rs.
+export interface QueueOverflow {
+  limit: number;
+  rejected_id: number|null;
+  evicted_id: number|null;
+}
+
+// One entry of the `get_resources_grouped` result (newest week first).
+// Mirrors the Rust `WeekGroup` in src-tauri/src/commands.rs.
+export interface WeekGroup {
+  week: WeekIdentifier;
+  resources: Resource[];
+}
+
+// Result of the `get_setup_state` command, for first-run onboarding.
+// Mirrors the Rust `SetupState` in src-tauri/src/commands.rs.
+export interface SetupState {
+  setup_completed: boolean;
+  needs_work_directory: boolean;
+  needs_first_poll: boolean;
+}